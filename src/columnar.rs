@@ -0,0 +1,172 @@
+//! Module with the definition of the columnar (one `Vec` per field) layout of the records
+use crate::record::field::{Date, DateTime, FieldType, FieldValue};
+
+/// The values of one field for all the records,
+/// with one variant per type of [FieldValue](enum.FieldValue.html)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    Character(Vec<Option<String>>),
+    Numeric(Vec<Option<f64>>),
+    Logical(Vec<Option<bool>>),
+    Date(Vec<Option<Date>>),
+    Float(Vec<Option<f32>>),
+    Integer(Vec<Option<i32>>),
+    Currency(Vec<Option<f64>>),
+    DateTime(Vec<Option<DateTime>>),
+    Double(Vec<Option<f64>>),
+    Memo(Vec<String>),
+    General(Vec<Vec<u8>>),
+    Picture(Vec<Vec<u8>>),
+    Varchar(Vec<Option<String>>),
+    Varbinary(Vec<Option<Vec<u8>>>),
+}
+
+impl Column {
+    /// Creates an empty column for the values of the given type,
+    /// `None` for the types that do not have values (`_NullFlags`)
+    pub(crate) fn new(field_type: FieldType) -> Option<Self> {
+        let column = match field_type {
+            FieldType::Character => Column::Character(vec![]),
+            FieldType::Numeric => Column::Numeric(vec![]),
+            FieldType::Logical => Column::Logical(vec![]),
+            FieldType::Date => Column::Date(vec![]),
+            FieldType::Float => Column::Float(vec![]),
+            FieldType::Integer => Column::Integer(vec![]),
+            FieldType::Currency => Column::Currency(vec![]),
+            FieldType::DateTime => Column::DateTime(vec![]),
+            FieldType::Double => Column::Double(vec![]),
+            FieldType::Memo => Column::Memo(vec![]),
+            FieldType::General => Column::General(vec![]),
+            FieldType::Picture => Column::Picture(vec![]),
+            FieldType::Varchar => Column::Varchar(vec![]),
+            FieldType::Varbinary => Column::Varbinary(vec![]),
+            FieldType::NullFlags => return None,
+        };
+        Some(column)
+    }
+
+    /// Appends the value to the column,
+    /// the value is given back if its type is not the one of the column
+    pub(crate) fn push(&mut self, value: FieldValue) -> Result<(), FieldValue> {
+        match (self, value) {
+            (Column::Character(values), FieldValue::Character(v)) => values.push(v),
+            (Column::Numeric(values), FieldValue::Numeric(v)) => values.push(v),
+            (Column::Logical(values), FieldValue::Logical(v)) => values.push(v),
+            (Column::Date(values), FieldValue::Date(v)) => values.push(v),
+            (Column::Float(values), FieldValue::Float(v)) => values.push(v),
+            (Column::Integer(values), FieldValue::Integer(v)) => values.push(v),
+            (Column::Currency(values), FieldValue::Currency(v)) => values.push(v),
+            (Column::DateTime(values), FieldValue::DateTime(v)) => values.push(v),
+            (Column::Double(values), FieldValue::Double(v)) => values.push(v),
+            (Column::Memo(values), FieldValue::Memo(v)) => values.push(v),
+            (Column::General(values), FieldValue::General(v)) => values.push(v),
+            (Column::Picture(values), FieldValue::Picture(v)) => values.push(v),
+            (Column::Varchar(values), FieldValue::Varchar(v)) => values.push(v),
+            (Column::Varbinary(values), FieldValue::Varbinary(v)) => values.push(v),
+            (_, value) => return Err(value),
+        }
+        Ok(())
+    }
+
+    /// Returns the number of values in the column
+    pub fn len(&self) -> usize {
+        match self {
+            Column::Character(values) | Column::Varchar(values) => values.len(),
+            Column::Numeric(values) | Column::Currency(values) | Column::Double(values) => {
+                values.len()
+            }
+            Column::Logical(values) => values.len(),
+            Column::Date(values) => values.len(),
+            Column::Float(values) => values.len(),
+            Column::Integer(values) => values.len(),
+            Column::DateTime(values) => values.len(),
+            Column::Memo(values) => values.len(),
+            Column::General(values) | Column::Picture(values) => values.len(),
+            Column::Varbinary(values) => values.len(),
+        }
+    }
+
+    /// Returns whether the column has no values
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value at `index`
+    pub fn get(&self, index: usize) -> Option<FieldValue> {
+        let value = match self {
+            Column::Character(values) => FieldValue::Character(values.get(index)?.clone()),
+            Column::Numeric(values) => FieldValue::Numeric(*values.get(index)?),
+            Column::Logical(values) => FieldValue::Logical(*values.get(index)?),
+            Column::Date(values) => FieldValue::Date(*values.get(index)?),
+            Column::Float(values) => FieldValue::Float(*values.get(index)?),
+            Column::Integer(values) => FieldValue::Integer(*values.get(index)?),
+            Column::Currency(values) => FieldValue::Currency(*values.get(index)?),
+            Column::DateTime(values) => FieldValue::DateTime(*values.get(index)?),
+            Column::Double(values) => FieldValue::Double(*values.get(index)?),
+            Column::Memo(values) => FieldValue::Memo(values.get(index)?.clone()),
+            Column::General(values) => FieldValue::General(values.get(index)?.clone()),
+            Column::Picture(values) => FieldValue::Picture(values.get(index)?.clone()),
+            Column::Varchar(values) => FieldValue::Varchar(values.get(index)?.clone()),
+            Column::Varbinary(values) => FieldValue::Varbinary(values.get(index)?.clone()),
+        };
+        Some(value)
+    }
+}
+
+/// The records of a table in a columnar layout: the values
+/// of each field are stored together, in a [Column](enum.Column.html)
+///
+/// It is returned by [Reader::read_columnar](struct.Reader.html#method.read_columnar)
+/// and written by [TableWriter::write_columnar](struct.TableWriter.html#method.write_columnar).
+///
+/// # Example
+///
+/// ```
+/// use dbase::{Column, Columns};
+///
+/// let mut columns = Columns::default();
+/// columns.insert("Name".to_owned(), Column::Character(vec![Some("Ferris".to_owned()), None]));
+/// columns.insert("Age".to_owned(), Column::Numeric(vec![Some(12.0), Some(3.0)]));
+/// assert_eq!(columns.num_records(), 2);
+/// assert_eq!(columns.get("Age"), Some(&Column::Numeric(vec![Some(12.0), Some(3.0)])));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Columns {
+    pub(crate) names: Vec<String>,
+    pub(crate) columns: Vec<Column>,
+}
+
+impl Columns {
+    /// Adds the column of a field, replacing the previous column of the field if any
+    pub fn insert(&mut self, field_name: String, column: Column) -> Option<Column> {
+        match self.names.iter().position(|name| *name == field_name) {
+            Some(index) => Some(std::mem::replace(&mut self.columns[index], column)),
+            None => {
+                self.names.push(field_name);
+                self.columns.push(column);
+                None
+            }
+        }
+    }
+
+    /// Returns the column of the field with the given name
+    pub fn get(&self, field_name: &str) -> Option<&Column> {
+        self.names
+            .iter()
+            .position(|name| name == field_name)
+            .map(|index| &self.columns[index])
+    }
+
+    /// Returns an iterator over the name of the fields and their columns
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Column)> {
+        self.names
+            .iter()
+            .map(String::as_str)
+            .zip(self.columns.iter())
+    }
+
+    /// Returns the number of records
+    pub fn num_records(&self) -> usize {
+        self.columns.first().map_or(0, Column::len)
+    }
+}
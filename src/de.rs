@@ -6,7 +6,7 @@ use serde::Deserializer;
 
 use crate::{ErrorKind, FieldIOError, FieldIterator, FieldValue, ReadableRecord};
 
-impl<'de, 'a, 'f, R: Read + Seek> SeqAccess<'de> for &mut FieldIterator<'a, R> {
+impl<'de, 'a, R: Read + Seek> SeqAccess<'de> for &mut FieldIterator<'a, R> {
     type Error = FieldIOError;
 
     fn next_element_seed<T>(
@@ -25,7 +25,7 @@ impl<'de, 'a, 'f, R: Read + Seek> SeqAccess<'de> for &mut FieldIterator<'a, R> {
 }
 
 //TODO maybe we can deserialize numbers other than f32 & f64 by converting using TryFrom
-impl<'de, 'a, 'f, T: Read + Seek> Deserializer<'de> for &mut FieldIterator<'a, T> {
+impl<'de, 'a, T: Read + Seek> Deserializer<'de> for &mut FieldIterator<'a, T> {
     type Error = FieldIOError;
 
     fn deserialize_any<V>(self, _visitor: V) -> Result<<V as Visitor<'de>>::Value, Self::Error>
@@ -0,0 +1,247 @@
+//! Module with the code pages the text of a dBase file can be converted from and to
+
+/// The language drivers (code pages) supported when converting the text of a file
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LanguageDriver {
+    /// U.S. MS-DOS (code page 437)
+    Cp437,
+    /// International MS-DOS (code page 850)
+    Cp850,
+    /// Windows ANSI (code page 1252)
+    Cp1252,
+}
+
+impl LanguageDriver {
+    /// Returns the language driver of the given code page, if it is supported
+    pub fn from_code_page(code_page: u16) -> Option<Self> {
+        match code_page {
+            437 => Some(LanguageDriver::Cp437),
+            850 => Some(LanguageDriver::Cp850),
+            1252 => Some(LanguageDriver::Cp1252),
+            _ => None,
+        }
+    }
+
+    /// Returns the code page number
+    pub fn code_page(self) -> u16 {
+        match self {
+            LanguageDriver::Cp437 => 437,
+            LanguageDriver::Cp850 => 850,
+            LanguageDriver::Cp1252 => 1252,
+        }
+    }
+
+    /// Returns the value of the language driver byte of the header
+    /// (`code_page_mark`) that identifies this code page
+    pub fn code_page_mark(self) -> u8 {
+        match self {
+            LanguageDriver::Cp437 => 0x01,
+            LanguageDriver::Cp850 => 0x02,
+            LanguageDriver::Cp1252 => 0x03,
+        }
+    }
+
+    fn high_chars(self) -> &'static [char; 128] {
+        match self {
+            LanguageDriver::Cp437 => &CP437_HIGH,
+            LanguageDriver::Cp850 => &CP850_HIGH,
+            LanguageDriver::Cp1252 => &CP1252_HIGH,
+        }
+    }
+
+    /// Decodes bytes encoded with this code page
+    pub fn decode(self, bytes: &[u8]) -> String {
+        let high_chars = self.high_chars();
+        bytes
+            .iter()
+            .map(|&byte| match byte {
+                0x80..=0xFF => high_chars[(byte - 0x80) as usize],
+                _ => char::from(byte),
+            })
+            .collect()
+    }
+
+    /// Encodes the text with this code page,
+    /// characters that the code page cannot represent are replaced by '?'
+    pub fn encode(self, text: &str) -> Vec<u8> {
+        let high_chars = self.high_chars();
+        text.chars()
+            .map(|c| match c {
+                '\u{0}'..='\u{7F}' => c as u8,
+                _ => high_chars
+                    .iter()
+                    .position(|&high| high == c)
+                    .map_or(b'?', |index| 0x80 + index as u8),
+            })
+            .collect()
+    }
+}
+
+/// The encoding used by the [Reader](struct.Reader.html) to decode
+/// the text of Character and Memo fields
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Encoding {
+    /// Decode the text as UTF-8, invalid sequences are replaced by `U+FFFD`
+    Utf8Lossy,
+    /// Decode the text with the given code page
+    ///
+    /// Code pages 437, 850 and 1252 are always supported, the other
+    /// Windows, Mac and Asian code pages require the `encoding` feature.
+    /// The text of an unsupported code page is decoded as UTF-8 (lossy).
+    CodePage(u16),
+    /// Decode the text with the code page declared by the language driver
+    /// byte of the header (see [Header::code_page](struct.Header.html#method.code_page)),
+    /// UTF-8 (lossy) is used when the file does not declare a supported code page
+    FromHeader,
+}
+
+impl Encoding {
+    /// Returns whether text encoded with the given code page can be decoded
+    pub fn is_code_page_supported(code_page: u16) -> bool {
+        LanguageDriver::from_code_page(code_page).is_some()
+            || encoding_rs_code_page_supported(code_page)
+    }
+
+    /// Decodes the bytes, `FromHeader` decodes them as UTF-8 as there
+    /// is no header to read the code page from
+    pub fn decode(self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Utf8Lossy | Encoding::FromHeader => {
+                String::from_utf8_lossy(bytes).into_owned()
+            }
+            Encoding::CodePage(code_page) => {
+                if let Some(driver) = LanguageDriver::from_code_page(code_page) {
+                    driver.decode(bytes)
+                } else {
+                    decode_with_encoding_rs(code_page, bytes)
+                        .unwrap_or_else(|| String::from_utf8_lossy(bytes).into_owned())
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "encoding")]
+fn encoding_rs_for_code_page(code_page: u16) -> Option<&'static encoding_rs::Encoding> {
+    match code_page {
+        866 => Some(encoding_rs::IBM866),
+        874 => Some(encoding_rs::WINDOWS_874),
+        932 => Some(encoding_rs::SHIFT_JIS),
+        936 => Some(encoding_rs::GBK),
+        949 => Some(encoding_rs::EUC_KR),
+        950 => Some(encoding_rs::BIG5),
+        1250 => Some(encoding_rs::WINDOWS_1250),
+        1251 => Some(encoding_rs::WINDOWS_1251),
+        1252 => Some(encoding_rs::WINDOWS_1252),
+        1253 => Some(encoding_rs::WINDOWS_1253),
+        1254 => Some(encoding_rs::WINDOWS_1254),
+        1255 => Some(encoding_rs::WINDOWS_1255),
+        1256 => Some(encoding_rs::WINDOWS_1256),
+        1257 => Some(encoding_rs::WINDOWS_1257),
+        1258 => Some(encoding_rs::WINDOWS_1258),
+        10000 => Some(encoding_rs::MACINTOSH),
+        10007 => Some(encoding_rs::X_MAC_CYRILLIC),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "encoding")]
+fn encoding_rs_code_page_supported(code_page: u16) -> bool {
+    encoding_rs_for_code_page(code_page).is_some()
+}
+
+#[cfg(not(feature = "encoding"))]
+fn encoding_rs_code_page_supported(_code_page: u16) -> bool {
+    false
+}
+
+#[cfg(feature = "encoding")]
+fn decode_with_encoding_rs(code_page: u16, bytes: &[u8]) -> Option<String> {
+    encoding_rs_for_code_page(code_page)
+        .map(|encoding| encoding.decode_without_bom_handling(bytes).0.into_owned())
+}
+
+#[cfg(not(feature = "encoding"))]
+fn decode_with_encoding_rs(_code_page: u16, _bytes: &[u8]) -> Option<String> {
+    None
+}
+
+const CP437_HIGH: [char; 128] = [
+    '\u{C7}', '\u{FC}', '\u{E9}', '\u{E2}', '\u{E4}', '\u{E0}', '\u{E5}', '\u{E7}', '\u{EA}',
+    '\u{EB}', '\u{E8}', '\u{EF}', '\u{EE}', '\u{EC}', '\u{C4}', '\u{C5}', '\u{C9}', '\u{E6}',
+    '\u{C6}', '\u{F4}', '\u{F6}', '\u{F2}', '\u{FB}', '\u{F9}', '\u{FF}', '\u{D6}', '\u{DC}',
+    '\u{A2}', '\u{A3}', '\u{A5}', '\u{20A7}', '\u{192}', '\u{E1}', '\u{ED}', '\u{F3}', '\u{FA}',
+    '\u{F1}', '\u{D1}', '\u{AA}', '\u{BA}', '\u{BF}', '\u{2310}', '\u{AC}', '\u{BD}', '\u{BC}',
+    '\u{A1}', '\u{AB}', '\u{BB}', '\u{2591}', '\u{2592}', '\u{2593}', '\u{2502}', '\u{2524}',
+    '\u{2561}', '\u{2562}', '\u{2556}', '\u{2555}', '\u{2563}', '\u{2551}', '\u{2557}', '\u{255D}',
+    '\u{255C}', '\u{255B}', '\u{2510}', '\u{2514}', '\u{2534}', '\u{252C}', '\u{251C}', '\u{2500}',
+    '\u{253C}', '\u{255E}', '\u{255F}', '\u{255A}', '\u{2554}', '\u{2569}', '\u{2566}', '\u{2560}',
+    '\u{2550}', '\u{256C}', '\u{2567}', '\u{2568}', '\u{2564}', '\u{2565}', '\u{2559}', '\u{2558}',
+    '\u{2552}', '\u{2553}', '\u{256B}', '\u{256A}', '\u{2518}', '\u{250C}', '\u{2588}', '\u{2584}',
+    '\u{258C}', '\u{2590}', '\u{2580}', '\u{3B1}', '\u{DF}', '\u{393}', '\u{3C0}', '\u{3A3}',
+    '\u{3C3}', '\u{B5}', '\u{3C4}', '\u{3A6}', '\u{398}', '\u{3A9}', '\u{3B4}', '\u{221E}',
+    '\u{3C6}', '\u{3B5}', '\u{2229}', '\u{2261}', '\u{B1}', '\u{2265}', '\u{2264}', '\u{2320}',
+    '\u{2321}', '\u{F7}', '\u{2248}', '\u{B0}', '\u{2219}', '\u{B7}', '\u{221A}', '\u{207F}',
+    '\u{B2}', '\u{25A0}', '\u{A0}',
+];
+const CP850_HIGH: [char; 128] = [
+    '\u{C7}', '\u{FC}', '\u{E9}', '\u{E2}', '\u{E4}', '\u{E0}', '\u{E5}', '\u{E7}', '\u{EA}',
+    '\u{EB}', '\u{E8}', '\u{EF}', '\u{EE}', '\u{EC}', '\u{C4}', '\u{C5}', '\u{C9}', '\u{E6}',
+    '\u{C6}', '\u{F4}', '\u{F6}', '\u{F2}', '\u{FB}', '\u{F9}', '\u{FF}', '\u{D6}', '\u{DC}',
+    '\u{F8}', '\u{A3}', '\u{D8}', '\u{D7}', '\u{192}', '\u{E1}', '\u{ED}', '\u{F3}', '\u{FA}',
+    '\u{F1}', '\u{D1}', '\u{AA}', '\u{BA}', '\u{BF}', '\u{AE}', '\u{AC}', '\u{BD}', '\u{BC}',
+    '\u{A1}', '\u{AB}', '\u{BB}', '\u{2591}', '\u{2592}', '\u{2593}', '\u{2502}', '\u{2524}',
+    '\u{C1}', '\u{C2}', '\u{C0}', '\u{A9}', '\u{2563}', '\u{2551}', '\u{2557}', '\u{255D}',
+    '\u{A2}', '\u{A5}', '\u{2510}', '\u{2514}', '\u{2534}', '\u{252C}', '\u{251C}', '\u{2500}',
+    '\u{253C}', '\u{E3}', '\u{C3}', '\u{255A}', '\u{2554}', '\u{2569}', '\u{2566}', '\u{2560}',
+    '\u{2550}', '\u{256C}', '\u{A4}', '\u{F0}', '\u{D0}', '\u{CA}', '\u{CB}', '\u{C8}', '\u{131}',
+    '\u{CD}', '\u{CE}', '\u{CF}', '\u{2518}', '\u{250C}', '\u{2588}', '\u{2584}', '\u{A6}',
+    '\u{CC}', '\u{2580}', '\u{D3}', '\u{DF}', '\u{D4}', '\u{D2}', '\u{F5}', '\u{D5}', '\u{B5}',
+    '\u{FE}', '\u{DE}', '\u{DA}', '\u{DB}', '\u{D9}', '\u{FD}', '\u{DD}', '\u{AF}', '\u{B4}',
+    '\u{AD}', '\u{B1}', '\u{2017}', '\u{BE}', '\u{B6}', '\u{A7}', '\u{F7}', '\u{B8}', '\u{B0}',
+    '\u{A8}', '\u{B7}', '\u{B9}', '\u{B3}', '\u{B2}', '\u{25A0}', '\u{A0}',
+];
+
+/// The bytes of Windows-1252 that are not assigned are mapped to the C1 control characters like Latin-1
+const CP1252_HIGH: [char; 128] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+    '\u{A0}', '\u{A1}', '\u{A2}', '\u{A3}', '\u{A4}', '\u{A5}', '\u{A6}', '\u{A7}', '\u{A8}',
+    '\u{A9}', '\u{AA}', '\u{AB}', '\u{AC}', '\u{AD}', '\u{AE}', '\u{AF}', '\u{B0}', '\u{B1}',
+    '\u{B2}', '\u{B3}', '\u{B4}', '\u{B5}', '\u{B6}', '\u{B7}', '\u{B8}', '\u{B9}', '\u{BA}',
+    '\u{BB}', '\u{BC}', '\u{BD}', '\u{BE}', '\u{BF}', '\u{C0}', '\u{C1}', '\u{C2}', '\u{C3}',
+    '\u{C4}', '\u{C5}', '\u{C6}', '\u{C7}', '\u{C8}', '\u{C9}', '\u{CA}', '\u{CB}', '\u{CC}',
+    '\u{CD}', '\u{CE}', '\u{CF}', '\u{D0}', '\u{D1}', '\u{D2}', '\u{D3}', '\u{D4}', '\u{D5}',
+    '\u{D6}', '\u{D7}', '\u{D8}', '\u{D9}', '\u{DA}', '\u{DB}', '\u{DC}', '\u{DD}', '\u{DE}',
+    '\u{DF}', '\u{E0}', '\u{E1}', '\u{E2}', '\u{E3}', '\u{E4}', '\u{E5}', '\u{E6}', '\u{E7}',
+    '\u{E8}', '\u{E9}', '\u{EA}', '\u{EB}', '\u{EC}', '\u{ED}', '\u{EE}', '\u{EF}', '\u{F0}',
+    '\u{F1}', '\u{F2}', '\u{F3}', '\u{F4}', '\u{F5}', '\u{F6}', '\u{F7}', '\u{F8}', '\u{F9}',
+    '\u{FA}', '\u{FB}', '\u{FC}', '\u{FD}', '\u{FE}', '\u{FF}',
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn convert_between_code_pages() {
+        let text = "Ça coûte 5€";
+        let cp850 = LanguageDriver::Cp850.encode(text);
+        assert_eq!(cp850, b"\x80a co\x96te 5?");
+        assert_eq!(LanguageDriver::Cp850.decode(&cp850), "Ça coûte 5?");
+
+        let cp1252 = LanguageDriver::Cp1252.encode(text);
+        assert_eq!(cp1252, b"\xC7a co\xFBte 5\x80");
+        assert_eq!(LanguageDriver::Cp1252.decode(&cp1252), text);
+    }
+
+    #[test]
+    fn decode_with_encoding() {
+        assert_eq!(Encoding::Utf8Lossy.decode(b"Caf\xE9"), "Caf\u{FFFD}");
+        assert_eq!(Encoding::CodePage(1252).decode(b"Caf\xE9"), "Café");
+        assert_eq!(Encoding::CodePage(850).decode(b"Caf\x82"), "Café");
+        assert!(!Encoding::is_code_page_supported(0));
+    }
+}
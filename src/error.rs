@@ -15,17 +15,84 @@ pub enum ErrorKind {
     MissingMemoFile,
     /// Something went wrong when we tried to open the associated memo file
     ErrorOpeningMemoFile(std::io::Error),
+    /// Something went wrong when we tried to open or read the associated index file
+    ErrorOpeningIndexFile(std::io::Error),
     /// The conversion from a FieldValue to another type could not be made
     BadConversion(FieldConversionError),
     /// End of the record, there are no more fields
     EndOfRecord,
     /// Not all the fields declared to the writer were given
     NotEnoughFields,
-    /// More fields than expected were given to the writer
+    /// More fields than expected were given to the writer,
+    /// or more fields than the file version allows were declared
     TooManyFields,
+    /// The size of a record exceeds the maximum allowed by the file version
+    RecordTooLarge,
+    /// The sum of the fields lengths exceeds the size of a record given by the header
+    FieldsExceedRecordSize,
+    /// Many fields of the file have this name
+    DuplicateFieldName(String),
+    /// A Numeric or Float value is partially made of `*` (the overflow marker)
+    InvalidNumericValue(String),
     /// The type of the value for the field is not compatible with the
     /// dbase field's type
     IncompatibleType,
+    /// The value does not fit in the length of the field
+    ValueTooLong,
+    /// The length of a field exceeds the maximum allowed by the file dialect
+    FieldTooLong,
+    /// The name of a field is longer than the 10 bytes a file can store
+    FieldNameTooLong,
+    /// The length of a field is zero
+    EmptyField,
+    /// The date does not exist (e.g. the 31st of February)
+    InvalidDate,
+    /// The hours, minutes or seconds of a time are out of range
+    InvalidTime,
+    /// The value does not fit in an Integer field (i32)
+    IntegerOutOfRange(i64),
+    /// The value is not a number or does not fit in a Currency field
+    /// (an i64 of ten-thousandths)
+    CurrencyOutOfRange(f64),
+    /// The memo file ends before the data of a block a field refers to
+    /// (e.g. the file was not fully downloaded)
+    TruncatedMemoFile {
+        /// Index of the block
+        index: u32,
+        /// Offset up to which the memo file must extend to hold the data of the block
+        needed_offset: u64,
+        /// Length of the memo file
+        file_len: u64,
+    },
+    /// The data of a memo block is longer than the maximum set with
+    /// [ReadingOptions::max_memo_length](struct.ReadingOptions.html#method.max_memo_length)
+    MemoTooLong {
+        /// Index of the block
+        index: u32,
+        /// Length of the data, as given by the header of the block
+        length: u32,
+        /// The maximum length
+        max: u32,
+    },
+    /// The value needs a bit of the _NullFlags field (to be null, or shorter than the field)
+    /// that the field does not have
+    NoNullFlagsBit,
+    /// The length of the file does not match the number of records given by its header
+    FileLengthMismatch {
+        /// Length expected from the header, without the end of file marker
+        expected: u64,
+        /// Actual length of the file
+        actual: u64,
+    },
+    /// A column does not have as many values as the other ones
+    ColumnLengthMismatch {
+        /// Name of the field of the column
+        field_name: String,
+        /// Number of values of the first column
+        expected: u32,
+        /// Number of values of the column
+        actual: u32,
+    },
     Message(String),
 }
 
@@ -160,13 +227,39 @@ impl std::error::Error for FieldIOError {
             ErrorKind::ErrorOpeningMemoFile(_) => {
                 "An error occurred when trying to open the memo file"
             }
+            ErrorKind::ErrorOpeningIndexFile(_) => {
+                "An error occurred when trying to open the index file"
+            }
             ErrorKind::BadConversion(_) => "The convertion cannot be made",
             ErrorKind::EndOfRecord => "End of record reached, no more fields left",
             ErrorKind::NotEnoughFields => {
                 "The writer did not expected that many fields for the record"
             }
             ErrorKind::TooManyFields => "The writer expected to write more fields for the record",
+            ErrorKind::RecordTooLarge => "The record size exceeds the maximum allowed",
+            ErrorKind::FieldsExceedRecordSize => {
+                "The fields lengths exceed the record size of the header"
+            }
+            ErrorKind::DuplicateFieldName(_) => "Many fields have the same name",
+            ErrorKind::InvalidNumericValue(_) => "The numeric value is partially overflowed",
             ErrorKind::IncompatibleType => "The types are not compatible",
+            ErrorKind::ValueTooLong => "The value is longer than the field",
+            ErrorKind::FieldTooLong => "The field is longer than the dialect allows",
+            ErrorKind::FieldNameTooLong => "The field name is longer than 10 bytes",
+            ErrorKind::EmptyField => "The length of the field is zero",
+            ErrorKind::InvalidDate => "The date is not a valid one",
+            ErrorKind::InvalidTime => "The time is not a valid one",
+            ErrorKind::IntegerOutOfRange(_) => "The value does not fit in an Integer field",
+            ErrorKind::CurrencyOutOfRange(_) => "The value does not fit in a Currency field",
+            ErrorKind::TruncatedMemoFile { .. } => "The memo file is truncated",
+            ErrorKind::MemoTooLong { .. } => "The data of the memo is too long",
+            ErrorKind::NoNullFlagsBit => {
+                "The field has no bit in the _NullFlags field to mark the value"
+            }
+            ErrorKind::FileLengthMismatch { .. } => {
+                "The length of the file does not match its header"
+            }
+            ErrorKind::ColumnLengthMismatch { .. } => "The columns do not have the same length",
             ErrorKind::Message(ref msg) => msg,
         }
     }
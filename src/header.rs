@@ -1,11 +1,13 @@
-use chrono;
-
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
 use std::io::{Read, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::record::field::{Date, MemoFileType};
 
+/// Julian day number of 1970-01-01
+const JULIAN_DAY_NUMBER_OF_UNIX_EPOCH: i32 = 2_440_588;
+
 /// Known version of dBase files
 #[derive(Debug, Copy, Clone)]
 pub enum Version {
@@ -18,7 +20,9 @@ pub enum Version {
 }
 
 impl Version {
-    pub(crate) fn supported_memo_type(self) -> Option<MemoFileType> {
+    /// Returns the type of memo file used by this version,
+    /// `None` if it does not support memo fields
+    pub fn supported_memo_type(self) -> Option<MemoFileType> {
         match self {
             Version::FoxBase => Some(MemoFileType::FoxBaseMemo),
             Version::DBase3 {
@@ -44,12 +48,50 @@ impl Version {
         }
     }
 
-    pub(crate) fn is_visual_fox_pro(self) -> bool {
+    /// Maximum number of fields a record can have
+    pub(crate) fn max_num_fields(self) -> usize {
         match self {
-            Version::VisualFoxPro => true,
-            _ => false,
+            Version::FoxBase | Version::DBase3 { .. } => 128,
+            _ => 255,
         }
     }
+
+    /// Maximum size in bytes of a record (deletion flag included)
+    pub(crate) fn max_record_size(self) -> usize {
+        match self {
+            Version::FoxBase | Version::DBase3 { .. } => 4000,
+            Version::DBase4 { .. } => 32767,
+            _ => 65500,
+        }
+    }
+
+    /// Code page used by the programs of this version when
+    /// the language driver byte of the header is not set
+    pub(crate) fn default_code_page(self) -> Option<u16> {
+        match self {
+            Version::VisualFoxPro => Some(1252),
+            Version::FoxBase | Version::FoxPro2 { .. } => Some(437),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_visual_fox_pro(self) -> bool {
+        matches!(self, Version::VisualFoxPro)
+    }
+
+    /// Whether the Character fields longer than 255 bytes store the high byte
+    /// of their length in place of the number of decimals.
+    ///
+    /// It is an extension of FoxPro, also used by Clipper which writes dBase III files.
+    pub(crate) fn has_long_character_fields(self) -> bool {
+        matches!(
+            self,
+            Version::FoxBase
+                | Version::DBase3 { .. }
+                | Version::VisualFoxPro
+                | Version::FoxPro2 { .. }
+        )
+    }
 }
 
 impl From<Version> for u8 {
@@ -92,7 +134,7 @@ impl From<u8> for Version {
             },
             // Each version has different feature (varchar / autoincrement)
             // but we don't support that for now
-            0x30 | 0x31 | 0x32 => Version::VisualFoxPro,
+            0x30..=0x32 => Version::VisualFoxPro,
             // Same here these different version num means that some features are different
             0x8b | 0xcb => Version::DBase4 {
                 supports_memo: true,
@@ -111,14 +153,26 @@ impl From<u8> for Version {
     }
 }
 
+/// The flags stored in byte 28 of the header
 #[derive(Debug, Copy, Clone)]
 pub struct TableFlags(u8);
 
 impl TableFlags {
+    /// Returns the byte of the flags
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
     pub fn has_structural_cdx(&self) -> bool {
         (self.0 & 0x01) == 1
     }
 
+    /// Whether the table has a production index (.mdx) that dBase IV opens with it,
+    /// dBase IV uses the same bit as the structural .cdx of FoxPro
+    pub fn has_production_mdx(&self) -> bool {
+        self.has_structural_cdx()
+    }
+
     pub fn has_memo_field(&self) -> bool {
         (self.0 & 0x02) == 2
     }
@@ -132,15 +186,27 @@ impl TableFlags {
 /// of each dBase file
 #[derive(Debug, Copy, Clone)]
 pub struct Header {
+    /// Version of the file, from byte 0
     pub file_type: Version,
+    /// Date of the last update, from bytes 1 to 3
     pub last_update: Date,
+    /// Number of records, from bytes 4 to 7
     pub num_records: u32,
+    /// Size of the header (field descriptors included), from bytes 8 and 9
     pub offset_to_first_record: u16,
+    /// Size of a record (deletion flag included), from bytes 10 and 11
     pub size_of_record: u16,
     pub is_transaction_incomplete: bool,
     pub encryption_flag: u8,
     pub table_flags: TableFlags,
+    /// Language driver byte, see [code_page](#method.code_page)
     pub code_page_mark: u8,
+    /// Byte 0 as read, different bytes can give the same `file_type`
+    pub(crate) version_byte: u8,
+    /// Bytes 12 and 13, reserved
+    pub(crate) reserved: [u8; 2],
+    /// Bytes 16 to 27, reserved for the multi-user processing of dBase
+    pub(crate) multi_user_bytes: [u8; 12],
 }
 
 impl Header {
@@ -148,23 +214,152 @@ impl Header {
 
     pub(crate) fn new(num_records: u32, offset: u16, size_of_records: u16) -> Self {
         let current_date = Self::get_today_date();
+        let file_type = Version::DBase3 {
+            supports_memo: false,
+        };
         Self {
-            file_type: Version::DBase3 {
-                supports_memo: false,
-            },
+            file_type,
+            version_byte: u8::from(file_type),
             last_update: current_date,
             num_records,
             offset_to_first_record: offset,
             size_of_record: size_of_records,
             is_transaction_incomplete: false,
             encryption_flag: 0,
-            table_flags: TableFlags { 0: 0 },
+            table_flags: TableFlags(0),
             code_page_mark: 0,
+            reserved: [0u8; 2],
+            multi_user_bytes: [0u8; 12],
+        }
+    }
+
+    /// Returns the version byte of the file
+    ///
+    /// Unlike `file_type`, it keeps the exact byte of the file
+    /// (e.g. `0xCB` and `0x8B` are both dBase IV with a memo file).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// let header = reader.header();
+    /// assert_eq!(header.version(), 0x03);
+    /// assert_eq!(header.num_records, 1);
+    /// assert_eq!(header.header_size(), 65);
+    /// assert_eq!(header.record_size(), 51);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn version(&self) -> u8 {
+        // The file type may have been changed since the header was read
+        if u8::from(Version::from(self.version_byte)) == u8::from(self.file_type) {
+            self.version_byte
+        } else {
+            u8::from(self.file_type)
+        }
+    }
+
+    /// Returns the date of the last update of the file
+    pub fn last_updated(&self) -> Date {
+        self.last_update
+    }
+
+    /// Returns the size in bytes of the header, field descriptors included,
+    /// which is also the offset of the first record
+    pub fn header_size(&self) -> u16 {
+        self.offset_to_first_record
+    }
+
+    /// Returns the size in bytes of a record, deletion flag included
+    pub fn record_size(&self) -> u16 {
+        self.size_of_record
+    }
+
+    /// Returns the code page used to encode the text in the file
+    ///
+    /// The code page is read from the language driver byte (`code_page_mark`),
+    /// when this byte is zero, the default code page of the file's version is
+    /// returned (if any).
+    ///
+    /// None is returned if the code page is not known.
+    pub fn code_page(&self) -> Option<u16> {
+        match self.code_page_mark {
+            0x00 => self.file_type.default_code_page(),
+            0x01 => Some(437),
+            0x02 => Some(850),
+            0x03 => Some(1252),
+            0x04 => Some(10000),
+            0x08 => Some(865),
+            0x09 => Some(437),
+            0x0A => Some(850),
+            0x0B => Some(437),
+            0x0D => Some(437),
+            0x0E => Some(850),
+            0x0F => Some(437),
+            0x10 => Some(850),
+            0x11 => Some(437),
+            0x12 => Some(850),
+            0x13 => Some(932),
+            0x14 => Some(850),
+            0x15 => Some(437),
+            0x16 => Some(850),
+            0x17 => Some(865),
+            0x18 => Some(437),
+            0x19 => Some(437),
+            0x1A => Some(850),
+            0x1B => Some(437),
+            0x1C => Some(863),
+            0x1D => Some(850),
+            0x1F => Some(852),
+            0x22 => Some(852),
+            0x23 => Some(852),
+            0x24 => Some(860),
+            0x25 => Some(850),
+            0x26 => Some(866),
+            0x37 => Some(850),
+            0x40 => Some(852),
+            0x4D => Some(936),
+            0x4E => Some(949),
+            0x4F => Some(950),
+            0x50 => Some(874),
+            0x57 => Some(1252),
+            0x58 => Some(1252),
+            0x59 => Some(1252),
+            0x64 => Some(852),
+            0x65 => Some(866),
+            0x66 => Some(865),
+            0x67 => Some(861),
+            0x6A => Some(737),
+            0x6B => Some(857),
+            0x6C => Some(863),
+            0x78 => Some(950),
+            0x79 => Some(949),
+            0x7A => Some(936),
+            0x7B => Some(932),
+            0x7C => Some(874),
+            0x86 => Some(737),
+            0x87 => Some(852),
+            0x88 => Some(857),
+            0x96 => Some(10007),
+            0x97 => Some(10029),
+            0x98 => Some(10006),
+            0xC8 => Some(1250),
+            0xC9 => Some(1251),
+            0xCA => Some(1254),
+            0xCB => Some(1253),
+            0xCC => Some(1257),
+            _ => None,
         }
     }
 
     fn get_today_date() -> Date {
-        let current_date: Date = chrono::Utc::now().date().into();
+        let days_since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs() / 86_400);
+        let current_date = Date::julian_day_number_to_gregorian_date(
+            JULIAN_DAY_NUMBER_OF_UNIX_EPOCH + days_since_epoch as i32,
+        );
         // The year will be saved a a u8 offset from 1900
         if current_date.year() < 1900 || current_date.year() > 2155 {
             panic!("the year current date is out of range");
@@ -178,7 +373,8 @@ impl Header {
     }
 
     pub(crate) fn read_from<T: Read>(source: &mut T) -> Result<Self, std::io::Error> {
-        let file_type = Version::from(source.read_u8()?);
+        let version_byte = source.read_u8()?;
+        let file_type = Version::from(version_byte);
 
         let mut date_bytes = [0u8; 3];
         source.read_exact(&mut date_bytes)?;
@@ -192,17 +388,16 @@ impl Header {
         let offset_to_first_record = source.read_u16::<LittleEndian>()?;
         let size_of_record = source.read_u16::<LittleEndian>()?;
 
-        let _reserved = source.read_u16::<LittleEndian>()?;
+        let mut reserved = [0u8; 2];
+        source.read_exact(&mut reserved)?;
 
-        let is_transaction_incomplete = (source.read_u8()? != 0) as bool;
+        let is_transaction_incomplete = source.read_u8()? != 0;
         let encryption_flag = source.read_u8()?;
 
-        let mut _reserved = [0u8; 12];
-        source.read_exact(&mut _reserved)?;
+        let mut multi_user_bytes = [0u8; 12];
+        source.read_exact(&mut multi_user_bytes)?;
 
-        let table_flags = TableFlags {
-            0: source.read_u8()?,
-        };
+        let table_flags = TableFlags(source.read_u8()?);
 
         let code_page_mark = source.read_u8()?;
 
@@ -219,6 +414,9 @@ impl Header {
             size_of_record,
             table_flags,
             code_page_mark,
+            reserved,
+            multi_user_bytes,
+            version_byte,
         })
     }
 
@@ -233,13 +431,10 @@ impl Header {
         dest.write_u16::<LittleEndian>(self.offset_to_first_record)?;
         dest.write_u16::<LittleEndian>(self.size_of_record)?;
 
-        // Reserved
-        dest.write_u16::<LittleEndian>(0)?;
+        dest.write_all(&self.reserved)?;
         dest.write_u8(u8::from(self.is_transaction_incomplete))?;
         dest.write_u8(self.encryption_flag)?;
-
-        let _reserved = [0u8; 12];
-        dest.write_all(&_reserved)?;
+        dest.write_all(&self.multi_user_bytes)?;
 
         dest.write_u8(self.table_flags.0)?;
         dest.write_u8(self.code_page_mark)?;
@@ -253,7 +448,7 @@ impl Header {
 #[cfg(test)]
 mod test {
     use std::fs::File;
-    use std::io::{Cursor, Seek, SeekFrom};
+    use std::io::{Cursor, Seek};
 
     use super::*;
 
@@ -261,7 +456,7 @@ mod test {
     fn pos_after_reading_header() {
         let mut file = File::open("tests/data/line.dbf").unwrap();
         let _hdr = Header::read_from(&mut file).unwrap();
-        let pos_after_reading = file.seek(SeekFrom::Current(0)).unwrap();
+        let pos_after_reading = file.stream_position().unwrap();
         assert_eq!(pos_after_reading, Header::SIZE as u64);
     }
 
@@ -272,7 +467,7 @@ mod test {
 
         let mut out = Cursor::new(Vec::<u8>::with_capacity(Header::SIZE));
         hdr.write_to(&mut out).unwrap();
-        let pos_after_writing = out.seek(SeekFrom::Current(0)).unwrap();
+        let pos_after_writing = out.stream_position().unwrap();
         assert_eq!(pos_after_writing, Header::SIZE as u64);
     }
 
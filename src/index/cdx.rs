@@ -0,0 +1,209 @@
+//! Module with the definition of fn's and struct's to read .cdx (compound index) files
+//!
+//! Only what is needed to iterate over the records in the order of an
+//! index tag is read: the name of the tags, and the record numbers
+//! stored in the leaf nodes of each tag.
+
+use std::collections::HashSet;
+use std::io::{Read, Seek, SeekFrom};
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+const NODE_SIZE: usize = 512;
+const NO_NODE: u32 = 0xFFFF_FFFF;
+const LEAF_NODE_FLAG: u16 = 0x02;
+
+/// A tag of a compound index
+#[derive(Debug, Clone)]
+pub(crate) struct IndexTag {
+    pub(crate) name: String,
+    /// The record numbers (starting at 1) in the order of the tag
+    pub(crate) record_numbers: Vec<u32>,
+}
+
+/// The content of a compound index file (.cdx)
+#[derive(Debug, Clone)]
+pub(crate) struct CompoundIndex {
+    /// The tags in the order they were created
+    pub(crate) tags: Vec<IndexTag>,
+}
+
+impl CompoundIndex {
+    pub(crate) fn read_from<T: Read + Seek>(source: &mut T) -> std::io::Result<Self> {
+        // The first header is the one of the tag directory,
+        // which is an index whose keys are the tags names, and whose
+        // record numbers are the position of the header of each tag
+        let directory = read_tag_entries(source, 0)?;
+        let mut tags_headers = directory
+            .into_iter()
+            .map(|(key, header_pos)| {
+                let name = String::from_utf8_lossy(&key)
+                    .trim_end_matches([' ', '\u{0}'])
+                    .to_owned();
+                (name, header_pos)
+            })
+            .collect::<Vec<_>>();
+        tags_headers.sort_by_key(|&(_, header_pos)| header_pos);
+
+        let mut tags = Vec::with_capacity(tags_headers.len());
+        for (name, header_pos) in tags_headers {
+            let record_numbers = read_tag_entries(source, header_pos)?
+                .into_iter()
+                .map(|(_, record_number)| record_number)
+                .collect();
+            tags.push(IndexTag {
+                name,
+                record_numbers,
+            });
+        }
+        Ok(Self { tags })
+    }
+
+    pub(crate) fn tag(&self, name: &str) -> Option<&IndexTag> {
+        self.tags
+            .iter()
+            .find(|tag| tag.name.eq_ignore_ascii_case(name))
+    }
+}
+
+fn invalid_data(msg: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+}
+
+fn read_node<T: Read + Seek>(source: &mut T, pos: u32) -> std::io::Result<[u8; NODE_SIZE]> {
+    let mut node = [0u8; NODE_SIZE];
+    source.seek(SeekFrom::Start(u64::from(pos)))?;
+    source.read_exact(&mut node)?;
+    Ok(node)
+}
+
+/// Reads the (key, record number) entries of the tag whose header is at `header_pos`,
+/// in the order of the tag
+fn read_tag_entries<T: Read + Seek>(
+    source: &mut T,
+    header_pos: u32,
+) -> std::io::Result<Vec<(Vec<u8>, u32)>> {
+    let header = read_node(source, header_pos)?;
+    let root = LittleEndian::read_u32(&header[0..4]);
+    let key_length = LittleEndian::read_u16(&header[12..14]) as usize;
+    if key_length == 0 || key_length + 8 > NODE_SIZE - 12 {
+        return Err(invalid_data("Invalid index key length"));
+    }
+
+    let mut visited = HashSet::new();
+
+    // Go down to the leftmost leaf
+    let mut pos = root;
+    let mut node = loop {
+        if !visited.insert(pos) {
+            return Err(invalid_data("Index nodes form a cycle"));
+        }
+        let node = read_node(source, pos)?;
+        if LittleEndian::read_u16(&node[0..2]) & LEAF_NODE_FLAG != 0 {
+            break node;
+        }
+        if LittleEndian::read_u16(&node[2..4]) == 0 {
+            return Err(invalid_data("Index interior node has no keys"));
+        }
+        // entries of interior nodes are: key, record number, child node
+        let child_pos = 12 + key_length + 4;
+        pos = BigEndian::read_u32(&node[child_pos..child_pos + 4]);
+    };
+
+    // Then go through all the leaves using the right sibling pointers
+    let mut entries = vec![];
+    loop {
+        read_leaf_entries(&node, key_length, &mut entries)?;
+        let right_sibling = LittleEndian::read_u32(&node[8..12]);
+        if right_sibling == NO_NODE {
+            break;
+        }
+        if !visited.insert(right_sibling) {
+            return Err(invalid_data("Index nodes form a cycle"));
+        }
+        node = read_node(source, right_sibling)?;
+    }
+    Ok(entries)
+}
+
+/// Reads the entries of a compact leaf node.
+///
+/// Each entry is made of a packed integer (record number, duplicate count, trailing count)
+/// stored after the node header, and the bytes of the key not shared with the previous key,
+/// stored from the end of the node.
+fn read_leaf_entries(
+    node: &[u8; NODE_SIZE],
+    key_length: usize,
+    entries: &mut Vec<(Vec<u8>, u32)>,
+) -> std::io::Result<()> {
+    let num_keys = LittleEndian::read_u16(&node[2..4]) as usize;
+    let record_number_mask = LittleEndian::read_u32(&node[14..18]);
+    let duplicate_count_mask = u64::from(node[18]);
+    let trailing_count_mask = u64::from(node[19]);
+    let record_number_bits = u32::from(node[20]);
+    let duplicate_count_bits = u32::from(node[21]);
+    let entry_size = node[23] as usize;
+    if entry_size == 0
+        || entry_size > 8
+        || record_number_bits + duplicate_count_bits >= 64
+        || 24 + num_keys * entry_size > NODE_SIZE
+    {
+        return Err(invalid_data("Invalid index leaf node"));
+    }
+
+    let mut previous_key = vec![b' '; key_length];
+    let mut key_end = NODE_SIZE;
+    for entry in node[24..24 + num_keys * entry_size].chunks(entry_size) {
+        let value = LittleEndian::read_uint(entry, entry_size);
+        let record_number = (value as u32) & record_number_mask;
+        let duplicate_count = ((value >> record_number_bits) & duplicate_count_mask) as usize;
+        let trailing_count =
+            ((value >> (record_number_bits + duplicate_count_bits)) & trailing_count_mask) as usize;
+
+        let new_count = key_length
+            .checked_sub(duplicate_count + trailing_count)
+            .ok_or_else(|| invalid_data("Invalid index leaf node"))?;
+        let key_start = key_end
+            .checked_sub(new_count)
+            .filter(|&start| start >= 24 + num_keys * entry_size)
+            .ok_or_else(|| invalid_data("Invalid index leaf node"))?;
+
+        let mut key = Vec::with_capacity(key_length);
+        key.extend_from_slice(&previous_key[..duplicate_count]);
+        key.extend_from_slice(&node[key_start..key_end]);
+        key.resize(key_length, b' ');
+        key_end = key_start;
+
+        previous_key.clone_from(&key);
+        entries.push((key, record_number));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn read_tags() {
+        let mut file = File::open("tests/data/indexed.cdx").unwrap();
+        let index = CompoundIndex::read_from(&mut file).unwrap();
+
+        let names = index
+            .tags
+            .iter()
+            .map(|tag| tag.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["AGE", "NAME"]);
+        assert_eq!(
+            index.tag("age").unwrap().record_numbers,
+            vec![3, 2, 5, 1, 4]
+        );
+        assert_eq!(
+            index.tag("NAME").unwrap().record_numbers,
+            vec![2, 4, 5, 1, 3]
+        );
+        assert!(index.tag("BIRTH").is_none());
+    }
+}
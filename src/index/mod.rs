@@ -0,0 +1,6 @@
+//! Module with the index files (.cdx, .ndx) readers
+mod cdx;
+mod ndx;
+
+pub(crate) use self::cdx::CompoundIndex;
+pub use self::ndx::{NdxIndex, NdxKeyType};
@@ -0,0 +1,263 @@
+//! Module with the definition of fn's and struct's to read .ndx (dBase III index) files
+//!
+//! A .ndx file indexes one table on a single key expression.
+//! It is made of 512 bytes pages: the first one is the header,
+//! the other ones are the nodes of a B-tree whose leaves store
+//! the keys along with the record number they belong to.
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::error::{Error, ErrorKind};
+use crate::FieldValue;
+
+const PAGE_SIZE: usize = 512;
+/// Size of the header of a node (its number of keys)
+const NODE_HEADER_SIZE: usize = 4;
+/// Size of the beginning of an entry (child page, record number)
+const ENTRY_HEADER_SIZE: usize = 8;
+
+/// The type of the keys of a .ndx index
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NdxKeyType {
+    /// Keys are the bytes of a Character expression, padded with spaces
+    Character,
+    /// Keys are Numeric (or Date) expressions, stored as 8 bytes floats
+    Numeric,
+}
+
+/// A dBase III index file (.ndx)
+///
+/// The whole file is read in memory when opened.
+///
+/// # Example
+///
+/// ```
+/// use dbase::{FieldValue, NdxIndex};
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut reader = dbase::Reader::from_path("tests/data/people.dbf")?;
+/// let index = NdxIndex::open("tests/data/people_name.ndx")?;
+///
+/// for record_index in index.find(&FieldValue::Character(Some("Paul".to_owned())))? {
+///     let record = reader.record(record_index)?;
+///     assert_eq!(record.get("NAME"), Some(&FieldValue::Character(Some("Paul".to_owned()))));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct NdxIndex {
+    root_page: u32,
+    next_free_page: u32,
+    key_length: u16,
+    key_type: NdxKeyType,
+    entry_size: usize,
+    key_expression: String,
+    unique: bool,
+    /// Content of the file, the header included
+    data: Vec<u8>,
+}
+
+/// A key, in the form used to compare it with the keys of the index
+enum Key {
+    Character(Vec<u8>),
+    Numeric(f64),
+}
+
+impl NdxIndex {
+    /// Opens the .ndx file at the given path
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let file = File::open(path).map_err(|error| Error {
+            record_num: 0,
+            field: None,
+            kind: ErrorKind::ErrorOpeningIndexFile(error),
+        })?;
+        Self::new(file)
+    }
+
+    /// Reads the .ndx index from the source
+    pub fn new<T: Read>(mut source: T) -> Result<Self, Error> {
+        let mut data = vec![];
+        source
+            .read_to_end(&mut data)
+            .map_err(|error| Error::io_error(error, 0))?;
+        if data.len() < PAGE_SIZE {
+            return Err(invalid_data("The index header is truncated"));
+        }
+
+        let root_page = LittleEndian::read_u32(&data[0..4]);
+        let next_free_page = LittleEndian::read_u32(&data[4..8]);
+        let key_length = LittleEndian::read_u16(&data[12..14]);
+        let key_type = match LittleEndian::read_u16(&data[16..18]) {
+            0 => NdxKeyType::Character,
+            _ => NdxKeyType::Numeric,
+        };
+        let entry_size = LittleEndian::read_u16(&data[18..20]) as usize;
+        let unique = data[22] != 0;
+        let key_expression = data[24..PAGE_SIZE]
+            .split(|byte| *byte == 0)
+            .next()
+            .map(|expression| String::from_utf8_lossy(expression).trim().to_owned())
+            .unwrap_or_default();
+
+        if key_length == 0
+            || (key_type == NdxKeyType::Numeric && key_length != 8)
+            || entry_size < ENTRY_HEADER_SIZE + key_length as usize
+            || NODE_HEADER_SIZE + entry_size > PAGE_SIZE
+        {
+            return Err(invalid_data("Invalid index key length"));
+        }
+
+        Ok(Self {
+            root_page,
+            next_free_page,
+            key_length,
+            key_type,
+            entry_size,
+            key_expression,
+            unique,
+            data,
+        })
+    }
+
+    /// Returns the page number of the root node of the B-tree
+    pub fn root_page(&self) -> u32 {
+        self.root_page
+    }
+
+    /// Returns the page number after the last page used by the index
+    pub fn next_free_page(&self) -> u32 {
+        self.next_free_page
+    }
+
+    /// Returns the length in bytes of the keys
+    pub fn key_length(&self) -> u16 {
+        self.key_length
+    }
+
+    /// Returns the type of the keys
+    pub fn key_type(&self) -> NdxKeyType {
+        self.key_type
+    }
+
+    /// Returns the expression the keys are computed from, e.g. `"NAME"`
+    pub fn key_expression(&self) -> &str {
+        &self.key_expression
+    }
+
+    /// Returns whether the index was created with `UNIQUE`,
+    /// in which case only the first record of each key is in the index
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    /// Returns the index (starting at 0) of the records whose key is equal to `key`,
+    /// in the order of the index.
+    ///
+    /// The indices can be given to [Reader::record](struct.Reader.html#method.record).
+    ///
+    /// Character keys are compared exactly, after being padded with spaces
+    /// to the key length, `FieldValue::Character(None)` looks for blank keys.
+    /// Numeric keys are looked up with a `FieldValue::Numeric(Some(_))`.
+    /// Any other value returns an `ErrorKind::IncompatibleType` error.
+    pub fn find(&self, key: &FieldValue) -> Result<Vec<usize>, Error> {
+        let key = match (self.key_type, key) {
+            (NdxKeyType::Character, FieldValue::Character(value)) => {
+                let mut bytes = value.as_deref().unwrap_or_default().as_bytes().to_vec();
+                bytes.resize(self.key_length as usize, b' ');
+                Key::Character(bytes)
+            }
+            (NdxKeyType::Numeric, FieldValue::Numeric(Some(value))) => Key::Numeric(*value),
+            _ => {
+                return Err(Error {
+                    record_num: 0,
+                    field: None,
+                    kind: ErrorKind::IncompatibleType,
+                })
+            }
+        };
+
+        let mut record_indices = vec![];
+        let mut visited = HashSet::new();
+        self.find_in_page(self.root_page, &key, &mut visited, &mut record_indices)?;
+        Ok(record_indices)
+    }
+
+    /// Looks for the key in the sub-tree whose root is the node at `page`
+    fn find_in_page(
+        &self,
+        page: u32,
+        key: &Key,
+        visited: &mut HashSet<u32>,
+        record_indices: &mut Vec<usize>,
+    ) -> Result<(), Error> {
+        if !visited.insert(page) {
+            return Err(invalid_data("Index nodes form a cycle"));
+        }
+        let node = self.page(page)?;
+        let num_keys = LittleEndian::read_u32(&node[0..4]) as usize;
+        let entries = &node[NODE_HEADER_SIZE..];
+        if num_keys.max(1) * self.entry_size > entries.len() {
+            return Err(invalid_data("Invalid index node"));
+        }
+        let entry = |i: usize| &entries[i * self.entry_size..(i + 1) * self.entry_size];
+
+        let is_leaf = LittleEndian::read_u32(&entry(0)[0..4]) == 0;
+        if is_leaf {
+            for i in 0..num_keys {
+                let entry = entry(i);
+                if self.compare(key, entry) == std::cmp::Ordering::Equal {
+                    let record_number = LittleEndian::read_u32(&entry[4..8]);
+                    if record_number == 0 {
+                        return Err(invalid_data("Invalid index record number"));
+                    }
+                    record_indices.push(record_number as usize - 1);
+                }
+            }
+            return Ok(());
+        }
+
+        // Interior nodes have one more child than keys
+        if (num_keys + 1) * self.entry_size > entries.len() {
+            return Err(invalid_data("Invalid index node"));
+        }
+        // The key of an entry is the greatest key of its child,
+        // equal keys may be spread over consecutive children
+        for i in 0..=num_keys {
+            if i > 0 && self.compare(key, entry(i - 1)) == std::cmp::Ordering::Less {
+                break;
+            }
+            if i == num_keys || self.compare(key, entry(i)) != std::cmp::Ordering::Greater {
+                let child = LittleEndian::read_u32(&entry(i)[0..4]);
+                self.find_in_page(child, key, visited, record_indices)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Compares the key with the key of the entry
+    fn compare(&self, key: &Key, entry: &[u8]) -> std::cmp::Ordering {
+        let entry_key = &entry[ENTRY_HEADER_SIZE..ENTRY_HEADER_SIZE + self.key_length as usize];
+        match key {
+            Key::Character(bytes) => bytes.as_slice().cmp(entry_key),
+            Key::Numeric(value) => value
+                .partial_cmp(&LittleEndian::read_f64(entry_key))
+                .unwrap_or(std::cmp::Ordering::Less),
+        }
+    }
+
+    fn page(&self, page: u32) -> Result<&[u8], Error> {
+        let start = page as usize * PAGE_SIZE;
+        if page == 0 || start + PAGE_SIZE > self.data.len() {
+            return Err(invalid_data("Index page is past the end of the file"));
+        }
+        Ok(&self.data[start..start + PAGE_SIZE])
+    }
+}
+
+fn invalid_data(msg: &str) -> Error {
+    Error::io_error(std::io::Error::new(std::io::ErrorKind::InvalidData, msg), 0)
+}
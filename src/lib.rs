@@ -220,28 +220,47 @@
 #![deny(unstable_features)]
 
 extern crate byteorder;
+#[cfg(feature = "chrono")]
 extern crate chrono;
+#[cfg(feature = "encoding")]
+extern crate encoding_rs;
 #[cfg(feature = "serde")]
 extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
 
 #[cfg(feature = "serde")]
 mod de;
 #[cfg(feature = "serde")]
 mod ser;
 
+mod columnar;
+mod encoding;
 mod error;
 mod header;
+mod index;
 mod reading;
 mod record;
 mod writing;
 
+pub use crate::columnar::{Column, Columns};
+pub use crate::encoding::{Encoding, LanguageDriver};
 pub use crate::error::{Error, ErrorKind, FieldIOError};
+pub use crate::header::{Header, TableFlags, Version};
+pub use crate::index::{NdxIndex, NdxKeyType};
 pub use crate::reading::{
-    read, FieldIterator, NamedValue, ReadableRecord, Reader, Record, RecordIterator, TableInfo,
+    read, read_field_at, DeletionFlag, DuplicateFieldNames, FieldIterator, FieldNameNormalization,
+    IndexOrderIterator, NamedValue, ReadableRecord, Reader, ReadingOptions, Record, RecordIterator,
+    RecordWithSchemaIterator, TableInfo, TrimMode,
+};
+pub use crate::record::field::{
+    Date, DateTime, FieldType, FieldValue, MemoFileType, MemoReader, MemoWriter, Time,
+};
+pub use crate::record::{FieldConversionError, FieldFlags, FieldInfo, FieldName};
+pub use crate::writing::{
+    concat, concat_with_memo, validate_record, Dialect, FieldWriter, RecordBuilder, TableWriter,
+    TableWriterBuilder, WritableRecord,
 };
-pub use crate::record::field::{Date, DateTime, FieldValue, Time};
-pub use crate::record::{FieldConversionError, FieldInfo, FieldName};
-pub use crate::writing::{FieldWriter, TableWriter, TableWriterBuilder, WritableRecord};
 
 /// macro to define a struct that implements the ReadableRecord and WritableRecord
 ///
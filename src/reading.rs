@@ -2,24 +2,33 @@
 
 use byteorder::ReadBytesExt;
 
-use std::collections::hash_map::RandomState;
-use std::collections::HashMap;
+use std::collections::hash_map::{Entry, RandomState};
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fs::File;
-use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::hash::Hasher;
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
 use std::iter::FusedIterator;
 use std::path::Path;
+use std::sync::Arc;
 
+use crate::columnar::{Column, Columns};
+use crate::encoding::{Encoding, LanguageDriver};
 use crate::error::{Error, ErrorKind, FieldIOError};
-use crate::header::Header;
-use crate::record::field::{FieldType, FieldValue, MemoFileType, MemoReader};
+use crate::header::{Header, Version};
+use crate::index::CompoundIndex;
+use crate::record::field::{
+    read_memo_index, read_numeric_text, FieldType, FieldValue, MemoFileType, MemoReader, MemoWriter,
+};
 use crate::record::FieldInfo;
+use crate::writing::{Dialect, FILE_TERMINATOR};
 use crate::FieldConversionError;
 
 /// Value of the byte between the last RecordFieldInfo and the first record
 pub(crate) const TERMINATOR_VALUE: u8 = 0x0D;
 
-const BACKLINK_SIZE: u16 = 263;
+/// Size of the path to the database container (.dbc) in Visual FoxPro files
+pub(crate) const BACKLINK_SIZE: u16 = 263;
 
 /// Trait to be implemented by structs that represent records read from a
 /// dBase file.
@@ -38,9 +47,19 @@ pub trait ReadableRecord: Sized {
 
 /// Type definition of a generic record.
 /// A .dbf file is composed of many records
-#[derive(Debug, PartialEq)]
+///
+/// Two records are equal when they have the same values,
+/// whether they are marked as deleted is not compared.
+#[derive(Debug, Clone, Default)]
 pub struct Record {
     map: HashMap<String, FieldValue>,
+    deleted: bool,
+}
+
+impl PartialEq for Record {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
 }
 
 impl ReadableRecord for Record {
@@ -48,12 +67,28 @@ impl ReadableRecord for Record {
     where
         T: Read + Seek,
     {
+        let deleted = field_iterator.is_deleted();
         let mut map = HashMap::<String, FieldValue>::new();
         for result in field_iterator {
             let NamedValue { name, value } = result?;
             map.insert(name.to_owned(), value);
         }
-        Ok(Self { map })
+        Ok(Self { map, deleted })
+    }
+}
+
+/// The (name, value) pairs of the fields, in the order of the fields of the file
+struct RecordPairs(Vec<(String, FieldValue)>);
+
+impl ReadableRecord for RecordPairs {
+    fn read_using<T>(field_iterator: &mut FieldIterator<T>) -> Result<Self, FieldIOError>
+    where
+        T: Read + Seek,
+    {
+        field_iterator
+            .map(|result| result.map(|NamedValue { name, value }| (name.to_owned(), value)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(RecordPairs)
     }
 }
 
@@ -84,6 +119,213 @@ impl Record {
     pub fn remove(&mut self, field_name: &str) -> Option<FieldValue> {
         self.map.remove(field_name)
     }
+
+    /// Returns whether the record was marked as deleted in the file it was read from
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// let records = reader.read()?;
+    /// assert!(!records[0].is_deleted());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+
+    /// Returns an iterator over the fields of the record paired with
+    /// the information of the field they belong to.
+    ///
+    /// The record does not store its fields information, so they have to be given
+    /// (e.g. from [Reader::fields](struct.Reader.html#method.fields)).
+    /// The values are returned in the same order as the `fields`,
+    /// fields for which the record has no value are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let records = reader.read()?;
+    /// for (info, value) in records[0].typed_fields(reader.fields()) {
+    ///     println!("{} ({} bytes): {}", info.name(), info.length(), value);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn typed_fields<'a>(
+        &'a self,
+        fields: &'a [FieldInfo],
+    ) -> impl Iterator<Item = (&'a FieldInfo, &'a FieldValue)> + 'a {
+        fields
+            .iter()
+            .filter_map(move |info| self.map.get(info.name()).map(|value| (info, value)))
+    }
+
+    /// Returns a hash of the names and values of the fields of the record
+    ///
+    /// The hash is deterministic (it does not change between runs nor between
+    /// platforms), and values are normalized before being hashed: trailing spaces
+    /// of Character and Memo values are ignored, and an empty Character value
+    /// is the same as `None`. It can be used to find duplicated records,
+    /// e.g. across many files.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldValue, Record};
+    ///
+    /// let mut record = Record::default();
+    /// record.insert("Name".to_owned(), FieldValue::Character(Some("Ferris".to_owned())));
+    /// let mut padded = Record::default();
+    /// padded.insert("Name".to_owned(), FieldValue::Character(Some("Ferris   ".to_owned())));
+    /// assert_eq!(record.content_hash(), padded.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        let mut fields = self.map.iter().collect::<Vec<_>>();
+        fields.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut hasher = Fnv1aHasher::default();
+        for (name, value) in fields {
+            hasher.write(name.as_bytes());
+            hasher.write(&[0, u8::from(value.field_type())]);
+            match value {
+                FieldValue::Character(text) => {
+                    let text = text.as_deref().unwrap_or("");
+                    hasher.write(text.trim_end_matches(' ').as_bytes());
+                    hasher.write(&[0]);
+                }
+                FieldValue::Memo(text) => {
+                    hasher.write(text.trim_end_matches(' ').as_bytes());
+                    hasher.write(&[0]);
+                }
+                FieldValue::General(bytes)
+                | FieldValue::Picture(bytes)
+                | FieldValue::Varbinary(Some(bytes)) => {
+                    hasher.write_u64(bytes.len() as u64);
+                    hasher.write(bytes);
+                }
+                FieldValue::Varchar(Some(text)) => {
+                    hasher.write(text.as_bytes());
+                    hasher.write(&[0]);
+                }
+                FieldValue::Varchar(None) | FieldValue::Varbinary(None) => {
+                    hasher.write_u64(u64::MAX)
+                }
+                FieldValue::Numeric(number) => {
+                    // Adding 0.0 turns -0.0 into 0.0
+                    hasher.write_u64(number.map_or(u64::MAX, |n| (n + 0.0).to_bits()))
+                }
+                FieldValue::Float(number) => {
+                    hasher.write_u64(number.map_or(u64::MAX, |n| u64::from((n + 0.0).to_bits())))
+                }
+                FieldValue::Logical(value) => hasher.write(&[match value {
+                    None => 0,
+                    Some(false) => 1,
+                    Some(true) => 2,
+                }]),
+                FieldValue::Date(date) => match date {
+                    Some(date) => {
+                        hasher.write_u32(date.year());
+                        hasher.write_u32(date.month());
+                        hasher.write_u32(date.day());
+                    }
+                    None => hasher.write_u32(u32::MAX),
+                },
+                FieldValue::Integer(Some(number)) => hasher.write_u32(*number as u32),
+                FieldValue::Currency(Some(number)) | FieldValue::Double(Some(number)) => {
+                    hasher.write_u64((number + 0.0).to_bits())
+                }
+                FieldValue::Integer(None) => hasher.write_u8(u8::MAX),
+                FieldValue::Currency(None) | FieldValue::Double(None) => hasher.write_u64(u64::MAX),
+                FieldValue::DateTime(None) => hasher.write_u32(u32::MAX),
+                FieldValue::DateTime(Some(datetime)) => {
+                    let (date, time) = (datetime.date(), datetime.time());
+                    hasher.write_u32(date.year());
+                    hasher.write_u32(date.month());
+                    hasher.write_u32(date.day());
+                    hasher.write_u32(time.hours());
+                    hasher.write_u32(time.minutes());
+                    hasher.write_u32(time.seconds());
+                }
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Replaces the value of an existing field
+    ///
+    /// Returns an error if the record has no field with that name, or if the
+    /// [FieldType](enum.FieldType.html) of the new value is not the same
+    /// as the one of the current value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut records = dbase::read("tests/data/line.dbf")?;
+    /// records[0].set("name", dbase::FieldValue::Character(Some("linestring2".to_owned())))?;
+    /// assert!(records[0].set("name", dbase::FieldValue::Logical(Some(true))).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set(&mut self, field_name: &str, value: FieldValue) -> Result<(), Error> {
+        match self.map.get_mut(field_name) {
+            Some(current) if current.field_type() == value.field_type() => {
+                *current = value;
+                Ok(())
+            }
+            Some(_) => Err(Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::IncompatibleType,
+            }),
+            None => Err(Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::Message(format!(
+                    "Could not find field named '{}' in the record",
+                    field_name
+                )),
+            }),
+        }
+    }
+}
+
+/// 64 bits FNV-1a hash, used because the hash of the standard library
+/// is not guaranteed to be the same between releases.
+///
+/// Integers are hashed as little endian bytes, so the hash does not depend on the platform.
+struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1aHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
 }
 
 impl IntoIterator for Record {
@@ -95,17 +337,12 @@ impl IntoIterator for Record {
     }
 }
 
-impl Default for Record {
-    fn default() -> Self {
-        Self {
-            map: Default::default(),
-        }
-    }
-}
-
 impl From<HashMap<String, FieldValue>> for Record {
     fn from(map: HashMap<String, FieldValue, RandomState>) -> Self {
-        Self { map }
+        Self {
+            map,
+            deleted: false,
+        }
     }
 }
 
@@ -119,150 +356,1905 @@ impl AsRef<HashMap<String, FieldValue>> for Record {
     fn as_ref(&self) -> &HashMap<String, FieldValue> {
         &self.map
     }
-}
+}
+
+impl AsMut<HashMap<String, FieldValue>> for Record {
+    fn as_mut(&mut self) -> &mut HashMap<String, FieldValue> {
+        &mut self.map
+    }
+}
+
+/// Structs containing the information allowing to
+/// create a new TableWriter which would write file
+/// with the same record structure as another dbase file.
+///
+/// You can get this by using [Reader::into_table_info].
+#[derive(Clone, Debug)]
+pub struct TableInfo {
+    pub(crate) header: Header,
+    pub(crate) fields_info: Vec<FieldInfo>,
+}
+
+impl TableInfo {
+    /// Returns the header of the table
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the fields of the table
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields_info
+    }
+
+    /// Returns whether the header says that a transaction
+    /// was started but not finished (byte 14 of the header)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let table_info = dbase::Reader::from_path("tests/data/line.dbf")?.into_table_info();
+    /// assert!(!table_info.is_transaction_incomplete());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_transaction_incomplete(&self) -> bool {
+        self.header.is_transaction_incomplete
+    }
+
+    /// Returns whether the header says that the table has a production index (.mdx),
+    /// (byte 28 of the header)
+    ///
+    /// The flag is kept by [TableWriterBuilder::from_table_info](struct.TableWriterBuilder.html#method.from_table_info)
+    /// so that dBase still opens the index with the rewritten table.
+    pub fn has_production_mdx(&self) -> bool {
+        self.header.table_flags.has_production_mdx()
+    }
+
+    /// Returns the raw bytes 12 and 13 of the header, which are reserved
+    pub fn reserved_bytes(&self) -> [u8; 2] {
+        self.header.reserved
+    }
+
+    /// Returns the raw bytes 16 to 27 of the header, which are reserved
+    /// for the multi-user processing of dBase (e.g. dBase for Windows, LAN versions)
+    pub fn multi_user_bytes(&self) -> [u8; 12] {
+        self.header.multi_user_bytes
+    }
+}
+
+/// What the [Reader](struct.Reader.html) does when a file declares
+/// many fields with the same name (compared case-insensitively)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DuplicateFieldNames {
+    /// Keep the names as they are, when reading a [Record](struct.Record.html)
+    /// the value of the last field will replace the others.
+    Keep,
+    /// Return an error when opening the file
+    Error,
+    /// Rename the duplicates by adding a numeric suffix (e.g. `AMOUNT_1`),
+    /// the renamings are available with
+    /// [Reader::renamed_fields](struct.Reader.html#method.renamed_fields)
+    Rename,
+}
+
+/// How the [Reader](struct.Reader.html) normalizes the names of the fields
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FieldNameNormalization {
+    /// Keep the names as they are written (only the surrounding NULs are removed)
+    Keep,
+    /// Cut the name at the first NUL and remove the surrounding spaces
+    Trim,
+    /// Like `Trim`, and also convert the name to uppercase
+    TrimAndUppercase,
+}
+
+/// How the [Reader](struct.Reader.html) interprets the deletion flag of the records
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DeletionFlag {
+    /// A space (`0x20`) marks a live record, a `*` (`0x2A`) a deleted one
+    Standard,
+    /// A NUL byte (`0x00`) marks a live record, any other byte a deleted one
+    ZeroIsLive,
+}
+
+impl DeletionFlag {
+    /// Returns whether the flag byte marks a deleted record
+    pub(crate) fn is_deleted(self, flag: u8) -> bool {
+        match self {
+            DeletionFlag::Standard => flag == b'*',
+            DeletionFlag::ZeroIsLive => flag != 0,
+        }
+    }
+}
+
+/// How the [Reader](struct.Reader.html) trims the values of Character fields
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TrimMode {
+    /// Remove the leading and trailing spaces
+    Both,
+    /// Only remove the trailing spaces, which are the padding of the field
+    End,
+}
+
+/// Options that change how the [Reader](struct.Reader.html) interprets
+/// the content of a file
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let options = dbase::ReadingOptions::default().empty_character_as_none(false);
+/// let reader = dbase::Reader::from_path_with_options("tests/data/line.dbf", options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ReadingOptions {
+    pub(crate) empty_character_as_none: bool,
+    pub(crate) strict_field_types: bool,
+    pub(crate) big_endian_binary_fields: bool,
+    pub(crate) duplicate_field_names: DuplicateFieldNames,
+    pub(crate) strict_numeric_values: bool,
+    pub(crate) field_name_normalization: FieldNameNormalization,
+    #[cfg(feature = "encoding")]
+    pub(crate) sniff_character_encoding: bool,
+    pub(crate) cap_record_count: bool,
+    pub(crate) character_trim: TrimMode,
+    pub(crate) encoding: Encoding,
+    pub(crate) include_deleted: bool,
+    pub(crate) deletion_flag: DeletionFlag,
+    pub(crate) max_memo_length: Option<u32>,
+    pub(crate) julian_day_offset: i32,
+}
+
+impl ReadingOptions {
+    /// Whether a Character field that only contains pad bytes is read
+    /// as `None` (the default) or as `Some(String::new())`
+    pub fn empty_character_as_none(mut self, value: bool) -> Self {
+        self.empty_character_as_none = value;
+        self
+    }
+
+    /// Whether field types declared with a lowercase letter
+    /// (which some non-conforming files do) are rejected.
+    ///
+    /// By default they are accepted.
+    pub fn strict_field_types(mut self, value: bool) -> Self {
+        self.strict_field_types = value;
+        self
+    }
+
+    /// Whether the fields stored in binary (Integer, Double, Currency, DateTime)
+    /// are read as big endian instead of little endian (the default).
+    ///
+    /// A few tools write these fields in big endian.
+    pub fn big_endian_binary_fields(mut self, value: bool) -> Self {
+        self.big_endian_binary_fields = value;
+        self
+    }
+
+    /// Sets what to do when many fields have the same name,
+    /// by default they are kept as is.
+    pub fn duplicate_field_names(mut self, value: DuplicateFieldNames) -> Self {
+        self.duplicate_field_names = value;
+        self
+    }
+
+    /// Whether a Numeric or Float value partially made of `*` (e.g. `"12**"`)
+    /// is rejected with an error.
+    ///
+    /// By default, like values only made of `*` (which is how an overflowing
+    /// value is written), they are read as `None`.
+    pub fn strict_numeric_values(mut self, value: bool) -> Self {
+        self.strict_numeric_values = value;
+        self
+    }
+
+    /// Sets how the names of the fields are normalized,
+    /// by default they are kept as they are written.
+    ///
+    /// The normalization is done before looking for duplicate names.
+    pub fn field_name_normalization(mut self, value: FieldNameNormalization) -> Self {
+        self.field_name_normalization = value;
+        self
+    }
+
+    /// Whether the encoding of Character fields is guessed when the file
+    /// does not declare a code page (its language driver byte is 0).
+    ///
+    /// When enabled, the bytes of a Character field are used as is if they
+    /// are valid UTF-8, and are otherwise decoded as Windows-1252 (CP1252).
+    /// Files that declare a code page are not affected.
+    ///
+    /// By default, invalid UTF-8 sequences are replaced by `U+FFFD`.
+    ///
+    /// Only available with the `encoding` feature.
+    #[cfg(feature = "encoding")]
+    pub fn sniff_character_encoding(mut self, value: bool) -> Self {
+        self.sniff_character_encoding = value;
+        self
+    }
+
+    /// Whether the number of records is capped to the number of records
+    /// that the file is big enough to hold.
+    ///
+    /// This guards against corrupted headers that declare more records than there are,
+    /// the iteration then stops at the real end of the file instead of failing
+    /// on each missing record. The capped count is the one returned in the
+    /// [Header](struct.Header.html) of the reader.
+    pub fn cap_record_count(mut self, value: bool) -> Self {
+        self.cap_record_count = value;
+        self
+    }
+
+    /// Sets how the values of Character fields are trimmed.
+    ///
+    /// By default only the trailing spaces are removed: dBase pads the values
+    /// with spaces on the right, leading spaces are part of the value.
+    pub fn character_trim(mut self, value: TrimMode) -> Self {
+        self.character_trim = value;
+        self
+    }
+
+    /// Sets the encoding used to decode the text of Character and Memo fields.
+    ///
+    /// By default the text is decoded as UTF-8 and invalid sequences are
+    /// replaced by `U+FFFD`. Use [Encoding::FromHeader](enum.Encoding.html#variant.FromHeader)
+    /// to decode the text with the code page declared in the file.
+    ///
+    /// When [sniff_character_encoding](#method.sniff_character_encoding) is enabled
+    /// and the file does not declare a code page, the sniffing takes precedence
+    /// for Character fields.
+    pub fn encoding(mut self, value: Encoding) -> Self {
+        self.encoding = value;
+        self
+    }
+
+    /// Whether the records marked as deleted are returned when iterating
+    /// over the records, or skipped (the default), like after a `PACK`.
+    ///
+    /// The deleted records can be recognized with
+    /// [Record::is_deleted](struct.Record.html#method.is_deleted).
+    ///
+    /// [Reader::iter_all](struct.Reader.html#method.iter_all) and
+    /// [Reader::iter_deleted](struct.Reader.html#method.iter_deleted)
+    /// do not depend on this option.
+    pub fn include_deleted(mut self, value: bool) -> Self {
+        self.include_deleted = value;
+        self
+    }
+
+    /// Sets how the deletion flag of the records is interpreted,
+    /// by default the standard convention (`0x20` live, `0x2A` deleted) is used.
+    ///
+    /// A few exporters do not follow the standard convention.
+    pub fn deletion_flag(mut self, value: DeletionFlag) -> Self {
+        self.deletion_flag = value;
+        self
+    }
+
+    /// Sets the maximum length of the data of a memo,
+    /// reading a longer memo returns an error instead of allocating memory for it.
+    ///
+    /// By default, only the length of the memo file limits the length of a memo.
+    pub fn max_memo_length(mut self, value: u32) -> Self {
+        self.max_memo_length = Some(value);
+        self
+    }
+
+    /// Sets a number of days added to the julian day numbers read from
+    /// DateTime fields (and Date fields stored in binary), 0 by default.
+    ///
+    /// Some exporters write julian day numbers shifted by a constant,
+    /// e.g. off by one day, this corrects the dates read from such files.
+    ///
+    /// Empty values (a julian day number of 0) are not shifted and stay `None`,
+    /// a shifted number that is not a date between the years 1 and 9999
+    /// gives an [InvalidDate](enum.ErrorKind.html#variant.InvalidDate) error.
+    pub fn julian_day_offset(mut self, days: i32) -> Self {
+        self.julian_day_offset = days;
+        self
+    }
+
+    #[cfg(feature = "encoding")]
+    pub(crate) fn sniffs_character_encoding(&self) -> bool {
+        self.sniff_character_encoding
+    }
+
+    #[cfg(not(feature = "encoding"))]
+    pub(crate) fn sniffs_character_encoding(&self) -> bool {
+        false
+    }
+}
+
+impl Default for ReadingOptions {
+    fn default() -> Self {
+        Self {
+            empty_character_as_none: true,
+            strict_field_types: false,
+            big_endian_binary_fields: false,
+            duplicate_field_names: DuplicateFieldNames::Keep,
+            strict_numeric_values: false,
+            field_name_normalization: FieldNameNormalization::Keep,
+            #[cfg(feature = "encoding")]
+            sniff_character_encoding: false,
+            cap_record_count: false,
+            character_trim: TrimMode::End,
+            encoding: Encoding::Utf8Lossy,
+            include_deleted: false,
+            deletion_flag: DeletionFlag::Standard,
+            max_memo_length: None,
+            julian_day_offset: 0,
+        }
+    }
+}
+
+/// Struct with the handle to the source .dbf file
+/// Responsible for reading the content
+#[derive(Clone, Debug)]
+pub struct Reader<T: Read + Seek> {
+    /// Where the data is read from
+    source: T,
+    memo_reader: Option<MemoReader<T>>,
+    /// The structural compound index (.cdx) of the file, if any
+    index: Option<CompoundIndex>,
+    /// Why the structural index could not be read, it only disables the index order
+    index_error: Option<Arc<std::io::Error>>,
+    header: Header,
+    fields_info: Vec<FieldInfo>,
+    options: ReadingOptions,
+    /// (original name, new name) of the fields renamed because of duplicates
+    renamed_fields: Vec<(String, String)>,
+}
+
+impl<T: Read + Seek> Reader<T> {
+    /// Creates a new reader from the source.
+    ///
+    /// Reads the header and fields information as soon as its created.
+    ///
+    /// Creating a reader from a file path using the [from_path](struct.Reader.html#method.from_path) is the prefered
+    /// way of doing it as it wraps the file in a BufReader for performance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// let records = reader.read()?;
+    /// # Ok(())
+    /// # }
+    ///
+    /// ```
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let f = File::open("tests/data/line.dbf").unwrap();
+    /// let reader = dbase::Reader::new(f)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(source: T) -> Result<Self, Error> {
+        Self::new_with_options(source, ReadingOptions::default())
+    }
+
+    /// Creates a new reader from the source, that will use the given options
+    pub fn new_with_options(mut source: T, mut options: ReadingOptions) -> Result<Self, Error> {
+        let mut header =
+            Header::read_from(&mut source).map_err(|error| Error::io_error(error, 0))?;
+        // Only guess the encoding when the file does not tell it
+        #[cfg(feature = "encoding")]
+        {
+            options.sniff_character_encoding &= header.code_page_mark == 0;
+        }
+        if options.encoding == Encoding::FromHeader {
+            options.encoding = match header.code_page() {
+                Some(code_page) if Encoding::is_code_page_supported(code_page) => {
+                    Encoding::CodePage(code_page)
+                }
+                _ => Encoding::Utf8Lossy,
+            };
+        }
+
+        let offset = if header.file_type.is_visual_fox_pro() {
+            header.offset_to_first_record - BACKLINK_SIZE
+        } else {
+            header.offset_to_first_record
+        };
+        let num_fields =
+            (offset as usize - Header::SIZE - std::mem::size_of::<u8>()) / FieldInfo::SIZE;
+
+        let mut fields_info = Vec::<FieldInfo>::with_capacity(num_fields as usize + 1);
+        fields_info.push(FieldInfo::new_deletion_flag());
+        for _ in 0..num_fields {
+            let info =
+                FieldInfo::read_from(&mut source, &options, header.file_type).map_err(|error| {
+                    Error {
+                        record_num: 0,
+                        field: None,
+                        kind: error,
+                    }
+                })?;
+            fields_info.push(info);
+        }
+
+        // The deletion flag is part of the fields, however some writers
+        // (including older versions of this crate) do not count it in the record size
+        let fields_size = fields_info
+            .iter()
+            .map(|info| u32::from(info.field_length))
+            .sum::<u32>();
+        if fields_size > u32::from(header.size_of_record) + 1 {
+            return Err(Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::FieldsExceedRecordSize,
+            });
+        } else if fields_size == u32::from(header.size_of_record) + 1 {
+            // The records are still stored one after the other,
+            // use their real size to find where they start
+            header.size_of_record = fields_size as u16;
+        }
+
+        let renamed_fields = match options.duplicate_field_names {
+            DuplicateFieldNames::Keep => vec![],
+            DuplicateFieldNames::Error => {
+                if let Some(i) = find_duplicate_field_name(&fields_info) {
+                    return Err(Error {
+                        record_num: 0,
+                        field: Some(fields_info[i].clone()),
+                        kind: ErrorKind::DuplicateFieldName(fields_info[i].name.clone()),
+                    });
+                }
+                vec![]
+            }
+            DuplicateFieldNames::Rename => rename_duplicate_field_names(&mut fields_info),
+        };
+
+        // Visual FoxPro tells which fields are null using
+        // one bit of the _NullFlags field per nullable field,
+        // Varchar and Varbinary fields also get one bit telling if their
+        // value is shorter than the field (it comes before the null bit)
+        let mut next_bit = 0;
+        for info in fields_info.iter_mut().filter(|info| !info.is_hidden()) {
+            if matches!(info.field_type, FieldType::Varchar | FieldType::Varbinary) {
+                info.varlength_bit = Some(next_bit);
+                next_bit += 1;
+            }
+            if info.is_nullable() {
+                info.null_bit = Some(next_bit);
+                next_bit += 1;
+            }
+        }
+
+        let terminator = source
+            .read_u8()
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        debug_assert_eq!(terminator, TERMINATOR_VALUE);
+
+        if options.cap_record_count && header.size_of_record > 0 {
+            let file_size = source
+                .seek(SeekFrom::End(0))
+                .map_err(|error| Error::io_error(error, 0))?;
+            let num_records_in_file = file_size
+                .saturating_sub(u64::from(header.offset_to_first_record))
+                / u64::from(header.size_of_record);
+            if num_records_in_file < u64::from(header.num_records) {
+                header.num_records = num_records_in_file as u32;
+            }
+        }
+
+        source
+            .seek(SeekFrom::Start(u64::from(header.offset_to_first_record)))
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        Ok(Self {
+            source,
+            memo_reader: None,
+            index: None,
+            index_error: None,
+            header,
+            fields_info,
+            options,
+            renamed_fields,
+        })
+    }
+
+    /// Creates a new reader from the source of the dbase file and the source
+    /// of its memo file.
+    ///
+    /// This is useful when the files do not come from the filesystem,
+    /// e.g. when reading both from the entries of an archive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let dbf = std::fs::read("tests/data/shared_memo.dbf").unwrap();
+    /// let fpt = std::fs::read("tests/data/shared_memo.fpt").unwrap();
+    /// let mut reader = dbase::Reader::new_with_memo(
+    ///     Cursor::new(dbf),
+    ///     Cursor::new(fpt),
+    ///     dbase::MemoFileType::FoxBaseMemo,
+    /// )?;
+    /// let records = reader.read()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_memo(
+        source: T,
+        memo_source: T,
+        memo_type: MemoFileType,
+    ) -> Result<Self, Error> {
+        Self::new_with_memo_and_options(source, memo_source, memo_type, ReadingOptions::default())
+    }
+
+    /// Creates a new reader from the source of the dbase file and the source
+    /// of its memo file, that will use the given options
+    pub fn new_with_memo_and_options(
+        source: T,
+        memo_source: T,
+        memo_type: MemoFileType,
+        options: ReadingOptions,
+    ) -> Result<Self, Error> {
+        let mut reader = Self::new_with_options(source, options)?;
+        let mut memo_reader =
+            MemoReader::new(memo_type, memo_source).map_err(|error| Error::io_error(error, 0))?;
+        memo_reader.max_data_length = reader.options.max_memo_length;
+        reader.memo_reader = Some(memo_reader);
+        Ok(reader)
+    }
+
+    /// Returns the header of the file
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the type of the memo file read along with the file, if any
+    ///
+    /// When the reader is created with [from_path](#method.from_path),
+    /// the type is chosen from the version of the file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::MemoFileType;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/shared_memo.dbf")?;
+    /// assert_eq!(reader.memo_file_type(), Some(MemoFileType::FoxBaseMemo));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn memo_file_type(&self) -> Option<MemoFileType> {
+        self.memo_reader.as_ref().map(MemoReader::memo_file_type)
+    }
+
+    /// Returns the fields contained in the opened file
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields_info
+    }
+
+    /// Returns the distinct types of the fields of the file,
+    /// without the types of the internal fields (e.g. `_NullFlags`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::FieldType;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// // Only dBase III types
+    /// let dbase3_types = [
+    ///     FieldType::Character,
+    ///     FieldType::Date,
+    ///     FieldType::Float,
+    ///     FieldType::Numeric,
+    ///     FieldType::Logical,
+    /// ];
+    /// assert!(reader.field_types().iter().all(|t| dbase3_types.contains(t)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn field_types(&self) -> HashSet<FieldType> {
+        self.fields_info
+            .iter()
+            .filter(|info| !info.is_hidden())
+            .map(|info| info.field_type)
+            .collect()
+    }
+
+    /// Returns the dialect the file most likely follows, inferred from its version byte,
+    /// the types of its fields and the format of its memo file.
+    ///
+    /// The files of the FoxBase and FoxPro 2 versions (and of unknown versions)
+    /// are reported as dBase III, unless their fields need a richer dialect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// assert_eq!(reader.detect_dialect(), dbase::Dialect::DbaseIII);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn detect_dialect(&self) -> Dialect {
+        let memo_type = self.memo_reader.as_ref().map(MemoReader::memo_file_type);
+        let dialect = match self.header.file_type {
+            Version::VisualFoxPro => Dialect::VisualFoxPro,
+            Version::DBase4 { .. } => Dialect::DbaseIV,
+            _ if memo_type == Some(MemoFileType::DbaseMemo4) => Dialect::DbaseIV,
+            _ => Dialect::DbaseIII,
+        };
+        let supports_fields = |dialect: Dialect| {
+            self.fields_info
+                .iter()
+                .filter(|info| !info.is_deletion_flag())
+                .all(|info| dialect.supports_field_type(info.field_type))
+        };
+        match dialect {
+            Dialect::DbaseIII if supports_fields(Dialect::DbaseIII) => Dialect::DbaseIII,
+            Dialect::DbaseIII | Dialect::DbaseIV if supports_fields(Dialect::DbaseIV) => {
+                Dialect::DbaseIV
+            }
+            _ => Dialect::VisualFoxPro,
+        }
+    }
+
+    /// Returns the range of bytes of the field named `field_name` in a record,
+    /// relative to the start of the record.
+    ///
+    /// The first byte of a record is the deletion flag,
+    /// so the first field starts at 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// assert_eq!(reader.field_byte_range("name"), Some(1..51));
+    /// assert_eq!(reader.field_byte_range("not_a_field"), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn field_byte_range(&self, field_name: &str) -> Option<std::ops::Range<usize>> {
+        let mut start = 0;
+        for info in &self.fields_info {
+            let end = start + info.field_length as usize;
+            if !info.is_deletion_flag() && info.name == field_name {
+                return Some(start..end);
+            }
+            start = end;
+        }
+        None
+    }
+
+    /// Returns the (original name, new name) of the fields that were renamed
+    /// because their name was already used by another field.
+    ///
+    /// Fields are only renamed when using
+    /// [DuplicateFieldNames::Rename](enum.DuplicateFieldNames.html#variant.Rename)
+    pub fn renamed_fields(&self) -> &[(String, String)] {
+        &self.renamed_fields
+    }
+
+    /// Creates an iterator of records of the type you want
+    ///
+    /// The records marked as deleted are skipped, unless
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    /// is set.
+    pub fn iter_records_as<R: ReadableRecord>(&mut self) -> RecordIterator<'_, T, R> {
+        let deleted_records = self.deleted_records();
+        self.iter_records_with(deleted_records)
+    }
+
+    /// Which records are read, depending on
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    fn deleted_records(&self) -> DeletedRecords {
+        if self.options.include_deleted {
+            DeletedRecords::Include
+        } else {
+            DeletedRecords::Skip
+        }
+    }
+
+    /// Reads the deletion flag of the record starting at `record_pos`,
+    /// leaving the source right after it.
+    fn read_flag_at(&mut self, record_pos: u64) -> std::io::Result<u8> {
+        self.source.seek(SeekFrom::Start(record_pos))?;
+        self.source.read_u8()
+    }
+
+    /// Shortcut function to get an iterator over the [Records](struct.Record.html) in the file
+    pub fn iter_records(&mut self) -> RecordIterator<'_, T, Record> {
+        self.iter_records_as::<Record>()
+    }
+
+    /// Creates an iterator over all the records, the ones marked as deleted included
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/deleted_records.dbf")?;
+    /// assert_eq!(reader.iter_all().count(), 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_all(&mut self) -> RecordIterator<'_, T, Record> {
+        self.iter_records_with(DeletedRecords::Include)
+    }
+
+    /// Creates an iterator over the records marked as deleted only
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/deleted_records.dbf")?;
+    /// for record_result in reader.iter_deleted() {
+    ///     assert!(record_result?.is_deleted());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_deleted(&mut self) -> RecordIterator<'_, T, Record> {
+        self.iter_records_with(DeletedRecords::Only)
+    }
+
+    pub(crate) fn iter_records_with<R: ReadableRecord>(
+        &mut self,
+        deleted_records: DeletedRecords,
+    ) -> RecordIterator<'_, T, R> {
+        RecordIterator {
+            reader: self,
+            record_type: std::marker::PhantomData,
+            current_record: 0,
+            deleted_records,
+        }
+    }
+
+    /// Creates an iterator of records, each one yielded along with the fields of the file
+    /// (without the internal fields, e.g. the deletion flag).
+    ///
+    /// The fields are shared between all the records, so code receiving the records
+    /// can look at the names and types of the fields without needing the reader.
+    ///
+    /// Like [iter_records](#method.iter_records), the records marked as deleted
+    /// are only yielded when
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    /// is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// for result in reader.records_with_schema() {
+    ///     let (schema, record) = result?;
+    ///     for info in schema.iter() {
+    ///         println!("{} ({:?}): {:?}", info.name(), info.field_type(), record.get(info.name()));
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn records_with_schema(&mut self) -> RecordWithSchemaIterator<'_, T> {
+        let schema = self
+            .fields_info
+            .iter()
+            .filter(|info| !info.is_hidden())
+            .cloned()
+            .collect::<Vec<_>>()
+            .into();
+        RecordWithSchemaIterator {
+            records: self.iter_records(),
+            schema,
+        }
+    }
+
+    /// Returns the names of the tags of the structural index of the file,
+    /// in the order they were created.
+    ///
+    /// The structural index (.cdx) is only opened by
+    /// [from_path](struct.Reader.html#method.from_path), when the header says it exists.
+    pub fn index_tags(&self) -> Vec<&str> {
+        self.index.as_ref().map_or_else(Vec::new, |index| {
+            index.tags.iter().map(|tag| tag.name.as_str()).collect()
+        })
+    }
+
+    /// Returns the error that prevented the structural index (.cdx) from being read.
+    ///
+    /// Such an error does not prevent the records from being read,
+    /// the file just behaves as if it had no structural index.
+    pub fn index_error(&self) -> Option<&std::io::Error> {
+        self.index_error.as_deref()
+    }
+
+    /// Creates an iterator of records of the type you want,
+    /// in the order of the tag named `tag_name` (case-insensitive) of the structural index.
+    ///
+    /// Returns `None` if the file has no structural index, if it could not be read
+    /// (see [index_error](#method.index_error)), or if the index has no such tag.
+    ///
+    /// The records marked as deleted are skipped, unless
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    /// is set.
+    pub fn iter_records_in_tag_order_as<R: ReadableRecord>(
+        &mut self,
+        tag_name: &str,
+    ) -> Option<IndexOrderIterator<'_, T, R>> {
+        let record_numbers = self.index.as_ref()?.tag(tag_name)?.record_numbers.clone();
+        let start_pos = self.source.stream_position().ok()?;
+        let deleted_records = self.deleted_records();
+        Some(IndexOrderIterator {
+            reader: self,
+            record_type: std::marker::PhantomData,
+            record_numbers: record_numbers.into_iter(),
+            start_pos,
+            deleted_records,
+        })
+    }
+
+    /// Shortcut function to get an iterator over the [Records](struct.Record.html)
+    /// in the order of the first tag of the structural index.
+    ///
+    /// Returns `None` if the file has no structural index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/indexed.dbf")?;
+    /// if let Some(records) = reader.iter_records_in_index_order() {
+    ///     for record in records {
+    ///         let record = record?;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_records_in_index_order(&mut self) -> Option<IndexOrderIterator<'_, T, Record>> {
+        let tag_name = self.index.as_ref()?.tags.first()?.name.clone();
+        self.iter_records_in_tag_order_as::<Record>(&tag_name)
+    }
+
+    /// Shortcut function to get an iterator over the records, where each record
+    /// is the `(name, value)` pairs of its fields in the order of the file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// for pairs in reader.iter_record_pairs() {
+    ///     for (name, value) in pairs? {
+    ///         println!("{}: {}", name, value);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn iter_record_pairs(
+        &mut self,
+    ) -> impl Iterator<Item = Result<Vec<(String, FieldValue)>, Error>> + '_ {
+        self.iter_records_as::<RecordPairs>()
+            .map(|result| result.map(|pairs| pairs.0))
+    }
+
+    /// Writes the records as newline-delimited JSON (NDJSON):
+    /// each record is written as a JSON object on its own line.
+    ///
+    /// The records are written one at a time, the table is not read in memory.
+    /// The records marked as deleted are only written when
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    /// is set, nothing in the output tells them apart.
+    /// The values are converted as described in
+    /// [From<&FieldValue> for serde_json::Value](enum.FieldValue.html#impl-From%3C%26FieldValue%3E-for-Value).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let mut ndjson = Vec::<u8>::new();
+    /// reader.to_ndjson(&mut ndjson)?;
+    /// assert_eq!(ndjson.iter().filter(|&&b| b == b'\n').count(), 6);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_ndjson<W: Write>(&mut self, mut out: W) -> Result<(), Error> {
+        let mut records = self.iter_records_as::<RecordPairs>();
+        while let Some(pairs) = records.next() {
+            let record_num = records.last_record_num();
+            let object = pairs?
+                .0
+                .iter()
+                .map(|(name, value)| (name.clone(), serde_json::Value::from(value)))
+                .collect::<serde_json::Map<_, _>>();
+            serde_json::to_writer(&mut out, &object).map_err(|error| Error {
+                record_num,
+                field: None,
+                kind: ErrorKind::Message(error.to_string()),
+            })?;
+            out.write_all(b"\n")
+                .map_err(|error| Error::io_error(error, record_num))?;
+        }
+        out.flush().map_err(|error| Error::io_error(error, 0))
+    }
+
+    /// Reads all the records of the file inside a `Vec`
+    pub fn read_as<R: ReadableRecord>(&mut self) -> Result<Vec<R>, Error> {
+        // We don't read the file terminator
+        self.iter_records_as::<R>()
+            .collect::<Result<Vec<R>, Error>>()
+    }
+
+    /// Make the `Reader` read the [Records](struct.Record.html)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// let records = reader.read()?;
+    /// assert_eq!(records.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read(&mut self) -> Result<Vec<Record>, Error> {
+        // We don't read the file terminator
+        self.iter_records().collect::<Result<Vec<Record>, Error>>()
+    }
+
+    /// Reads all the records in a columnar layout,
+    /// where the values of each field are stored in their own `Vec`.
+    ///
+    /// The records are still decoded one after the other,
+    /// each of their values is appended to the column of its field.
+    /// The records marked as deleted are only read when
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    /// is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::Column;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let columns = reader.read_columnar()?;
+    /// assert_eq!(columns.num_records(), 6);
+    /// let names = match columns.get("name") {
+    ///     Some(Column::Character(names)) => names,
+    ///     _ => panic!("name is a Character field"),
+    /// };
+    /// assert_eq!(names[0].as_deref(), Some("Van Dorn Street"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_columnar(&mut self) -> Result<Columns, Error> {
+        let fields_info = self
+            .fields_info
+            .iter()
+            .filter(|info| !info.is_hidden())
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut columns = fields_info
+            .iter()
+            .map(|info| Column::new(info.field_type).expect("hidden fields are filtered"))
+            .collect::<Vec<_>>();
+
+        let mut records = self.iter_records_as::<RecordPairs>();
+        while let Some(pairs) = records.next() {
+            let record_num = records.last_record_num();
+            for ((column, info), (_, value)) in columns.iter_mut().zip(&fields_info).zip(pairs?.0) {
+                column.push(value).map_err(|_| Error {
+                    record_num,
+                    field: Some(info.clone()),
+                    kind: ErrorKind::IncompatibleType,
+                })?;
+            }
+        }
+        Ok(Columns {
+            names: fields_info.into_iter().map(|info| info.name).collect(),
+            columns,
+        })
+    }
+
+    /// Returns the fields of the file along with its first record,
+    /// or `None` when the file has no records.
+    ///
+    /// The first record is the first one [iter_records](#method.iter_records) yields,
+    /// so records marked as deleted are passed over unless
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    /// is set.
+    ///
+    /// The internal fields (e.g. the deletion flag) are not returned,
+    /// and the position of the reader is left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+    /// let (fields, first_record) = reader.preview()?;
+    /// assert_eq!(fields[0].name(), "name");
+    /// assert!(first_record.is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn preview(&mut self) -> Result<(Vec<FieldInfo>, Option<Record>), Error> {
+        let fields = self
+            .fields_info
+            .iter()
+            .filter(|info| !info.is_hidden())
+            .cloned()
+            .collect();
+
+        let start_pos = self
+            .source
+            .stream_position()
+            .map_err(|error| Error::io_error(error, 0))?;
+        self.seek(0)?;
+        let first_record = self.iter_records().next().transpose();
+        self.source
+            .seek(SeekFrom::Start(start_pos))
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        Ok((fields, first_record?))
+    }
+
+    /// Returns an estimation of the memory (in bytes) needed to load
+    /// all the records of the file as [Records](struct.Record.html).
+    ///
+    /// The estimation considers that Character fields use all their declared length
+    /// and that Memo fields use one block of the memo file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// if reader.estimated_memory() < 1024 * 1024 {
+    ///     // it is fine to read everything at once
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn estimated_memory(&self) -> u64 {
+        let memo_block_size = self
+            .memo_reader
+            .as_ref()
+            .map_or(0, |memo_reader| memo_reader.block_size());
+        let record_size = self
+            .fields_info
+            .iter()
+            .filter(|info| !info.is_hidden())
+            .map(|info| {
+                let value_size = match info.field_type {
+                    FieldType::Character => u64::from(info.field_length),
+                    FieldType::Memo | FieldType::General | FieldType::Picture => {
+                        u64::from(memo_block_size)
+                    }
+                    _ => 0,
+                };
+                (std::mem::size_of::<String>()
+                    + info.name.len()
+                    + std::mem::size_of::<FieldValue>()) as u64
+                    + value_size
+            })
+            .sum::<u64>()
+            + std::mem::size_of::<Record>() as u64;
+        u64::from(self.header.num_records) * record_size
+    }
+
+    /// Reads the content of all the memo blocks referenced by the records,
+    /// keyed by their block index.
+    ///
+    /// Blocks referenced by many records are only read once,
+    /// and only the Memo fields of the records are read.
+    ///
+    /// The records marked as deleted are skipped, unless
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    /// is set.
+    ///
+    /// Returns a `MissingMemoFile` error if the file has Memo fields,
+    /// but its memo file was not opened.
+    pub fn collect_memos(&mut self) -> Result<HashMap<u32, String>, Error> {
+        let mut memo_fields = vec![];
+        let mut field_offset = 0u64;
+        for info in &self.fields_info {
+            if info.field_type == FieldType::Memo && !info.is_deletion_flag() {
+                memo_fields.push((info, field_offset));
+            }
+            field_offset += u64::from(info.field_length);
+        }
+
+        let mut memos = HashMap::new();
+        if memo_fields.is_empty() {
+            return Ok(memos);
+        }
+        let deleted_records = self.deleted_records();
+        let memo_reader = self.memo_reader.as_mut().ok_or_else(|| Error {
+            record_num: 0,
+            field: Some(memo_fields[0].0.clone()),
+            kind: ErrorKind::MissingMemoFile,
+        })?;
+
+        let start_pos = self
+            .source
+            .stream_position()
+            .map_err(|error| Error::io_error(error, 0))?;
+        for record_num in 0..self.header.num_records {
+            let record_pos = u64::from(self.header.offset_to_first_record)
+                + u64::from(record_num) * u64::from(self.header.size_of_record);
+            // The memo reader is borrowed, so the flag is read without read_flag_at
+            let flag = match self.source.seek(SeekFrom::Start(record_pos)) {
+                Ok(_) => self.source.read_u8(),
+                Err(error) => Err(error),
+            }
+            .map_err(|error| Error::io_error(error, record_num as usize))?;
+            if deleted_records.skips(self.options.deletion_flag.is_deleted(flag)) {
+                continue;
+            }
+            for &(field_info, field_offset) in &memo_fields {
+                let to_error = |kind| Error {
+                    record_num: record_num as usize,
+                    field: Some(field_info.clone()),
+                    kind,
+                };
+                self.source
+                    .seek(SeekFrom::Start(record_pos + field_offset))
+                    .map_err(|error| to_error(ErrorKind::IoError(error)))?;
+                let block_index = match read_memo_index(&mut self.source, field_info) {
+                    // The block 0 is where the header of the memo file is
+                    Ok(Some(0)) | Ok(None) => continue,
+                    Ok(Some(block_index)) => block_index,
+                    Err(kind) => return Err(to_error(kind)),
+                };
+                if let Entry::Vacant(entry) = memos.entry(block_index) {
+                    match memo_reader.read_data_at(block_index) {
+                        Ok(data) => entry.insert(self.options.encoding.decode(data)),
+                        Err(error) => {
+                            return Err(to_error(memo_reader.read_error(block_index, error)))
+                        }
+                    };
+                }
+            }
+        }
+        self.source
+            .seek(SeekFrom::Start(start_pos))
+            .map_err(|error| Error::io_error(error, 0))?;
+        Ok(memos)
+    }
+
+    /// Counts the records for which the field named `field_name` is equal to `value`
+    ///
+    /// Only the requested field is read in each record, the other ones are skipped.
+    /// The records marked as deleted are not counted, unless
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    /// is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let value = dbase::FieldValue::Character(Some("red".to_string()));
+    /// assert_eq!(reader.count_where_eq("line", &value)?, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn count_where_eq(&mut self, field_name: &str, value: &FieldValue) -> Result<u32, Error> {
+        let mut count = 0;
+        self.for_each_value_of(field_name, FieldValue::read_from, |_, _, field_value| {
+            if &field_value == value {
+                count += 1;
+            }
+            Ok(())
+        })?;
+        Ok(count)
+    }
+
+    /// Reads the values of the field named `field_name` of all the records,
+    /// converted to `V`, with `None` for the null values (e.g. blank Numeric fields).
+    ///
+    /// Only the requested field is read in each record, the other ones are skipped.
+    /// The records marked as deleted are skipped, unless
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    /// is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let lines = reader.column_as::<String>("line")?;
+    /// assert_eq!(lines[0], Some("blue".to_string()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn column_as<V>(&mut self, field_name: &str) -> Result<Vec<Option<V>>, Error>
+    where
+        V: TryFrom<FieldValue, Error = FieldConversionError>,
+    {
+        let mut values = Vec::with_capacity(self.header.num_records as usize);
+        self.for_each_value_of(
+            field_name,
+            FieldValue::read_from,
+            |record_num, field_info, field_value| {
+                match V::try_from(field_value) {
+                    Ok(value) => values.push(Some(value)),
+                    Err(FieldConversionError::NoneValue) => values.push(None),
+                    Err(error) => {
+                        return Err(Error {
+                            record_num,
+                            field: Some(field_info.clone()),
+                            kind: error.into(),
+                        })
+                    }
+                }
+                Ok(())
+            },
+        )?;
+        Ok(values)
+    }
+
+    /// Reads the text stored in the file of the Numeric or Float field named `field_name`
+    /// of all the records, with `None` for the blank and overflowed (`*`) values.
+    ///
+    /// Unlike the `FieldValue` read for these fields, the text keeps all the digits
+    /// of values that have more than the 15 significant digits a `f64` can hold
+    /// (e.g. 18 digits account numbers), so they can be parsed to an integer or a decimal type.
+    ///
+    /// Returns an `IncompatibleType` error if the field is neither a Numeric nor a Float one.
+    /// The records marked as deleted are skipped, unless
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    /// is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/long_numeric.dbf")?;
+    /// let accounts = reader.column_raw("ACCOUNT")?;
+    /// assert_eq!(accounts[0].as_deref(), Some("123456789012345678"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn column_raw(&mut self, field_name: &str) -> Result<Vec<Option<String>>, Error> {
+        if let Some(info) = self.fields_info.iter().find(|info| {
+            !info.is_deletion_flag()
+                && info.name == field_name
+                && info.field_type != FieldType::Numeric
+                && info.field_type != FieldType::Float
+        }) {
+            return Err(Error {
+                record_num: 0,
+                field: Some(info.clone()),
+                kind: ErrorKind::IncompatibleType,
+            });
+        }
+        let mut values = Vec::with_capacity(self.header.num_records as usize);
+        self.for_each_value_of(
+            field_name,
+            |source, _, field_info, options| read_numeric_text(source, field_info, options),
+            |_, _, text| {
+                values.push(text);
+                Ok(())
+            },
+        )?;
+        Ok(values)
+    }
+
+    /// Reads the value of the field named `field_name` of each record with `read`,
+    /// calling `f` with the record number, the field and the value.
+    ///
+    /// The records marked as deleted are skipped, unless
+    /// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+    /// is set.
+    ///
+    /// The position of the source is restored afterwards, even when an error is returned.
+    fn for_each_value_of<V, R, F>(
+        &mut self,
+        field_name: &str,
+        mut read: R,
+        mut f: F,
+    ) -> Result<(), Error>
+    where
+        R: FnMut(
+            &mut T,
+            &mut Option<MemoReader<T>>,
+            &FieldInfo,
+            &ReadingOptions,
+        ) -> Result<V, ErrorKind>,
+        F: FnMut(usize, &FieldInfo, V) -> Result<(), Error>,
+    {
+        let index = self
+            .fields_info
+            .iter()
+            .position(|info| !info.is_deletion_flag() && info.name == field_name)
+            .ok_or_else(|| Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::Message(format!("No field named '{}'", field_name)),
+            })?;
+        let field_offset = self.fields_info[..index]
+            .iter()
+            .map(|info| u64::from(info.field_length))
+            .sum::<u64>();
+
+        let start_pos = self
+            .source
+            .stream_position()
+            .map_err(|error| Error::io_error(error, 0))?;
+        let deleted_records = self.deleted_records();
+        let result = (0..self.header.num_records).try_for_each(|record_num| {
+            let record_pos = u64::from(self.header.offset_to_first_record)
+                + u64::from(record_num) * u64::from(self.header.size_of_record);
+            let flag = self
+                .read_flag_at(record_pos)
+                .map_err(|error| Error::io_error(error, record_num as usize))?;
+            if deleted_records.skips(self.options.deletion_flag.is_deleted(flag)) {
+                return Ok(());
+            }
+            let field_info = &self.fields_info[index];
+            self.source
+                .seek(SeekFrom::Start(record_pos + field_offset))
+                .map_err(|error| Error::io_error(error, record_num as usize))?;
+            let field_value = read(
+                &mut self.source,
+                &mut self.memo_reader,
+                field_info,
+                &self.options,
+            )
+            .map_err(|kind| Error {
+                record_num: record_num as usize,
+                field: Some(field_info.clone()),
+                kind,
+            })?;
+            f(record_num as usize, field_info, field_value)
+        });
+        self.source
+            .seek(SeekFrom::Start(start_pos))
+            .map_err(|error| Error::io_error(error, 0))?;
+        result
+    }
 
-impl AsMut<HashMap<String, FieldValue>> for Record {
-    fn as_mut(&mut self) -> &mut HashMap<String, FieldValue> {
-        &mut self.map
+    /// Reads the record at the current position of the source
+    fn read_current_record_as<R: ReadableRecord>(&mut self, record_num: u32) -> Result<R, Error> {
+        let flag = self
+            .source
+            .read_u8()
+            .map_err(|error| Error::io_error(error, record_num as usize))?;
+        self.read_current_record_after_flag_as(record_num, flag)
     }
-}
 
-/// Structs containing the information allowing to
-/// create a new TableWriter which would write file
-/// with the same record structure as another dbase file.
-///
-/// You can get this by using [Reader::into_table_info].
-#[derive(Clone, Debug)]
-pub struct TableInfo {
-    pub(crate) header: Header,
-    pub(crate) fields_info: Vec<FieldInfo>,
-}
+    /// Reads the record whose deletion flag, `flag`, was just read from the source
+    fn read_current_record_after_flag_as<R: ReadableRecord>(
+        &mut self,
+        record_num: u32,
+        flag: u8,
+    ) -> Result<R, Error> {
+        let null_flags = self
+            .read_current_null_flags()
+            .map_err(|error| Error::io_error(error, record_num as usize))?;
+        let deleted = self.options.deletion_flag.is_deleted(flag);
+        let mut iter = FieldIterator {
+            source: &mut self.source,
+            // The deletion flag is always the first field
+            fields_info: self.fields_info[1..].iter().peekable(),
+            memo_reader: &mut self.memo_reader,
+            options: &self.options,
+            null_flags,
+            deleted,
+        };
 
-/// Struct with the handle to the source .dbf file
-/// Responsible for reading the content
-#[derive(Clone, Debug)]
-pub struct Reader<T: Read + Seek> {
-    /// Where the data is read from
-    source: T,
-    memo_reader: Option<MemoReader<T>>,
-    header: Header,
-    fields_info: Vec<FieldInfo>,
-}
+        let record = R::read_using(&mut iter)
+            .and_then(|record| iter.skip_remaining_fields().and(Ok(record)))
+            .map_err(|error| Error::new(error, record_num as usize))?;
 
-impl<T: Read + Seek> Reader<T> {
-    /// Creates a new reader from the source.
+        // Records may have some unused bytes after their last field
+        let fields_size = self
+            .fields_info
+            .iter()
+            .map(|info| i64::from(info.field_length))
+            .sum::<i64>();
+        let padding = i64::from(self.header.size_of_record) - fields_size;
+        if padding > 0 {
+            self.source
+                .seek(SeekFrom::Current(padding))
+                .map_err(|error| Error::io_error(error, record_num as usize))?;
+        }
+        Ok(record)
+    }
+
+    /// Reads the content of the _NullFlags field of the record whose deletion flag
+    /// was just read, without moving the source.
     ///
-    /// Reads the header and fields information as soon as its created.
+    /// Returns an empty vec if there is no such field.
+    fn read_current_null_flags(&mut self) -> std::io::Result<Vec<u8>> {
+        let mut offset = 0i64;
+        for info in &self.fields_info[1..] {
+            if info.field_type == FieldType::NullFlags {
+                let mut null_flags = vec![0u8; info.field_length as usize];
+                self.source.seek(SeekFrom::Current(offset))?;
+                self.source.read_exact(&mut null_flags)?;
+                self.source
+                    .seek(SeekFrom::Current(-(offset + i64::from(info.field_length))))?;
+                return Ok(null_flags);
+            }
+            offset += i64::from(info.field_length);
+        }
+        Ok(vec![])
+    }
+
+    /// Returns whether the record at `index` (starting at 0) is marked as deleted.
     ///
-    /// Creating a reader from a file path using the [from_path](struct.Reader.html#method.from_path) is the prefered
-    /// way of doing it as it wraps the file in a BufReader for performance.
+    /// Only the deletion flag of the record is read,
+    /// and the position of the reader is left unchanged.
     ///
     /// # Example
     ///
     /// ```
     /// # fn main() -> Result<(), dbase::Error> {
     /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
-    /// let records = reader.read()?;
+    /// assert!(!reader.is_deleted(0)?);
+    /// assert!(reader.is_deleted(1).is_err());
     /// # Ok(())
     /// # }
-    ///
     /// ```
+    pub fn is_deleted(&mut self, index: u32) -> Result<bool, Error> {
+        if index >= self.header.num_records {
+            return Err(Error {
+                record_num: index as usize,
+                field: None,
+                kind: ErrorKind::Message(format!("No record at index {}", index)),
+            });
+        }
+        let start_pos = self
+            .source
+            .stream_position()
+            .map_err(|error| Error::io_error(error, index as usize))?;
+        let pos = u64::from(self.header.offset_to_first_record)
+            + u64::from(index) * u64::from(self.header.size_of_record);
+        let flag = self
+            .source
+            .seek(SeekFrom::Start(pos))
+            .and_then(|_| self.source.read_u8());
+        self.source
+            .seek(SeekFrom::Start(start_pos))
+            .map_err(|error| Error::io_error(error, index as usize))?;
+        flag.map(|flag| self.options.deletion_flag.is_deleted(flag))
+            .map_err(|error| Error::io_error(error, index as usize))
+    }
+
+    /// Reads the record at `index` (starting at 0).
+    ///
+    /// As all the records have the same size, the record is read directly
+    /// without reading the ones before it,
+    /// and the position of the reader is left unchanged.
+    ///
+    /// # Example
     ///
     /// ```
-    /// use std::fs::File;
+    /// use dbase::FieldValue;
     /// # fn main() -> Result<(), dbase::Error> {
-    /// let f = File::open("tests/data/line.dbf").unwrap();
-    /// let reader = dbase::Reader::new(f)?;
+    /// let mut reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+    /// let record = reader.record(5)?;
+    /// assert_eq!(
+    ///     record.get("name"),
+    ///     Some(&FieldValue::Character(Some("Metro Center".to_owned())))
+    /// );
+    /// assert!(reader.record(6).is_err());
     /// # Ok(())
     /// # }
     /// ```
-    pub fn new(mut source: T) -> Result<Self, Error> {
-        let header = Header::read_from(&mut source).map_err(|error| Error::io_error(error, 0))?;
-
-        let offset = if header.file_type.is_visual_fox_pro() {
-            header.offset_to_first_record - BACKLINK_SIZE
-        } else {
-            header.offset_to_first_record
-        };
-        let num_fields =
-            (offset as usize - Header::SIZE - std::mem::size_of::<u8>()) / FieldInfo::SIZE;
+    pub fn record(&mut self, index: usize) -> Result<Record, Error> {
+        if index >= self.header.num_records as usize {
+            return Err(Error {
+                record_num: index,
+                field: None,
+                kind: ErrorKind::Message(format!("No record at index {}", index)),
+            });
+        }
+        let start_pos = self
+            .source
+            .stream_position()
+            .map_err(|error| Error::io_error(error, index))?;
+        let record = self
+            .seek(index)
+            .and_then(|_| self.read_current_record_as::<Record>(index as u32));
+        self.source
+            .seek(SeekFrom::Start(start_pos))
+            .map_err(|error| Error::io_error(error, index))?;
+        record
+    }
 
-        let mut fields_info = Vec::<FieldInfo>::with_capacity(num_fields as usize + 1);
-        fields_info.push(FieldInfo::new_deletion_flag());
-        for _ in 0..num_fields {
-            let info = FieldInfo::read_from(&mut source).map_err(|error| Error {
+    /// Writes a copy of the file to `out`, with the text of its Character fields
+    /// converted from the code page of the file to the `target` one.
+    ///
+    /// The language driver byte of the header is updated accordingly.
+    /// Characters that do not exist in the target code page are replaced by '?'.
+    ///
+    /// Tables with Memo fields are rejected, as their text is stored in the memo file,
+    /// which is not rewritten. General and Picture fields hold binary data,
+    /// they are kept as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use dbase::LanguageDriver;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/cp850.dbf")?;
+    /// let mut out = Cursor::new(Vec::<u8>::new());
+    /// reader.reencode(&mut out, LanguageDriver::Cp1252)?;
+    ///
+    /// out.set_position(0);
+    /// let reencoded = dbase::Reader::new(out)?;
+    /// assert_eq!(reencoded.header().code_page(), Some(1252));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reencode<W: Write + Seek>(
+        &mut self,
+        mut out: W,
+        target: LanguageDriver,
+    ) -> Result<(), Error> {
+        if let Some(memo_field) = self
+            .fields_info
+            .iter()
+            .find(|info| info.field_type == FieldType::Memo)
+        {
+            return Err(Error {
+                record_num: 0,
+                field: Some(memo_field.clone()),
+                kind: ErrorKind::Message(
+                    "Cannot convert the text of Memo fields, it is stored in the memo file"
+                        .to_string(),
+                ),
+            });
+        }
+        let source_driver = self
+            .header
+            .code_page()
+            .and_then(LanguageDriver::from_code_page)
+            .ok_or_else(|| Error {
                 record_num: 0,
                 field: None,
-                kind: error,
+                kind: ErrorKind::Message(format!(
+                    "Cannot convert text from code page {:?}",
+                    self.header.code_page()
+                )),
             })?;
-            fields_info.push(info);
-        }
-
-        let terminator = source
-            .read_u8()
+        let start_pos = self
+            .source
+            .stream_position()
+            .map_err(|error| Error::io_error(error, 0))?;
+        let result = self.write_reencoded(&mut out, source_driver, target);
+        self.source
+            .seek(SeekFrom::Start(start_pos))
             .map_err(|error| Error::io_error(error, 0))?;
+        result
+    }
 
-        debug_assert_eq!(terminator, TERMINATOR_VALUE);
+    fn write_reencoded<W: Write>(
+        &mut self,
+        out: &mut W,
+        source_driver: LanguageDriver,
+        target: LanguageDriver,
+    ) -> Result<(), Error> {
+        let mut character_ranges = vec![];
+        let mut start = 0;
+        for info in &self.fields_info {
+            let end = start + info.field_length as usize;
+            if info.field_type == FieldType::Character {
+                character_ranges.push(start..end);
+            }
+            start = end;
+        }
 
-        source
-            .seek(SeekFrom::Start(u64::from(header.offset_to_first_record)))
+        let mut header = self.read_raw_header()?;
+        header[29] = target.code_page_mark();
+        out.write_all(&header)
             .map_err(|error| Error::io_error(error, 0))?;
 
-        Ok(Self {
-            source,
-            memo_reader: None,
-            header,
-            fields_info,
-        })
-    }
+        let mut record = vec![0u8; self.header.size_of_record as usize];
+        for record_num in 0..self.header.num_records as usize {
+            self.source
+                .read_exact(&mut record)
+                .map_err(|error| Error::io_error(error, record_num))?;
+            for range in &character_ranges {
+                let text = source_driver.decode(&record[range.clone()]);
+                // Both code pages use one byte per character
+                record[range.clone()].copy_from_slice(&target.encode(&text));
+            }
+            out.write_all(&record)
+                .map_err(|error| Error::io_error(error, record_num))?;
+        }
 
-    /// Returns the header of the file
-    pub fn header(&self) -> &Header {
-        &self.header
+        // The end of file marker, if any
+        std::io::copy(&mut self.source, out)
+            .and_then(|_| out.flush())
+            .map_err(|error| Error::io_error(error, self.header.num_records as usize))
     }
 
-    /// Returns the fields contained in the opened file
-    pub fn fields(&self) -> &[FieldInfo] {
-        &self.fields_info
+    /// Writes a copy of the file to `out` and a copy of its memo file to `memo_out`
+    /// that only keeps the memo blocks referenced by a record
+    /// (the memo file equivalent of PACK).
+    ///
+    /// The memo indices of the records are rewritten to point to the new blocks,
+    /// the data referenced by many records is written once.
+    /// Records marked as deleted are copied with their memos.
+    ///
+    /// Returns an error if the file has no memo file or if it is a dBase IV memo file,
+    /// which cannot be written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut reader = dbase::Reader::from_path("tests/data/shared_memo.dbf")?;
+    /// let mut dbf = Cursor::new(Vec::<u8>::new());
+    /// let mut fpt = Cursor::new(Vec::<u8>::new());
+    /// reader.pack_memo(&mut dbf, &mut fpt)?;
+    ///
+    /// dbf.set_position(0);
+    /// fpt.set_position(0);
+    /// let mut packed = dbase::Reader::new_with_memo(dbf, fpt, dbase::MemoFileType::FoxBaseMemo)?;
+    /// assert_eq!(packed.read()?, reader.read()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pack_memo<W: Write, M: Write + Seek>(
+        &mut self,
+        mut out: W,
+        memo_out: M,
+    ) -> Result<(), Error> {
+        let memo_type = self
+            .memo_reader
+            .as_ref()
+            .map(|memo_reader| memo_reader.memo_file_type())
+            .ok_or(Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::MissingMemoFile,
+            })?;
+        let mut memo_writer =
+            MemoWriter::new(memo_type, memo_out).map_err(|error| Error::io_error(error, 0))?;
+        let start_pos = self
+            .source
+            .stream_position()
+            .map_err(|error| Error::io_error(error, 0))?;
+        let result = self.write_packed(&mut out, &mut memo_writer);
+        self.source
+            .seek(SeekFrom::Start(start_pos))
+            .map_err(|error| Error::io_error(error, 0))?;
+        result?;
+        memo_writer
+            .finish()
+            .map_err(|error| Error::io_error(error, self.header.num_records as usize))
     }
 
-    /// Creates an iterator of records of the type you want
-    pub fn iter_records_as<R: ReadableRecord>(&mut self) -> RecordIterator<T, R> {
-        RecordIterator {
-            reader: self,
-            record_type: std::marker::PhantomData,
-            current_record: 0,
-        }
+    fn write_packed<W: Write, M: Write>(
+        &mut self,
+        out: &mut W,
+        memo_writer: &mut MemoWriter<M>,
+    ) -> Result<(), Error> {
+        let header = self.read_raw_header()?;
+        out.write_all(&header)
+            .map_err(|error| Error::io_error(error, 0))?;
+
+        self.copy_records_with_memo(out, memo_writer, DeletedRecords::Include)?;
+
+        // The end of file marker, if any
+        std::io::copy(&mut self.source, out)
+            .and_then(|_| out.flush())
+            .map_err(|error| Error::io_error(error, self.header.num_records as usize))
     }
 
-    /// Shortcut function to get an iterator over the [Records](struct.Record.html) in the file
-    pub fn iter_records(&mut self) -> RecordIterator<T, Record> {
-        self.iter_records_as::<Record>()
+    /// Reads the bytes of the header, the field descriptors included
+    pub(crate) fn read_raw_header(&mut self) -> Result<Vec<u8>, Error> {
+        let mut header = vec![0u8; self.header.offset_to_first_record as usize];
+        self.source
+            .seek(SeekFrom::Start(0))
+            .and_then(|_| self.source.read_exact(&mut header))
+            .map_err(|error| Error::io_error(error, 0))?;
+        Ok(header)
     }
 
-    /// Reads all the records of the file inside a `Vec`
-    pub fn read_as<R: ReadableRecord>(&mut self) -> Result<Vec<R>, Error> {
-        // We don't read the file terminator
-        self.iter_records_as::<R>()
-            .collect::<Result<Vec<R>, Error>>()
+    /// Copies the bytes of the records to `out`, the data of their memo fields is copied
+    /// using the `memo_writer` and their block indices are changed accordingly.
+    ///
+    /// Returns the number of records copied.
+    pub(crate) fn copy_records_with_memo<W: Write, M: Write>(
+        &mut self,
+        out: &mut W,
+        memo_writer: &mut MemoWriter<M>,
+        deleted_records: DeletedRecords,
+    ) -> Result<u32, Error> {
+        let mut memo_ranges = vec![];
+        let mut start = 0;
+        for info in &self.fields_info {
+            let end = start + info.field_length as usize;
+            if info.field_type.uses_memo_file() {
+                memo_ranges.push((info, start..end));
+            }
+            start = end;
+        }
+        let memo_reader = &mut self.memo_reader;
+        if let (None, Some((info, _))) = (memo_reader.as_ref(), memo_ranges.first()) {
+            return Err(Error {
+                record_num: 0,
+                field: Some((*info).clone()),
+                kind: ErrorKind::MissingMemoFile,
+            });
+        }
+
+        let source = &mut self.source;
+        source
+            .seek(SeekFrom::Start(u64::from(
+                self.header.offset_to_first_record,
+            )))
+            .map_err(|error| Error::io_error(error, 0))?;
+        // Old index -> new index, for the data shared by many records
+        let mut new_indices = HashMap::<u32, u32>::new();
+        let mut record = vec![0u8; self.header.size_of_record as usize];
+        let mut num_copied = 0;
+        for record_num in 0..self.header.num_records as usize {
+            source
+                .read_exact(&mut record)
+                .map_err(|error| Error::io_error(error, record_num))?;
+            let is_deleted = self.options.deletion_flag.is_deleted(record[0]);
+            if deleted_records.skips(is_deleted) {
+                continue;
+            }
+            for (info, range) in &memo_ranges {
+                let memo_reader = memo_reader
+                    .as_mut()
+                    .expect("the memo reader is checked before the records");
+                let to_error = |kind| Error {
+                    record_num,
+                    field: Some((*info).clone()),
+                    kind,
+                };
+                let index = match read_memo_index(&mut &record[range.clone()], info) {
+                    Ok(Some(index)) => index,
+                    Ok(None) => continue,
+                    Err(kind) => return Err(to_error(kind)),
+                };
+                let new_index = match new_indices.entry(index) {
+                    Entry::Occupied(entry) => *entry.get(),
+                    Entry::Vacant(entry) => {
+                        // The type of the block (text, picture) is kept
+                        let new_index = match memo_reader.read_raw_data_at(index) {
+                            Ok((block_type, data)) => {
+                                memo_writer.write_data_with_type(data, block_type)
+                            }
+                            Err(error) => {
+                                return Err(to_error(memo_reader.read_error(index, error)))
+                            }
+                        }
+                        .map_err(|error| to_error(ErrorKind::IoError(error)))?;
+                        *entry.insert(new_index)
+                    }
+                };
+                let field_bytes = &mut record[range.clone()];
+                if field_bytes.len() > 4 {
+                    let text = format!("{:>width$}", new_index, width = field_bytes.len());
+                    field_bytes.copy_from_slice(text.as_bytes());
+                } else {
+                    field_bytes.copy_from_slice(&new_index.to_le_bytes());
+                }
+            }
+            out.write_all(&record)
+                .map_err(|error| Error::io_error(error, record_num))?;
+            num_copied += 1;
+        }
+        Ok(num_copied)
     }
 
-    /// Make the `Reader` read the [Records](struct.Record.html)
+    /// Returns a human readable description of the raw bytes of the record at `index`,
+    /// annotated by field.
     ///
-    /// # Examples
+    /// For each field, the dump gives its name, type, byte range in the record,
+    /// raw bytes in hex and decoded value (or the error that occurred when decoding it),
+    /// which helps to diagnose malformed records.
+    ///
+    /// The position of the reader is not changed.
+    ///
+    /// # Example
     ///
     /// ```
-    /// use std::fs::File;
     /// # fn main() -> Result<(), dbase::Error> {
     /// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
-    /// let records = reader.read()?;
-    /// assert_eq!(records.len(), 1);
+    /// println!("{}", reader.debug_record(0)?);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn read(&mut self) -> Result<Vec<Record>, Error> {
-        // We don't read the file terminator
-        self.iter_records().collect::<Result<Vec<Record>, Error>>()
+    pub fn debug_record(&mut self, index: u32) -> Result<String, Error> {
+        if index >= self.header.num_records {
+            return Err(Error {
+                record_num: index as usize,
+                field: None,
+                kind: ErrorKind::Message(format!("No record at index {}", index)),
+            });
+        }
+        let start_pos = self
+            .source
+            .stream_position()
+            .map_err(|error| Error::io_error(error, index as usize))?;
+        let dump = self.dump_record(index);
+        self.source
+            .seek(SeekFrom::Start(start_pos))
+            .map_err(|error| Error::io_error(error, index as usize))?;
+        dump.map_err(|error| Error::io_error(error, index as usize))
+    }
+
+    fn dump_record(&mut self, index: u32) -> std::io::Result<String> {
+        let record_pos = u64::from(self.header.offset_to_first_record)
+            + u64::from(index) * u64::from(self.header.size_of_record);
+        let mut bytes = vec![0u8; self.header.size_of_record as usize];
+        self.source.seek(SeekFrom::Start(record_pos))?;
+        self.source.read_exact(&mut bytes)?;
+
+        let to_hex = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|byte| format!("{:02X}", byte))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut dump = format!("Record {} ({} bytes)\n", index, bytes.len());
+        let mut start = 0;
+        for info in &self.fields_info {
+            let end = start + info.field_length as usize;
+            let raw = bytes.get(start..end).unwrap_or(&[]);
+            if info.is_deletion_flag() {
+                let status = match raw.first() {
+                    Some(&flag) if self.options.deletion_flag.is_deleted(flag) => "deleted",
+                    _ => "not deleted",
+                };
+                dump += &format!(
+                    "  deletion flag [{}..{}]: {} ({})\n",
+                    start,
+                    end,
+                    to_hex(raw),
+                    status
+                );
+            } else {
+                self.source
+                    .seek(SeekFrom::Start(record_pos + start as u64))?;
+                let value = match FieldValue::read_from(
+                    &mut self.source,
+                    &mut self.memo_reader,
+                    info,
+                    &self.options,
+                ) {
+                    Ok(value) => format!("{:?}", value),
+                    Err(kind) => format!("error: {}", kind),
+                };
+                dump += &format!(
+                    "  {} ({}) [{}..{}]: {} => {}\n",
+                    info.name,
+                    char::from(u8::from(info.field_type)),
+                    start,
+                    end,
+                    to_hex(raw),
+                    value
+                );
+            }
+            start = end;
+        }
+        Ok(dump)
     }
 
     /// Seek to the start of the record at `index`
@@ -275,6 +2267,15 @@ impl<T: Read + Seek> Reader<T> {
         Ok(())
     }
 
+    /// Returns the info that allow to create a writer with
+    /// the same structure, without consuming the reader.
+    pub(crate) fn table_info(&self) -> TableInfo {
+        TableInfo {
+            header: self.header,
+            fields_info: self.fields_info.clone(),
+        }
+    }
+
     /// Consumes the reader, and returns the info that
     /// allow to create a writer that would write a file
     /// with the same structure.
@@ -313,17 +2314,25 @@ impl Reader<BufReader<File>> {
     /// # }
     /// ```
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_path_with_options(path, ReadingOptions::default())
+    }
+
+    /// Creates a new dbase Reader from a path, that will use the given options
+    pub fn from_path_with_options<P: AsRef<Path>>(
+        path: P,
+        options: ReadingOptions,
+    ) -> Result<Self, Error> {
         let p = path.as_ref().to_owned();
         let bufreader =
             BufReader::new(File::open(path).map_err(|error| Error::io_error(error, 0))?);
-        let mut reader = Reader::new(bufreader)?;
+        let mut reader = Reader::new_with_options(bufreader, options)?;
         let at_least_one_field_is_memo = reader
             .fields_info
             .iter()
-            .any(|f_info| f_info.field_type == FieldType::Memo);
+            .any(|f_info| f_info.field_type.uses_memo_file());
 
         if at_least_one_field_is_memo {
-            let memo_type = dbg!(reader.header.file_type.supported_memo_type());
+            let memo_type = reader.header.file_type.supported_memo_type();
             if let Some(mt) = memo_type {
                 let memo_path = match mt {
                     MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => p.with_extension("dbt"),
@@ -336,15 +2345,64 @@ impl Reader<BufReader<File>> {
                     kind: ErrorKind::ErrorOpeningMemoFile(error),
                 })?;
 
-                let memo_reader = MemoReader::new(mt, BufReader::new(memo_file))
+                let mut memo_reader = MemoReader::new(mt, BufReader::new(memo_file))
                     .map_err(|error| Error::io_error(error, 0))?;
+                memo_reader.max_data_length = reader.options.max_memo_length;
                 reader.memo_reader = Some(memo_reader);
             }
         }
+
+        // A bogus index must not prevent the records from being read
+        let index_path = p.with_extension("cdx");
+        if reader.header.table_flags.has_structural_cdx() && index_path.is_file() {
+            match File::open(index_path)
+                .map(BufReader::new)
+                .and_then(|mut index_file| CompoundIndex::read_from(&mut index_file))
+            {
+                Ok(index) => reader.index = Some(index),
+                Err(error) => reader.index_error = Some(Arc::new(error)),
+            }
+        }
         Ok(reader)
     }
 }
 
+/// Returns the index of the first field whose name (case-insensitive)
+/// is used by a previous field
+pub(crate) fn find_duplicate_field_name(fields_info: &[FieldInfo]) -> Option<usize> {
+    let mut names = HashSet::<String>::new();
+    fields_info
+        .iter()
+        .position(|info| !names.insert(info.name.to_uppercase()))
+}
+
+/// Renames the fields whose name (case-insensitive) is already used by another
+/// field by adding a numeric suffix, returns the (original, new) names.
+fn rename_duplicate_field_names(fields_info: &mut [FieldInfo]) -> Vec<(String, String)> {
+    let mut renamed_fields = vec![];
+    while let Some(i) = find_duplicate_field_name(fields_info) {
+        let original_name = fields_info[i].name.clone();
+        let new_name = (1..)
+            .map(|n| {
+                let suffix = format!("_{}", n);
+                let mut base = original_name.clone();
+                while base.len() + suffix.len() > FieldInfo::MAX_NAME_LENGTH {
+                    base.pop();
+                }
+                base + &suffix
+            })
+            .find(|candidate| {
+                fields_info
+                    .iter()
+                    .all(|info| !info.name.eq_ignore_ascii_case(candidate))
+            })
+            .unwrap();
+        fields_info[i].name = new_name.clone();
+        renamed_fields.push((original_name, new_name));
+    }
+    renamed_fields
+}
+
 /// Simple struct to wrap together the value with the name
 /// of the field it belongs to
 pub struct NamedValue<'a, T> {
@@ -367,9 +2425,20 @@ pub struct FieldIterator<'a, T: Read + Seek> {
     pub(crate) fields_info: std::iter::Peekable<std::slice::Iter<'a, FieldInfo>>,
     /// The source where the Memo field data is read
     pub(crate) memo_reader: &'a mut Option<MemoReader<T>>,
+    /// The options of the reader
+    pub(crate) options: &'a ReadingOptions,
+    /// The content of the _NullFlags field of the record, empty if there is none
+    pub(crate) null_flags: Vec<u8>,
+    /// Whether the record is marked as deleted
+    pub(crate) deleted: bool,
 }
 
 impl<'a, T: Read + Seek> FieldIterator<'a, T> {
+    /// Returns whether the record being read is marked as deleted
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+
     /// Reads the next field and returns its name and value
     ///
     /// If the "DeletionFlag" field is present in the file it won't be returned
@@ -378,8 +2447,8 @@ impl<'a, T: Read + Seek> FieldIterator<'a, T> {
         let field_info = self
             .fields_info
             .next()
-            .ok_or_else(|| FieldIOError::end_of_record())?;
-        if field_info.is_deletion_flag() {
+            .ok_or_else(FieldIOError::end_of_record)?;
+        if field_info.is_hidden() {
             if let Err(e) = self.skip_field(field_info) {
                 Err(FieldIOError {
                     field: Some(field_info.clone()),
@@ -389,10 +2458,32 @@ impl<'a, T: Read + Seek> FieldIterator<'a, T> {
                 self.read_next_field_impl()
             }
         } else {
+            // Types that cannot represent a null are read as is
+            if self.is_null(field_info) {
+                if let Some(null_value) = FieldValue::null_value(field_info.field_type) {
+                    self.skip_field(field_info).map_err(|error| {
+                        FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
+                    })?;
+                    return Ok((field_info, null_value));
+                }
+            }
             Ok((field_info, self.read_field(field_info)?))
         }
     }
 
+    /// Whether the _NullFlags field of the record says the field is null
+    fn is_null(&self, field_info: &FieldInfo) -> bool {
+        self.is_null_flags_bit_set(field_info.null_bit)
+    }
+
+    fn is_null_flags_bit_set(&self, bit: Option<usize>) -> bool {
+        bit.is_some_and(|bit| {
+            self.null_flags
+                .get(bit / 8)
+                .is_some_and(|byte| byte & (1 << (bit % 8)) != 0)
+        })
+    }
+
     /// Reads the next field and returns its name and value
     ///
     /// If the "DeletionFlag" field is present in the file it won't be returned
@@ -460,7 +2551,7 @@ impl<'a, T: Read + Seek> FieldIterator<'a, T> {
             .fields_info
             .next()
             .ok_or(FieldIOError::end_of_record())?;
-        if field_info.is_deletion_flag() {
+        if field_info.is_hidden() {
             self.skip_field(field_info).map_err(|error| {
                 FieldIOError::new(ErrorKind::IoError(error), Some(field_info.to_owned()))
             })?;
@@ -480,7 +2571,7 @@ impl<'a, T: Read + Seek> FieldIterator<'a, T> {
             field: None,
             kind: ErrorKind::EndOfRecord,
         })?;
-        if field_info.is_deletion_flag() {
+        if field_info.is_hidden() {
             self.skip_field(field_info).map_err(|error| {
                 FieldIOError::new(ErrorKind::IoError(error), Some(field_info.to_owned()))
             })?;
@@ -512,7 +2603,13 @@ impl<'a, T: Read + Seek> FieldIterator<'a, T> {
 
     /// read the next field using the given info
     fn read_field(&mut self, field_info: &'a FieldInfo) -> Result<FieldValue, FieldIOError> {
-        match FieldValue::read_from(self.source, self.memo_reader, field_info) {
+        let result = if field_info.varlength_bit.is_some() {
+            let is_shorter = self.is_null_flags_bit_set(field_info.varlength_bit);
+            FieldValue::read_variable_length(self.source, field_info, is_shorter, self.options)
+        } else {
+            FieldValue::read_from(self.source, self.memo_reader, field_info, self.options)
+        };
+        match result {
             Ok(value) => Ok(value),
             Err(kind) => Err(FieldIOError {
                 field: Some(field_info.clone()),
@@ -539,35 +2636,186 @@ impl<'a, T: Read + Seek> Iterator for FieldIterator<'a, T> {
 impl<'a, T: Read + Seek> FusedIterator for FieldIterator<'a, T> {}
 
 /// Iterator over records contained in the dBase
+///
+/// The records are read one at a time from the source, as the iteration goes.
+/// It stops after the number of records given by the header,
+/// or earlier at the end of file marker (0x1A).
 pub struct RecordIterator<'a, T: Read + Seek, R: ReadableRecord> {
     reader: &'a mut Reader<T>,
     record_type: std::marker::PhantomData<R>,
     current_record: u32,
+    deleted_records: DeletedRecords,
+}
+
+/// Which records a [RecordIterator] yields, depending on their deletion flag
+#[derive(Copy, Clone)]
+pub(crate) enum DeletedRecords {
+    Include,
+    Skip,
+    Only,
+}
+
+impl DeletedRecords {
+    /// Whether a record with this deletion state is skipped
+    pub(crate) fn skips(self, is_deleted: bool) -> bool {
+        match self {
+            DeletedRecords::Include => false,
+            DeletedRecords::Skip => is_deleted,
+            DeletedRecords::Only => !is_deleted,
+        }
+    }
+}
+
+impl<'a, T: Read + Seek, R: ReadableRecord> RecordIterator<'a, T, R> {
+    /// Returns the number of the record the iterator read last,
+    /// records skipped because of their deletion flag included
+    fn last_record_num(&self) -> usize {
+        self.current_record.saturating_sub(1) as usize
+    }
 }
 
 impl<'a, T: Read + Seek, R: ReadableRecord> Iterator for RecordIterator<'a, T, R> {
     type Item = Result<R, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current_record >= self.reader.header.num_records {
-            None
-        } else {
-            let mut iter = FieldIterator {
-                source: &mut self.reader.source,
-                fields_info: self.reader.fields_info.iter().peekable(),
-                memo_reader: &mut self.reader.memo_reader,
+        while self.current_record < self.reader.header.num_records {
+            // Files with a wrong record count can have the end of file marker
+            // where a record was expected
+            let flag = match self.reader.source.read_u8() {
+                Ok(FILE_TERMINATOR) => {
+                    self.current_record = self.reader.header.num_records;
+                    return None;
+                }
+                Ok(flag) => flag,
+                Err(error) => {
+                    return Some(Err(Error::io_error(error, self.current_record as usize)))
+                }
             };
+            let is_deleted = self.reader.options.deletion_flag.is_deleted(flag);
+            if self.deleted_records.skips(is_deleted) {
+                let skipped = self.reader.source.seek(SeekFrom::Current(
+                    i64::from(self.reader.header.size_of_record) - 1,
+                ));
+                self.current_record += 1;
+                if let Err(error) = skipped {
+                    return Some(Err(Error::io_error(error, self.current_record as usize)));
+                }
+                continue;
+            }
+            let record = self
+                .reader
+                .read_current_record_after_flag_as(self.current_record, flag);
+            self.current_record += 1;
+            return Some(record);
+        }
+        None
+    }
+}
 
-            let record = R::read_using(&mut iter)
-                .and_then(|record| iter.skip_remaining_fields().and(Ok(record)))
-                .map_err(|error| Error::new(error, self.current_record as usize));
+/// Iterator over the records contained in the dBase,
+/// each one yielded with the fields of the file
+pub struct RecordWithSchemaIterator<'a, T: Read + Seek> {
+    records: RecordIterator<'a, T, Record>,
+    schema: Arc<[FieldInfo]>,
+}
 
-            self.current_record += 1;
-            Some(record)
+impl<'a, T: Read + Seek> Iterator for RecordWithSchemaIterator<'a, T> {
+    type Item = Result<(Arc<[FieldInfo]>, Record), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.records
+            .next()
+            .map(|record| record.map(|record| (Arc::clone(&self.schema), record)))
+    }
+}
+
+/// Iterator over the records contained in the dBase, in the order
+/// of a tag of the structural index
+pub struct IndexOrderIterator<'a, T: Read + Seek, R: ReadableRecord> {
+    reader: &'a mut Reader<T>,
+    record_type: std::marker::PhantomData<R>,
+    /// The record numbers (starting at 1) left to read
+    record_numbers: std::vec::IntoIter<u32>,
+    /// Position of the source when the iterator was created, restored when dropped
+    start_pos: u64,
+    deleted_records: DeletedRecords,
+}
+
+impl<'a, T: Read + Seek, R: ReadableRecord> Iterator for IndexOrderIterator<'a, T, R> {
+    type Item = Result<R, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let record_number = self.record_numbers.next()?;
+            if record_number == 0 || record_number > self.reader.header.num_records {
+                return Some(Err(Error {
+                    record_num: record_number as usize,
+                    field: None,
+                    kind: ErrorKind::Message(
+                        "The index references a record that does not exist".to_owned(),
+                    ),
+                }));
+            }
+            let record_num = record_number - 1;
+            let record_pos = u64::from(self.reader.header.offset_to_first_record)
+                + u64::from(record_num) * u64::from(self.reader.header.size_of_record);
+            let flag = match self.reader.read_flag_at(record_pos) {
+                Ok(flag) => flag,
+                Err(error) => return Some(Err(Error::io_error(error, record_num as usize))),
+            };
+            let is_deleted = self.reader.options.deletion_flag.is_deleted(flag);
+            if self.deleted_records.skips(is_deleted) {
+                continue;
+            }
+            return Some(
+                self.reader
+                    .read_current_record_after_flag_as(record_num, flag),
+            );
         }
     }
 }
 
+impl<'a, T: Read + Seek, R: ReadableRecord> Drop for IndexOrderIterator<'a, T, R> {
+    fn drop(&mut self) {
+        let _ = self.reader.source.seek(SeekFrom::Start(self.start_pos));
+    }
+}
+
+/// Reads the value of one field, located at `offset` bytes from the start of `src`,
+/// using the default [ReadingOptions](struct.ReadingOptions.html).
+///
+/// # Example
+///
+/// ```
+/// use std::fs::File;
+/// # fn main() -> Result<(), dbase::Error> {
+/// let reader = dbase::Reader::from_path("tests/data/stations.dbf")?;
+/// // The first field of the first record, after its deletion flag
+/// let offset = u64::from(reader.header().offset_to_first_record) + 1;
+/// let info = &reader.fields()[1];
+///
+/// let mut file = File::open("tests/data/stations.dbf").unwrap();
+/// let value = dbase::read_field_at(&mut file, offset, info, &mut None)?;
+/// assert_eq!(value, dbase::FieldValue::Character(Some("Van Dorn Street".to_string())));
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_field_at<R: Read + Seek>(
+    src: &mut R,
+    offset: u64,
+    info: &FieldInfo,
+    memo: &mut Option<MemoReader<R>>,
+) -> Result<FieldValue, Error> {
+    let to_error = |kind| Error {
+        record_num: 0,
+        field: Some(info.clone()),
+        kind,
+    };
+    src.seek(SeekFrom::Start(offset))
+        .map_err(|error| to_error(ErrorKind::IoError(error)))?;
+    FieldValue::read_from(src, memo, info, &ReadingOptions::default()).map_err(to_error)
+}
+
 /// One liner to read the content of a .dbf file
 ///
 /// # Example
@@ -584,7 +2832,7 @@ pub fn read<P: AsRef<Path>>(path: P) -> Result<Vec<Record>, Error> {
 #[cfg(test)]
 mod test {
     use std::fs::File;
-    use std::io::{Seek, SeekFrom};
+    use std::io::Seek;
 
     use super::*;
 
@@ -592,7 +2840,7 @@ mod test {
     fn pos_after_reading() {
         let file = File::open("tests/data/line.dbf").unwrap();
         let mut reader = Reader::new(file).unwrap();
-        let pos_after_reading = reader.source.seek(SeekFrom::Current(0)).unwrap();
+        let pos_after_reading = reader.source.stream_position().unwrap();
 
         // Do not count the the "DeletionFlag record info that is added
         let mut expected_pos = Header::SIZE + ((reader.fields_info.len() - 1) * FieldInfo::SIZE);
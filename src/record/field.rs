@@ -1,21 +1,26 @@
-use std::convert::{TryFrom, TryInto};
+use std::convert::TryFrom;
 use std::fmt;
-use std::io::{Read, Seek, SeekFrom, Write};
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
 use std::str::FromStr;
 
-use byteorder::{BigEndian, LittleEndian, ReadBytesExt, WriteBytesExt};
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use chrono::Datelike;
-
-use crate::error::ErrorKind;
+use crate::encoding::LanguageDriver;
+use crate::error::{Error, ErrorKind};
+use crate::reading::{ReadingOptions, TrimMode};
 use crate::record::FieldInfo;
 use crate::writing::WritableAsDbaseField;
 
 /// The different types of Memo file structure there seem to exist
 #[derive(Debug, PartialEq, Copy, Clone)]
-pub(crate) enum MemoFileType {
+pub enum MemoFileType {
+    /// dBase III memo file (.dbt)
     DbaseMemo,
+    /// dBase IV memo file (.dbt)
     DbaseMemo4,
+    /// FoxBase / FoxPro memo file (.fpt)
     FoxBaseMemo,
 }
 
@@ -23,7 +28,6 @@ pub(crate) enum MemoFileType {
 /// header organisation, we use the same struct internally
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct MemoHeader {
-    next_available_block_index: u32,
     block_size: u32,
 }
 
@@ -32,7 +36,8 @@ impl MemoHeader {
         src: &mut R,
         memo_type: MemoFileType,
     ) -> std::io::Result<Self> {
-        let next_available_block_index = src.read_u32::<LittleEndian>()?;
+        // The index of the next available block is only useful to write
+        let _next_available_block_index = src.read_u32::<LittleEndian>()?;
         let block_size = match memo_type {
             MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => {
                 match src.read_u16::<LittleEndian>()? {
@@ -41,29 +46,57 @@ impl MemoHeader {
                 }
             }
             MemoFileType::FoxBaseMemo => {
-                let _ = src.read_u16::<BigEndian>();
-                u32::from(src.read_u16::<BigEndian>()?)
+                // The block size is the big endian u16 at offset 6, after 2 unused bytes
+                let _unused = src.read_u16::<BigEndian>()?;
+                match src.read_u16::<BigEndian>()? {
+                    // 64 is the default block size of FoxPro
+                    0 => 64,
+                    v => u32::from(v),
+                }
             }
         };
 
-        Ok(Self {
-            next_available_block_index,
-            block_size,
-        })
+        Ok(Self { block_size })
     }
 }
 
 /// Struct that reads knows how to read data from a memo source
+///
+/// It is usually created by the [Reader](struct.Reader.html), but it can also be
+/// created directly to read a memo file without its .dbf file.
+///
+/// # Example
+///
+/// ```
+/// use dbase::{MemoFileType, MemoReader};
+/// # fn main() -> std::io::Result<()> {
+/// let mut memo_reader =
+///     MemoReader::from_path("tests/data/shared_memo.fpt", MemoFileType::FoxBaseMemo)?;
+/// let data = memo_reader.read_data_at(8)?;
+/// assert_eq!(data, b"Shared notes");
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug, Clone)]
-pub(crate) struct MemoReader<T: Read + Seek> {
+pub struct MemoReader<T: Read + Seek> {
     memo_file_type: MemoFileType,
     header: MemoHeader,
     source: T,
     internal_buffer: Vec<u8>,
+    /// Offset up to which the memo file must extend to hold the data being read
+    needed_offset: u64,
+    /// Length of the memo file, known once a block length had to be checked
+    file_len: Option<u64>,
+    /// Maximum length of the data of a block
+    pub(crate) max_data_length: Option<u32>,
+    /// Length of the data of the last block read, as given by its header
+    data_length: u32,
 }
 
 impl<T: Read + Seek> MemoReader<T> {
-    pub(crate) fn new(memo_type: MemoFileType, mut src: T) -> std::io::Result<Self> {
+    /// Creates a new memo reader, reading the header of the memo file
+    /// which is expected to have the given type
+    pub fn new(memo_type: MemoFileType, mut src: T) -> std::io::Result<Self> {
         let header = MemoHeader::read_from(&mut src, memo_type)?;
         let internal_buffer = vec![0u8; header.block_size as usize];
         Ok(Self {
@@ -71,22 +104,39 @@ impl<T: Read + Seek> MemoReader<T> {
             header,
             source: src,
             internal_buffer,
+            needed_offset: 0,
+            file_len: None,
+            max_data_length: None,
+            data_length: 0,
         })
     }
 
-    fn read_data_at(&mut self, index: u32) -> std::io::Result<&[u8]> {
-        let byte_offset = index * self.header.block_size;
-        self.source.seek(SeekFrom::Start(u64::from(byte_offset)))?;
+    /// Sets the maximum length of the data of a block,
+    /// reading a longer one returns an error instead of allocating memory for it.
+    ///
+    /// By default, only the length of the memo file limits the length of the data.
+    pub fn max_data_length(mut self, value: u32) -> Self {
+        self.max_data_length = Some(value);
+        self
+    }
+
+    /// Returns the size in bytes of the blocks of the memo file
+    pub fn block_size(&self) -> u32 {
+        self.header.block_size
+    }
+
+    /// Returns the format of the memo file
+    pub(crate) fn memo_file_type(&self) -> MemoFileType {
+        self.memo_file_type
+    }
+
+    /// Reads the data stored at the block `index`
+    pub fn read_data_at(&mut self, index: u32) -> std::io::Result<&[u8]> {
+        let byte_offset = self.seek_to_block(index)?;
 
         match self.memo_file_type {
             MemoFileType::FoxBaseMemo => {
-                let _type = self.source.read_u32::<BigEndian>()?;
-                let length = self.source.read_u32::<BigEndian>()?;
-                if length as usize > self.internal_buffer.len() {
-                    self.internal_buffer.resize(length as usize, 0);
-                }
-                let buf_slice = &mut self.internal_buffer[..length as usize];
-                self.source.read_exact(buf_slice)?;
+                let (_, buf_slice) = self.read_fox_base_data()?;
                 match buf_slice.iter().rposition(|b| *b != 0) {
                     Some(pos) => Ok(&buf_slice[..=pos]),
                     None => {
@@ -99,8 +149,14 @@ impl<T: Read + Seek> MemoReader<T> {
                 }
             }
             MemoFileType::DbaseMemo4 => {
+                self.needed_offset = byte_offset + 8;
                 let _ = self.source.read_u32::<LittleEndian>()?;
                 let length = self.source.read_u32::<LittleEndian>()?;
+                self.needed_offset += u64::from(length);
+                self.check_data_length(length)?;
+                if length as usize > self.internal_buffer.len() {
+                    self.internal_buffer.resize(length as usize, 0);
+                }
                 self.source
                     .read_exact(&mut self.internal_buffer[..length as usize])?;
                 match self.internal_buffer[..length as usize]
@@ -108,28 +164,295 @@ impl<T: Read + Seek> MemoReader<T> {
                     .position(|b| *b == 0x1F)
                 {
                     Some(pos) => Ok(&self.internal_buffer[..pos]),
-                    None => Ok(&self.internal_buffer),
+                    None => Ok(&self.internal_buffer[..length as usize]),
                 }
             }
             MemoFileType::DbaseMemo => {
-                if let Err(e) = self.source.read_exact(&mut self.internal_buffer) {
-                    if index != self.header.next_available_block_index - 1
-                        && e.kind() != std::io::ErrorKind::UnexpectedEof
+                // The data ends with 0x1A and can span many blocks
+                self.internal_buffer.clear();
+                self.needed_offset = byte_offset + 1;
+                loop {
+                    let start = self.internal_buffer.len();
+                    let num_read = (&mut self.source)
+                        .take(u64::from(self.header.block_size))
+                        .read_to_end(&mut self.internal_buffer)?;
+                    if self.internal_buffer.is_empty() {
+                        return Err(std::io::ErrorKind::UnexpectedEof.into());
+                    }
+                    if let Some(pos) = self.internal_buffer[start..]
+                        .iter()
+                        .position(|b| *b == 0x1A)
                     {
-                        return Err(e);
+                        self.internal_buffer.truncate(start + pos);
+                        break;
+                    }
+                    if num_read < self.header.block_size as usize {
+                        break;
                     }
                 }
-                match self.internal_buffer.iter().position(|b| *b == 0x1A) {
-                    Some(pos) => Ok(&self.internal_buffer[..pos]),
-                    None => Ok(&self.internal_buffer),
-                }
+                Ok(&self.internal_buffer)
             }
         }
     }
 }
 
+impl<T: Read + Seek> MemoReader<T> {
+    /// Moves the source to the start of the block `index`, returns its offset
+    fn seek_to_block(&mut self, index: u32) -> std::io::Result<u64> {
+        let byte_offset = u64::from(index) * u64::from(self.header.block_size);
+        self.source.seek(SeekFrom::Start(byte_offset))?;
+        self.needed_offset = byte_offset;
+        Ok(byte_offset)
+    }
+
+    /// Reads the type and the data of the FoxPro block at the current position
+    fn read_fox_base_data(&mut self) -> std::io::Result<(u32, &mut [u8])> {
+        self.needed_offset += 8;
+        let block_type = self.source.read_u32::<BigEndian>()?;
+        let length = self.source.read_u32::<BigEndian>()?;
+        self.needed_offset += u64::from(length);
+        self.check_data_length(length)?;
+        if length as usize > self.internal_buffer.len() {
+            self.internal_buffer.resize(length as usize, 0);
+        }
+        let buf_slice = &mut self.internal_buffer[..length as usize];
+        self.source.read_exact(buf_slice)?;
+        Ok((block_type, buf_slice))
+    }
+
+    /// Checks the length of the data of a block, given by its header,
+    /// before memory is allocated for it: a corrupted length could be huge.
+    ///
+    /// `needed_offset` must already include the length.
+    fn check_data_length(&mut self, length: u32) -> std::io::Result<()> {
+        self.data_length = length;
+        if self.max_data_length.is_some_and(|max| length > max) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "The memo data is longer than the maximum length",
+            ));
+        }
+        let file_len = match self.file_len {
+            Some(file_len) => file_len,
+            None => {
+                let position = self.source.stream_position()?;
+                let file_len = self.source.seek(SeekFrom::End(0))?;
+                self.source.seek(SeekFrom::Start(position))?;
+                self.file_len = Some(file_len);
+                file_len
+            }
+        };
+        if self.needed_offset > file_len {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+
+    /// Reads the type and the data stored at the block `index` as is,
+    /// unlike [read_data_at](#method.read_data_at) trailing zeros of FoxPro memos are kept.
+    ///
+    /// Only FoxPro blocks have a type, the data of the other memo files is text.
+    pub(crate) fn read_raw_data_at(&mut self, index: u32) -> std::io::Result<(u32, &[u8])> {
+        match self.memo_file_type {
+            MemoFileType::FoxBaseMemo => {
+                self.seek_to_block(index)?;
+                self.read_fox_base_data()
+                    .map(|(block_type, data)| (block_type, &*data))
+            }
+            _ => self
+                .read_data_at(index)
+                .map(|data| (FOX_BASE_MEMO_TEXT_TYPE, data)),
+        }
+    }
+
+    /// Converts the error returned when reading the block `index`,
+    /// an unexpected end of file means that the memo file is truncated
+    pub(crate) fn read_error(&mut self, index: u32, error: std::io::Error) -> ErrorKind {
+        if let Some(max) = self.max_data_length {
+            if error.kind() == std::io::ErrorKind::InvalidData && self.data_length > max {
+                return ErrorKind::MemoTooLong {
+                    index,
+                    length: self.data_length,
+                    max,
+                };
+            }
+        }
+        if error.kind() != std::io::ErrorKind::UnexpectedEof {
+            return ErrorKind::IoError(error);
+        }
+        match self.source.seek(SeekFrom::End(0)) {
+            Ok(file_len) => ErrorKind::TruncatedMemoFile {
+                index,
+                needed_offset: self.needed_offset,
+                file_len,
+            },
+            Err(_) => ErrorKind::IoError(error),
+        }
+    }
+}
+
+impl MemoReader<BufReader<File>> {
+    /// Creates a new memo reader from the path of a memo file (.dbt, .fpt)
+    pub fn from_path<P: AsRef<Path>>(path: P, memo_type: MemoFileType) -> std::io::Result<Self> {
+        Self::new(memo_type, BufReader::new(File::open(path)?))
+    }
+}
+
+/// Size of the header of the memo files written
+const MEMO_HEADER_SIZE: u32 = 512;
+/// Size of the blocks of the dBase III memo files written
+const DBASE_MEMO_BLOCK_SIZE: u32 = 512;
+/// Size of the blocks of the FoxPro memo files written (the default one of FoxPro)
+const FOX_BASE_MEMO_BLOCK_SIZE: u32 = 64;
+/// Marks the end of the data of a dBase III memo
+const MEMO_TERMINATOR: [u8; 2] = [0x1A, 0x1A];
+/// Type of the data of a FoxPro memo block that holds text
+pub(crate) const FOX_BASE_MEMO_TEXT_TYPE: u32 = 1;
+/// Type of the data of a FoxPro memo block that holds a picture or an OLE object
+pub(crate) const FOX_BASE_MEMO_PICTURE_TYPE: u32 = 0;
+
+/// Struct that knows how to write data to a memo destination
+///
+/// dBase III memo files (.dbt) and FoxPro memo files (.fpt) can be written:
+///
+/// - In dBase III memo files, the data is stored in blocks of 512 bytes,
+///   the data of a memo ends with `0x1A 0x1A` and is padded to the end of its last block.
+/// - In FoxPro memo files, the data is stored in blocks of 64 bytes,
+///   the data of a memo is preceded by its type and length and is padded to the end of its last block.
+///
+/// It is given to the [TableWriterBuilder](struct.TableWriterBuilder.html) to write tables
+/// with Memo fields.
+///
+/// # Example
+///
+/// ```
+/// use dbase::{MemoFileType, MemoWriter};
+/// use std::io::Cursor;
+/// # fn main() -> std::io::Result<()> {
+/// let mut memo_writer = MemoWriter::new(MemoFileType::DbaseMemo, Cursor::new(Vec::<u8>::new()))?;
+/// assert_eq!(memo_writer.write_data(b"Some notes")?, 1);
+/// assert_eq!(memo_writer.write_data(b"Other notes")?, 2);
+///
+/// // The header of FoxPro memo files takes 8 blocks
+/// let mut memo_writer =
+///     MemoWriter::new(MemoFileType::FoxBaseMemo, Cursor::new(Vec::<u8>::new()))?;
+/// assert_eq!(memo_writer.write_data(b"Some notes")?, 8);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MemoWriter<W: Write + ?Sized> {
+    memo_type: MemoFileType,
+    next_available_block_index: u32,
+    // Last, so that the writer can be used as a `MemoWriter<dyn Write>`
+    dst: W,
+}
+
+impl<W: Write> MemoWriter<W> {
+    /// Creates a new memo writer of the given type,
+    /// the space of the header is reserved in the destination.
+    ///
+    /// Returns an error if the type is [DbaseMemo4](enum.MemoFileType.html#variant.DbaseMemo4),
+    /// which cannot be written.
+    pub fn new(memo_type: MemoFileType, mut dst: W) -> std::io::Result<Self> {
+        if memo_type == MemoFileType::DbaseMemo4 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Writing {:?} files is not supported", memo_type),
+            ));
+        }
+        dst.write_all(&[0u8; MEMO_HEADER_SIZE as usize])?;
+        let mut writer = Self {
+            memo_type,
+            next_available_block_index: 0,
+            dst,
+        };
+        writer.next_available_block_index = MEMO_HEADER_SIZE / writer.block_size();
+        Ok(writer)
+    }
+}
+
+impl<W: Write + ?Sized> MemoWriter<W> {
+    fn block_size(&self) -> u32 {
+        match self.memo_type {
+            MemoFileType::FoxBaseMemo => FOX_BASE_MEMO_BLOCK_SIZE,
+            _ => DBASE_MEMO_BLOCK_SIZE,
+        }
+    }
+
+    /// Writes the data in the next available blocks,
+    /// and returns the index of the first block used.
+    ///
+    /// In FoxPro memo files the data is marked as text,
+    /// use [write_data_with_type](#method.write_data_with_type) for other data.
+    pub fn write_data(&mut self, data: &[u8]) -> std::io::Result<u32> {
+        self.write_data_with_type(data, FOX_BASE_MEMO_TEXT_TYPE)
+    }
+
+    /// Writes the data in the next available blocks, and returns the index of the first block used.
+    ///
+    /// In FoxPro memo files the `block_type` is written before the data
+    /// (0 for pictures and OLE objects, 1 for text), the other memo files ignore it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{MemoFileType, MemoWriter};
+    /// use std::io::Cursor;
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut memo_writer =
+    ///     MemoWriter::new(MemoFileType::FoxBaseMemo, Cursor::new(Vec::<u8>::new()))?;
+    /// assert_eq!(memo_writer.write_data_with_type(&[0xFF, 0xD8], 0)?, 8);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_data_with_type(&mut self, data: &[u8], block_type: u32) -> std::io::Result<u32> {
+        let index = self.next_available_block_index;
+        let size = if self.memo_type == MemoFileType::FoxBaseMemo {
+            self.dst.write_u32::<BigEndian>(block_type)?;
+            self.dst.write_u32::<BigEndian>(data.len() as u32)?;
+            self.dst.write_all(data)?;
+            8 + data.len()
+        } else {
+            self.dst.write_all(data)?;
+            self.dst.write_all(&MEMO_TERMINATOR)?;
+            data.len() + MEMO_TERMINATOR.len()
+        };
+
+        let block_size = self.block_size();
+        let num_blocks = (size as u32).div_ceil(block_size);
+        let padding = (num_blocks * block_size) as usize - size;
+        self.dst.write_all(&vec![0u8; padding])?;
+
+        self.next_available_block_index += num_blocks;
+        Ok(index)
+    }
+}
+
+impl<W: Write + Seek> MemoWriter<W> {
+    /// Writes the header (which holds the index of the next available block)
+    /// and flushes the destination.
+    pub fn finish(&mut self) -> std::io::Result<()> {
+        let mut header = [0u8; MEMO_HEADER_SIZE as usize];
+        if self.memo_type == MemoFileType::FoxBaseMemo {
+            BigEndian::write_u32(&mut header[..4], self.next_available_block_index);
+            BigEndian::write_u16(&mut header[6..8], self.block_size() as u16);
+        } else {
+            LittleEndian::write_u32(&mut header[..4], self.next_available_block_index);
+            // dBase III version
+            header[16] = 0x03;
+        }
+
+        let end = self.dst.stream_position()?;
+        self.dst.seek(SeekFrom::Start(0))?;
+        self.dst.write_all(&header)?;
+        self.dst.seek(SeekFrom::Start(end))?;
+        self.dst.flush()
+    }
+}
+
 /// Enum listing all the field types we know of
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum FieldType {
     // dBASE III
     Character,
@@ -144,7 +467,17 @@ pub enum FieldType {
     // Unknown
     Double,
     Memo,
-    //General,
+    /// Visual FoxPro system field (named `_NullFlags`),
+    /// its bits tell which nullable fields of the record are null
+    NullFlags,
+    /// Visual FoxPro OLE object, stored in the memo file
+    General,
+    /// FoxPro picture, its raw image bytes are stored in the memo file
+    Picture,
+    /// Visual FoxPro variable length string
+    Varchar,
+    /// Visual FoxPro variable length binary data
+    Varbinary,
     //BinaryCharacter,
     //BinaryMemo,
 }
@@ -162,6 +495,11 @@ impl From<FieldType> for u8 {
             FieldType::Integer => 'I',
             FieldType::Double => 'B',
             FieldType::Memo => 'M',
+            FieldType::NullFlags => '0',
+            FieldType::General => 'G',
+            FieldType::Picture => 'P',
+            FieldType::Varchar => 'V',
+            FieldType::Varbinary => 'Q',
         };
         v as u8
     }
@@ -185,7 +523,11 @@ impl FieldType {
             // unknown version
             'B' => Some(FieldType::Double),
             'M' => Some(FieldType::Memo),
-            //'G' => Some(FieldType::General),
+            '0' => Some(FieldType::NullFlags),
+            'G' => Some(FieldType::General),
+            'P' => Some(FieldType::Picture),
+            'V' => Some(FieldType::Varchar),
+            'Q' => Some(FieldType::Varbinary),
             //'C' => Some(FieldType::BinaryCharacter), ??
             //'M' => Some(FieldType::BinaryMemo),
             _ => None,
@@ -209,6 +551,14 @@ impl FieldType {
             _ => None,
         }
     }
+
+    /// Whether the values of fields of this type are stored in the memo file
+    pub(crate) fn uses_memo_file(self) -> bool {
+        matches!(
+            self,
+            FieldType::Memo | FieldType::General | FieldType::Picture
+        )
+    }
 }
 
 impl TryFrom<char> for FieldType {
@@ -246,17 +596,35 @@ pub enum FieldValue {
     Date(Option<Date>),
     /// Another dBase type to represent numbers, stored as String in the file
     Float(Option<f32>),
-    //Visual FoxPro fields
-    Integer(i32),
-    Currency(f64),
-    DateTime(DateTime),
-    Double(f64),
+    // Visual FoxPro fields, stored in binary.
+    // They are `None` when the _NullFlags field of the record says they are null
+    /// Visual FoxPro integer, stored as a little endian i32
+    Integer(Option<i32>),
+    /// Visual FoxPro currency, stored as a little endian i64
+    /// holding the amount multiplied by 10 000
+    Currency(Option<f64>),
+    /// Visual FoxPro date and time, stored as two little endian i32
+    DateTime(Option<DateTime>),
+    /// Visual FoxPro double, stored as a little endian f64
+    Double(Option<f64>),
 
     /// Memo is a dBase type that allows to store Strings
     /// that are longer than 255 bytes.
     /// These strings are stored in an external file
     /// called the `Memo file`
     Memo(String),
+    /// Visual FoxPro OLE object, its raw bytes are stored in the `Memo file`
+    General(Vec<u8>),
+    /// FoxPro picture, its raw image bytes are stored in the `Memo file`
+    Picture(Vec<u8>),
+    /// Visual FoxPro variable length string, stored in the record
+    ///
+    /// It is `None` when the _NullFlags field of the record says it is null
+    Varchar(Option<String>),
+    /// Visual FoxPro variable length binary data, stored in the record
+    ///
+    /// It is `None` when the _NullFlags field of the record says it is null
+    Varbinary(Option<Vec<u8>>),
 }
 
 impl FieldValue {
@@ -264,6 +632,7 @@ impl FieldValue {
         mut source: &mut T,
         memo_reader: &mut Option<MemoReader<T>>,
         field_info: &FieldInfo,
+        options: &ReadingOptions,
     ) -> Result<Self, ErrorKind> {
         let value = match field_info.field_type {
             FieldType::Logical => match source.read_u8()? as char {
@@ -273,68 +642,270 @@ impl FieldValue {
                 _ => FieldValue::Logical(None),
             },
             FieldType::Character => {
-                let value = read_string_of_len(&mut source, field_info.field_length)?;
-                let trimmed_value = value.trim();
-                if trimmed_value.is_empty() {
+                let value = if options.sniffs_character_encoding() {
+                    read_sniffed_string_of_len(&mut source, field_info.field_length)?
+                } else {
+                    let bytes = read_bytes_of_len(&mut source, field_info.field_length)?;
+                    options.encoding.decode(&bytes)
+                };
+                let trimmed_value = match options.character_trim {
+                    TrimMode::Both => value.trim(),
+                    TrimMode::End => value.trim_end(),
+                };
+                if trimmed_value.is_empty() && options.empty_character_as_none {
                     FieldValue::Character(None)
                 } else {
                     FieldValue::Character(Some(trimmed_value.to_owned()))
                 }
             }
-            FieldType::Numeric => {
-                let value = read_string_of_len(&mut source, field_info.field_length)?;
-                let trimmed_value = value.trim();
-                if trimmed_value.is_empty() || value.chars().all(|c| c == '*') {
-                    FieldValue::Numeric(None)
-                } else {
-                    FieldValue::Numeric(Some(trimmed_value.parse::<f64>()?))
-                }
-            }
-            FieldType::Float => {
-                let value = read_string_of_len(&mut source, field_info.field_length)?;
-                let trimmed_value = value.trim();
-                if trimmed_value.is_empty() || value.chars().all(|c| c == '*') {
-                    FieldValue::Float(None)
-                } else {
-                    FieldValue::Float(Some(trimmed_value.parse::<f32>()?))
+            FieldType::Numeric => match read_numeric_text(&mut source, field_info, options)? {
+                Some(text) => FieldValue::Numeric(Some(text.parse::<f64>()?)),
+                None => FieldValue::Numeric(None),
+            },
+            FieldType::Float => match read_numeric_text(&mut source, field_info, options)? {
+                Some(text) => FieldValue::Float(Some(text.parse::<f32>()?)),
+                None => FieldValue::Float(None),
+            },
+            // Some dBase IV variants store the date as a binary julian day number
+            FieldType::Date if field_info.field_length == 4 => {
+                match source.read_i32::<LittleEndian>()? {
+                    0 => FieldValue::Date(None),
+                    jdn => FieldValue::Date(Some(date_from_julian_day_number(
+                        jdn,
+                        options.julian_day_offset,
+                    )?)),
                 }
             }
             FieldType::Date => {
                 let value = read_string_of_len(&mut source, field_info.field_length)?;
-                if value.chars().all(|c| c == ' ') {
+                // Some writers use zeros instead of spaces for empty dates
+                if value.chars().all(|c| c == ' ') || value.chars().all(|c| c == '0') {
                     FieldValue::Date(None)
                 } else {
                     FieldValue::Date(Some(value.parse::<Date>()?))
                 }
             }
-            FieldType::Integer => FieldValue::Integer(source.read_i32::<LittleEndian>()?),
-            FieldType::Double => FieldValue::Double(source.read_f64::<LittleEndian>()?),
-            FieldType::Currency => FieldValue::Currency(source.read_f64::<LittleEndian>()?),
-            FieldType::DateTime => FieldValue::DateTime(DateTime::read_from(&mut source)?),
+            FieldType::Integer | FieldType::Double | FieldType::Currency | FieldType::DateTime => {
+                if options.big_endian_binary_fields {
+                    read_binary_value::<BigEndian, T>(source, field_info.field_type, options)?
+                } else {
+                    read_binary_value::<LittleEndian, T>(source, field_info.field_type, options)?
+                }
+            }
+            FieldType::NullFlags => {
+                return Err(ErrorKind::Message(
+                    "_NullFlags fields do not have a value".to_owned(),
+                ))
+            }
             FieldType::Memo => {
-                let index_in_memo = if field_info.field_length > 4 {
-                    let string = read_string_of_len(&mut source, field_info.field_length)?;
-                    let trimmed_str = string.trim();
-                    if trimmed_str.is_empty() {
-                        return Ok(FieldValue::Memo(String::from("")));
-                    } else {
-                        trimmed_str.parse::<u32>()?
+                let index_in_memo = match read_memo_index(source, field_info)? {
+                    Some(index) => index,
+                    None => return Ok(FieldValue::Memo(String::from(""))),
+                };
+
+                if let Some(memo_reader) = memo_reader {
+                    match memo_reader.read_data_at(index_in_memo) {
+                        Ok(data_from_memo) => {
+                            FieldValue::Memo(options.encoding.decode(data_from_memo))
+                        }
+                        Err(error) => return Err(memo_reader.read_error(index_in_memo, error)),
                     }
                 } else {
-                    source.read_u32::<LittleEndian>()?
+                    return Err(ErrorKind::MissingMemoFile);
+                }
+            }
+            FieldType::General | FieldType::Picture => {
+                let blob = if field_info.field_type == FieldType::General {
+                    FieldValue::General
+                } else {
+                    FieldValue::Picture
+                };
+                let index_in_memo = match read_memo_index(source, field_info)? {
+                    Some(index) => index,
+                    None => return Ok(blob(vec![])),
                 };
 
                 if let Some(memo_reader) = memo_reader {
-                    let data_from_memo = memo_reader.read_data_at(index_in_memo)?;
-                    FieldValue::Memo(String::from_utf8_lossy(data_from_memo).to_string())
+                    match memo_reader.read_raw_data_at(index_in_memo) {
+                        Ok((_, data)) => blob(data.to_vec()),
+                        Err(error) => return Err(memo_reader.read_error(index_in_memo, error)),
+                    }
                 } else {
                     return Err(ErrorKind::MissingMemoFile);
                 }
             }
+            FieldType::Varchar | FieldType::Varbinary => {
+                return FieldValue::read_variable_length(source, field_info, false, options)
+            }
         };
         Ok(value)
     }
 
+    /// Reads the value of a Varchar or Varbinary field
+    ///
+    /// When the value is shorter than the field (which the _NullFlags field
+    /// of the record tells), its real length is stored in the last byte of the field.
+    pub(crate) fn read_variable_length<T: Read>(
+        source: &mut T,
+        field_info: &FieldInfo,
+        is_shorter: bool,
+        options: &ReadingOptions,
+    ) -> Result<Self, ErrorKind> {
+        let mut bytes = vec![0u8; usize::from(field_info.field_length)];
+        source.read_exact(&mut bytes)?;
+        if is_shorter {
+            let length = bytes.last().map_or(0, |&length| usize::from(length));
+            bytes.truncate(length.min(bytes.len().saturating_sub(1)));
+        }
+        if field_info.field_type == FieldType::Varchar {
+            Ok(FieldValue::Varchar(Some(options.encoding.decode(&bytes))))
+        } else {
+            Ok(FieldValue::Varbinary(Some(bytes)))
+        }
+    }
+
+    /// Returns the value used for a null field of the given type,
+    /// `None` if the type cannot represent the absence of value
+    pub(crate) fn null_value(field_type: FieldType) -> Option<FieldValue> {
+        match field_type {
+            FieldType::Character => Some(FieldValue::Character(None)),
+            FieldType::Numeric => Some(FieldValue::Numeric(None)),
+            FieldType::Float => Some(FieldValue::Float(None)),
+            FieldType::Logical => Some(FieldValue::Logical(None)),
+            FieldType::Date => Some(FieldValue::Date(None)),
+            FieldType::Integer => Some(FieldValue::Integer(None)),
+            FieldType::Currency => Some(FieldValue::Currency(None)),
+            FieldType::DateTime => Some(FieldValue::DateTime(None)),
+            FieldType::Double => Some(FieldValue::Double(None)),
+            FieldType::Varchar => Some(FieldValue::Varchar(None)),
+            FieldType::Varbinary => Some(FieldValue::Varbinary(None)),
+            _ => None,
+        }
+    }
+
+    /// Creates an Integer value, returning an error with the
+    /// [IntegerOutOfRange](enum.ErrorKind.html#variant.IntegerOutOfRange) kind
+    /// instead of wrapping if the value does not fit in an i32
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::FieldValue;
+    ///
+    /// let id: usize = 42;
+    /// let value = FieldValue::integer_checked(id as i64).unwrap();
+    /// assert_eq!(value, FieldValue::Integer(Some(42)));
+    /// assert!(FieldValue::integer_checked(i64::from(i32::MAX) + 1).is_err());
+    /// ```
+    pub fn integer_checked(value: i64) -> Result<FieldValue, Error> {
+        i32::try_from(value)
+            .map(|value| FieldValue::Integer(Some(value)))
+            .map_err(|_| Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::IntegerOutOfRange(value),
+            })
+    }
+
+    /// Returns the text of a Character, Varchar or Memo value,
+    /// `None` for the other variants and for `Character(None)`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::FieldValue;
+    ///
+    /// let value = FieldValue::Character(Some("Ferris".to_owned()));
+    /// assert_eq!(value.as_character(), Some("Ferris"));
+    /// assert_eq!(FieldValue::Character(None).as_character(), None);
+    /// assert_eq!(FieldValue::Integer(Some(1)).as_character(), None);
+    /// ```
+    pub fn as_character(&self) -> Option<&str> {
+        match self {
+            FieldValue::Character(Some(text))
+            | FieldValue::Varchar(Some(text))
+            | FieldValue::Memo(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of a Numeric, Float, Double or Currency value,
+    /// `None` for the other variants and for empty values
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::FieldValue;
+    ///
+    /// assert_eq!(FieldValue::Numeric(Some(1.5)).as_numeric(), Some(1.5));
+    /// assert_eq!(FieldValue::Float(Some(2.0)).as_numeric(), Some(2.0));
+    /// assert_eq!(FieldValue::Numeric(None).as_numeric(), None);
+    /// assert_eq!(FieldValue::Logical(Some(true)).as_numeric(), None);
+    /// ```
+    pub fn as_numeric(&self) -> Option<f64> {
+        match *self {
+            FieldValue::Numeric(number) => number,
+            FieldValue::Float(number) => number.map(f64::from),
+            FieldValue::Double(number) | FieldValue::Currency(number) => number,
+            _ => None,
+        }
+    }
+
+    /// Returns the date of a Date value,
+    /// `None` for the other variants and for `Date(None)`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{Date, FieldValue};
+    ///
+    /// let date = Date::new(20, 7, 2019);
+    /// assert_eq!(FieldValue::Date(Some(date)).as_date(), Some(date));
+    /// assert_eq!(FieldValue::Date(None).as_date(), None);
+    /// ```
+    pub fn as_date(&self) -> Option<Date> {
+        match *self {
+            FieldValue::Date(date) => date,
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean of a Logical value,
+    /// `None` for the other variants and for `Logical(None)`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::FieldValue;
+    ///
+    /// assert_eq!(FieldValue::Logical(Some(false)).as_logical(), Some(false));
+    /// assert_eq!(FieldValue::Integer(Some(0)).as_logical(), None);
+    /// ```
+    pub fn as_logical(&self) -> Option<bool> {
+        match *self {
+            FieldValue::Logical(value) => value,
+            _ => None,
+        }
+    }
+
+    /// Returns the integer of an Integer value,
+    /// `None` for the other variants and for `Integer(None)`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::FieldValue;
+    ///
+    /// assert_eq!(FieldValue::Integer(Some(42)).as_integer(), Some(42));
+    /// assert_eq!(FieldValue::Numeric(Some(42.0)).as_integer(), None);
+    /// ```
+    pub fn as_integer(&self) -> Option<i32> {
+        match *self {
+            FieldValue::Integer(value) => value,
+            _ => None,
+        }
+    }
+
     /// Returns the corresponding field type of the contained value
     pub fn field_type(&self) -> FieldType {
         match self {
@@ -346,8 +917,12 @@ impl FieldValue {
             FieldValue::Double(_) => FieldType::Double,
             FieldValue::Date(_) => FieldType::Date,
             FieldValue::Memo(_) => FieldType::Memo,
+            FieldValue::General(_) => FieldType::General,
+            FieldValue::Picture(_) => FieldType::Picture,
             FieldValue::Currency(_) => FieldType::Currency,
             FieldValue::DateTime(_) => FieldType::DateTime,
+            FieldValue::Varchar(_) => FieldType::Varchar,
+            FieldValue::Varbinary(_) => FieldType::Varbinary,
         }
     }
 }
@@ -366,7 +941,8 @@ impl fmt::Display for FieldValue {
 /// with just a very few checks.
 ///
 /// Also, dBase files do not have concept of timezones.
-#[derive(Debug, Copy, Clone, PartialEq)]
+// The derived ordering is the chronological one, the fields go from the most significant
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Date {
     pub(crate) year: u32,
     pub(crate) month: u32,
@@ -375,6 +951,9 @@ pub struct Date {
 
 impl Date {
     /// Creates a new dbase::Date
+    ///
+    /// Any year from 0 to 9999 is accepted, Date fields store the year with 4 digits.
+    ///
     /// # panic
     ///
     /// panics if the year has more than 4 digits or if the day is greater than 31 or
@@ -407,9 +986,54 @@ impl Date {
         self.day
     }
 
+    /// Returns the date in the ISO 8601 format (`YYYY-MM-DD`),
+    /// unlike `to_string` which returns the format of the files (`YYYYMMDD`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let date = dbase::Date::new(20, 7, 2019);
+    /// assert_eq!(date.to_iso_string(), "2019-07-20");
+    /// assert_eq!(date.to_string(), "20190720");
+    /// ```
+    pub fn to_iso_string(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+
+    /// Julian day number of 0001-01-01
+    const MIN_JULIAN_DAY_NUMBER: i32 = 1_721_426;
+    /// Julian day number of 9999-12-31
+    const MAX_JULIAN_DAY_NUMBER: i32 = 5_373_484;
+
+    /// Creates a Date from a julian day number
+    ///
+    /// Returns an [InvalidDate](enum.ErrorKind.html#variant.InvalidDate) error
+    /// if the date it corresponds to is not between the years 1 and 9999.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let date = dbase::Date::from_julian_day_number(2_458_685)?;
+    /// assert_eq!(date, dbase::Date::new(20, 7, 2019));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_julian_day_number(jdn: i32) -> Result<Date, Error> {
+        if (Self::MIN_JULIAN_DAY_NUMBER..=Self::MAX_JULIAN_DAY_NUMBER).contains(&jdn) {
+            Ok(Self::julian_day_number_to_gregorian_date(jdn))
+        } else {
+            Err(Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::InvalidDate,
+            })
+        }
+    }
+
     // https://en.wikipedia.org/wiki/Julian_day
     // at "Julian or Gregorian calendar from Julian day number"
-    fn julian_day_number_to_gregorian_date(jdn: i32) -> Date {
+    pub(crate) fn julian_day_number_to_gregorian_date(jdn: i32) -> Date {
         const Y: i32 = 4716;
         const J: i32 = 1401;
         const M: i32 = 2;
@@ -439,65 +1063,122 @@ impl Date {
         }
     }
 
-    fn to_julian_day_number(&self) -> i32 {
+    /// Returns the julian day number of the date
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let date = dbase::Date::new(20, 7, 2019);
+    /// assert_eq!(date.to_julian_day_number(), 2_458_685);
+    /// ```
+    pub fn to_julian_day_number(self) -> i32 {
+        // Signed, as the year before January and February of the year 0 is -1
         let (month, year) = if self.month > 2 {
-            (self.month - 3, self.year)
+            (self.month as i32 - 3, self.year as i32)
         } else {
-            (self.month + 9, self.year - 1)
+            (self.month as i32 + 9, self.year as i32 - 1)
         };
 
-        let century = year / 100;
-        let decade = year - 100 * century;
+        let century = year.div_euclid(100);
+        let decade = year.rem_euclid(100);
 
-        ((146_097 * century) / 4
+        (146_097 * century).div_euclid(4)
             + (1461 * decade) / 4
             + (153 * month + 2) / 5
-            + self.day
-            + 1_721_119) as i32
+            + self.day as i32
+            + 1_721_119
+    }
+
+    /// Returns the date `n` days after this one (before it if `n` is negative)
+    ///
+    /// Returns an error if the resulting date is not between the years 1 and 9999,
+    /// the range accepted by [from_julian_day_number](#method.from_julian_day_number).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let date = dbase::Date::new(28, 2, 2020);
+    /// assert_eq!(date.add_days(2)?, dbase::Date::new(1, 3, 2020));
+    /// assert_eq!(date.add_days(-59)?, dbase::Date::new(31, 12, 2019));
+    /// assert!(dbase::Date::new(31, 12, 9999).add_days(1).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_days(&self, n: i32) -> Result<Date, Error> {
+        let jdn = self
+            .to_julian_day_number()
+            .checked_add(n)
+            .ok_or_else(|| Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::Message(format!("Cannot add {} days to {}", n, self)),
+            })?;
+        Self::from_julian_day_number(jdn)
+    }
+
+    /// Returns the number of days from this date to `other`,
+    /// negative if `other` is before this date
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let start = dbase::Date::new(20, 7, 2019);
+    /// let end = dbase::Date::new(1, 1, 2020);
+    /// assert_eq!(start.days_between(&end), 165);
+    /// assert_eq!(end.days_between(&start), -165);
+    /// ```
+    pub fn days_between(&self, other: &Date) -> i32 {
+        other.to_julian_day_number() - self.to_julian_day_number()
     }
 }
 
+/// Parses a date in the format of the files (`YYYYMMDD`)
+///
+/// Returns an error with the [InvalidDate](enum.ErrorKind.html#variant.InvalidDate) kind
+/// if the date does not exist (e.g. 31st of February), or if the string
+/// is not made of exactly 8 ASCII characters.
+///
+/// # Example
+///
+/// ```
+/// use dbase::Date;
+///
+/// assert_eq!("18991231".parse::<Date>().unwrap(), Date::new(31, 12, 1899));
+/// assert!("20190230".parse::<Date>().is_err());
+/// assert!("201".parse::<Date>().is_err());
+/// ```
 impl FromStr for Date {
-    type Err = std::num::ParseIntError;
+    type Err = ErrorKind;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 8 || !s.is_ascii() {
+            return Err(ErrorKind::InvalidDate);
+        }
         let year = s[0..4].parse::<u32>()?;
         let month = s[4..6].parse::<u32>()?;
         let day = s[6..8].parse::<u32>()?;
 
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(ErrorKind::InvalidDate);
+        }
         Ok(Self { year, month, day })
     }
 }
 
-impl std::string::ToString for Date {
-    fn to_string(&self) -> String {
-        format!("{:04}{:02}{:02}", self.year, self.month, self.day)
-    }
-}
-
-impl From<Date> for chrono::NaiveDate {
-    fn from(d: Date) -> Self {
-        Self::from_ymd(d.year as i32, d.month, d.day)
+fn days_in_month(year: u32, month: u32) -> u32 {
+    let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    match month {
+        2 if is_leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
     }
 }
 
-impl From<chrono::NaiveDate> for Date {
-    fn from(d: chrono::NaiveDate) -> Self {
-        Self {
-            year: d.year().try_into().unwrap(),
-            month: d.month(),
-            day: d.day(),
-        }
-    }
-}
-
-impl<Tz: chrono::TimeZone> From<chrono::Date<Tz>> for Date {
-    fn from(d: chrono::Date<Tz>) -> Self {
-        Self {
-            year: d.year() as u32,
-            month: d.month(),
-            day: d.day(),
-        }
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}{:02}{:02}", self.year, self.month, self.day)
     }
 }
 
@@ -506,7 +1187,7 @@ impl<Tz: chrono::TimeZone> From<chrono::Date<Tz>> for Date {
 ///
 /// This is a very naive Time struct, very minimal verifications are done.
 ///
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Time {
     hours: u32,
     minutes: u32,
@@ -521,17 +1202,76 @@ impl Time {
     /// Creates a new Time
     ///
     /// # panics
-    /// will panic if the  minutes or seconds are greater than 60 or
-    /// if the hours are greater than 24
+    /// will panic if the minutes or seconds are greater than 59 or
+    /// if the hours are greater than 23
     pub fn new(hours: u32, minutes: u32, seconds: u32) -> Self {
-        if hours > 24 || minutes > 60 || seconds > 60 {
-            panic!("Invalid Time")
+        Self::try_new(hours, minutes, seconds).expect("Invalid Time")
+    }
+
+    /// Creates a new Time, returning an error with the
+    /// [InvalidTime](enum.ErrorKind.html#variant.InvalidTime) kind if the minutes or seconds
+    /// are greater than 59 or if the hours are greater than 23
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::Time;
+    ///
+    /// let time = Time::try_new(13, 37, 0).unwrap();
+    /// assert_eq!(time.hours(), 13);
+    /// assert!(Time::try_new(24, 0, 0).is_err());
+    /// ```
+    pub fn try_new(hours: u32, minutes: u32, seconds: u32) -> Result<Self, Error> {
+        if hours > 23 || minutes > 59 || seconds > 59 {
+            return Err(Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::InvalidTime,
+            });
         }
-        Self {
+        Ok(Self {
             hours,
             minutes,
             seconds,
-        }
+        })
+    }
+
+    /// Returns the hours
+    pub fn hours(&self) -> u32 {
+        self.hours
+    }
+
+    /// Returns the minutes
+    pub fn minutes(&self) -> u32 {
+        self.minutes
+    }
+
+    /// Returns the seconds
+    pub fn seconds(&self) -> u32 {
+        self.seconds
+    }
+
+    /// Adds `secs` seconds (which may be negative) to the time.
+    ///
+    /// Returns the number of whole days rolled over (negative when going back
+    /// before midnight) and the resulting time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::Time;
+    ///
+    /// let (days, time) = Time::new(23, 59, 30).add_seconds(45);
+    /// assert_eq!(days, 1);
+    /// assert_eq!(time, Time::new(0, 0, 15));
+    /// ```
+    pub fn add_seconds(&self, secs: i32) -> (i32, Time) {
+        const SECONDS_PER_DAY: i64 = 86_400;
+        let seconds = i64::from(self.to_time_word() / Self::SECONDS_FACTOR) + i64::from(secs);
+        let days = seconds.div_euclid(SECONDS_PER_DAY);
+        let seconds_in_day = seconds.rem_euclid(SECONDS_PER_DAY);
+        let time = Self::from_word((seconds_in_day * i64::from(Self::SECONDS_FACTOR)) as i32);
+        (days as i32, time)
     }
 
     fn from_word(mut time_word: i32) -> Self {
@@ -547,7 +1287,7 @@ impl Time {
         }
     }
 
-    fn to_time_word(&self) -> i32 {
+    fn to_time_word(self) -> i32 {
         let mut time_word = self.hours * Self::HOURS_FACTOR as u32;
         time_word += self.minutes * Self::MINUTES_FACTOR as u32;
         time_word += self.seconds * Self::SECONDS_FACTOR as u32;
@@ -556,7 +1296,18 @@ impl Time {
 }
 
 /// FoxBase representation of a DateTime
-#[derive(Debug, Copy, Clone, PartialEq)]
+///
+/// It is displayed in the ISO 8601 format (`YYYY-MM-DDTHH:MM:SS`)
+///
+/// # Example
+///
+/// ```
+/// use dbase::{Date, DateTime, Time};
+///
+/// let datetime = DateTime::new(Date::new(5, 3, 2021), Time::new(13, 4, 9));
+/// assert_eq!(datetime.to_string(), "2021-03-05T13:04:09");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct DateTime {
     date: Date,
     time: Time,
@@ -568,12 +1319,31 @@ impl DateTime {
         Self { date, time }
     }
 
-    fn read_from<T: Read>(src: &mut T) -> Result<Self, ErrorKind> {
-        let julian_day_number = src.read_i32::<LittleEndian>()?;
-        let time_word = src.read_i32::<LittleEndian>()?;
+    /// Returns the date
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    /// Returns the time
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    /// Reads the date time, `julian_day_offset` is added to the julian day number read.
+    ///
+    /// Returns `None` for an empty date time (a julian day number of 0).
+    fn read_from<B: ByteOrder, T: Read>(
+        src: &mut T,
+        julian_day_offset: i32,
+    ) -> Result<Option<Self>, ErrorKind> {
+        let julian_day_number = src.read_i32::<B>()?;
+        let time_word = src.read_i32::<B>()?;
+        if julian_day_number == 0 {
+            return Ok(None);
+        }
         let time = Time::from_word(time_word);
-        let date = Date::julian_day_number_to_gregorian_date(julian_day_number);
-        Ok(Self { date, time })
+        let date = date_from_julian_day_number(julian_day_number, julian_day_offset)?;
+        Ok(Some(Self { date, time }))
     }
 
     fn write_to<W: Write>(&self, dest: &mut W) -> std::io::Result<()> {
@@ -583,7 +1353,37 @@ impl DateTime {
     }
 }
 
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}T{:02}:{:02}:{:02}",
+            self.date.to_iso_string(),
+            self.time.hours,
+            self.time.minutes,
+            self.time.seconds
+        )
+    }
+}
+
 impl WritableAsDbaseField for FieldValue {
+    fn is_null(&self) -> bool {
+        matches!(
+            self,
+            FieldValue::Character(None)
+                | FieldValue::Numeric(None)
+                | FieldValue::Logical(None)
+                | FieldValue::Date(None)
+                | FieldValue::Float(None)
+                | FieldValue::Integer(None)
+                | FieldValue::Currency(None)
+                | FieldValue::DateTime(None)
+                | FieldValue::Double(None)
+                | FieldValue::Varchar(None)
+                | FieldValue::Varbinary(None)
+        )
+    }
+
     fn write_as<W: Write>(&self, field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind> {
         if self.field_type() != field_type {
             Err(ErrorKind::IncompatibleType)
@@ -598,7 +1398,22 @@ impl WritableAsDbaseField for FieldValue {
                 FieldValue::Currency(value) => value.write_as(field_type, dst),
                 FieldValue::DateTime(value) => value.write_as(field_type, dst),
                 FieldValue::Double(value) => value.write_as(field_type, dst),
-                FieldValue::Memo(_) => unimplemented!("Cannot write memo"),
+                FieldValue::Memo(text) => {
+                    dst.write_all(text.as_bytes())?;
+                    Ok(())
+                }
+                FieldValue::General(bytes)
+                | FieldValue::Picture(bytes)
+                | FieldValue::Varbinary(Some(bytes)) => {
+                    dst.write_all(bytes)?;
+                    Ok(())
+                }
+                // The writer pads the values and sets their bits of the _NullFlags field
+                FieldValue::Varchar(Some(text)) => {
+                    dst.write_all(text.as_bytes())?;
+                    Ok(())
+                }
+                FieldValue::Varchar(None) | FieldValue::Varbinary(None) => Ok(()),
             }
         }
     }
@@ -611,7 +1426,16 @@ impl WritableAsDbaseField for f64 {
                 write!(dst, "{}", self)?;
                 Ok(())
             }
-            FieldType::Currency | FieldType::Double => {
+            FieldType::Currency => {
+                let scaled = (*self * CURRENCY_SCALE).round();
+                // NaN fails both comparisons, i64::MAX as f64 is 2^63
+                if !(scaled >= i64::MIN as f64 && scaled < i64::MAX as f64) {
+                    return Err(ErrorKind::CurrencyOutOfRange(*self));
+                }
+                dst.write_i64::<LittleEndian>(scaled as i64)?;
+                Ok(())
+            }
+            FieldType::Double => {
                 dst.write_f64::<LittleEndian>(*self)?;
                 Ok(())
             }
@@ -632,6 +1456,10 @@ impl WritableAsDbaseField for Date {
 }
 
 impl WritableAsDbaseField for Option<Date> {
+    fn is_null(&self) -> bool {
+        self.is_none()
+    }
+
     fn write_as<W: Write>(&self, field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind> {
         if field_type == FieldType::Date {
             if let Some(date) = self {
@@ -649,15 +1477,21 @@ impl WritableAsDbaseField for Option<Date> {
 }
 
 impl WritableAsDbaseField for Option<f64> {
+    fn is_null(&self) -> bool {
+        self.is_none()
+    }
+
     fn write_as<W: Write>(&self, field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind> {
-        if field_type == FieldType::Numeric {
-            if let Some(value) = self {
+        match (field_type, self) {
+            (FieldType::Numeric, Some(value)) => value.write_as(field_type, dst),
+            (FieldType::Numeric, None) => Ok(()),
+            (FieldType::Currency, Some(value)) | (FieldType::Double, Some(value)) => {
                 value.write_as(field_type, dst)
-            } else {
-                Ok(())
             }
-        } else {
-            Err(ErrorKind::IncompatibleType)
+            (FieldType::Currency, None) | (FieldType::Double, None) => {
+                write_null_binary_value(field_type, dst)
+            }
+            _ => Err(ErrorKind::IncompatibleType),
         }
     }
 }
@@ -674,6 +1508,10 @@ impl WritableAsDbaseField for f32 {
 }
 
 impl WritableAsDbaseField for Option<f32> {
+    fn is_null(&self) -> bool {
+        self.is_none()
+    }
+
     fn write_as<W: Write>(&self, field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind> {
         if field_type == FieldType::Float {
             if let Some(value) = self {
@@ -698,6 +1536,10 @@ impl WritableAsDbaseField for String {
 }
 
 impl WritableAsDbaseField for Option<String> {
+    fn is_null(&self) -> bool {
+        self.is_none()
+    }
+
     fn write_as<W: Write>(&self, field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind> {
         if field_type == FieldType::Character {
             if let Some(s) = self {
@@ -721,6 +1563,19 @@ impl WritableAsDbaseField for &str {
     }
 }
 
+/// The bytes are written as is in Character fields, which allows to write
+/// text that is already encoded (e.g. with [LanguageDriver::encode](../../enum.LanguageDriver.html#method.encode)).
+impl WritableAsDbaseField for &[u8] {
+    fn write_as<W: Write>(&self, field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind> {
+        if field_type == FieldType::Character {
+            dst.write_all(self)?;
+            Ok(())
+        } else {
+            Err(ErrorKind::IncompatibleType)
+        }
+    }
+}
+
 impl WritableAsDbaseField for bool {
     fn write_as<W: Write>(&self, field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind> {
         if field_type == FieldType::Logical {
@@ -737,6 +1592,10 @@ impl WritableAsDbaseField for bool {
 }
 
 impl WritableAsDbaseField for Option<bool> {
+    fn is_null(&self) -> bool {
+        self.is_none()
+    }
+
     fn write_as<W: Write>(&self, field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind> {
         if field_type == FieldType::Logical {
             if let Some(v) = self {
@@ -771,6 +1630,45 @@ impl WritableAsDbaseField for DateTime {
     }
 }
 
+impl WritableAsDbaseField for Option<i32> {
+    fn is_null(&self) -> bool {
+        self.is_none()
+    }
+
+    fn write_as<W: Write>(&self, field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind> {
+        match self {
+            Some(value) => value.write_as(field_type, dst),
+            None if field_type == FieldType::Integer => write_null_binary_value(field_type, dst),
+            None => Err(ErrorKind::IncompatibleType),
+        }
+    }
+}
+
+impl WritableAsDbaseField for Option<DateTime> {
+    fn is_null(&self) -> bool {
+        self.is_none()
+    }
+
+    fn write_as<W: Write>(&self, field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind> {
+        match self {
+            Some(value) => value.write_as(field_type, dst),
+            None if field_type == FieldType::DateTime => write_null_binary_value(field_type, dst),
+            None => Err(ErrorKind::IncompatibleType),
+        }
+    }
+}
+
+/// The null values of the binary fields are written as zeros,
+/// the writer sets their bit of the _NullFlags field
+fn write_null_binary_value<W: Write>(field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind> {
+    let size = match field_type {
+        FieldType::Integer => 4,
+        _ => 8,
+    };
+    dst.write_all(&[0u8; 8][..size])?;
+    Ok(())
+}
+
 #[cfg(feature = "serde")]
 mod de {
     use super::*;
@@ -796,7 +1694,7 @@ mod de {
                     E: serde::de::Error,
                 {
                     let string = String::from_utf8(v).unwrap();
-                    Ok(Date::from_str(&string).unwrap())
+                    Date::from_str(&string).map_err(E::custom)
                 }
             }
             deserializer.deserialize_byte_buf(DateVisitor)
@@ -817,8 +1715,9 @@ mod de {
             E: serde::de::Error,
         {
             let mut cursor = Cursor::new(v);
-            match DateTime::read_from(&mut cursor) {
-                Ok(d) => Ok(d),
+            match DateTime::read_from::<LittleEndian, _>(&mut cursor, 0) {
+                Ok(Some(d)) => Ok(d),
+                Ok(None) => Err(E::custom("the DateTime is empty")),
                 Err(e) => Err(E::custom(e)),
             }
         }
@@ -869,16 +1768,312 @@ mod ser {
     }
 }
 
-fn read_string_of_len<T: Read>(source: &mut T, len: u8) -> Result<String, std::io::Error> {
-    let mut bytes = Vec::<u8>::new();
-    bytes.resize(len as usize, 0u8);
+#[cfg(feature = "chrono")]
+mod chrono_conversions {
+    use super::*;
+
+    use chrono::{Datelike, Timelike};
+    use std::convert::TryInto;
+
+    fn invalid(kind: ErrorKind) -> Error {
+        Error {
+            record_num: 0,
+            field: None,
+            kind,
+        }
+    }
+
+    /// Returns an error with the [InvalidDate](enum.ErrorKind.html#variant.InvalidDate) kind
+    /// if the date does not exist (e.g. 31st of February)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate dbase;
+    /// use std::convert::TryFrom;
+    ///
+    /// let date = chrono::NaiveDate::try_from(dbase::Date::new(20, 7, 2019)).unwrap();
+    /// assert_eq!(date, chrono::NaiveDate::from_ymd_opt(2019, 7, 20).unwrap());
+    /// assert!(chrono::NaiveDate::try_from(dbase::Date::new(31, 2, 2019)).is_err());
+    /// ```
+    impl TryFrom<Date> for chrono::NaiveDate {
+        type Error = Error;
+
+        fn try_from(d: Date) -> Result<Self, Self::Error> {
+            Self::from_ymd_opt(d.year as i32, d.month, d.day)
+                .ok_or_else(|| invalid(ErrorKind::InvalidDate))
+        }
+    }
+
+    fn year_from_chrono(year: i32) -> Result<u32, Error> {
+        year.try_into()
+            .ok()
+            .filter(|year| *year <= 9999)
+            .ok_or_else(|| invalid(ErrorKind::InvalidDate))
+    }
+
+    /// Returns an error with the [InvalidDate](enum.ErrorKind.html#variant.InvalidDate) kind
+    /// if the year is not between 0 and 9999
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate chrono;
+    /// # extern crate dbase;
+    /// use std::convert::TryFrom;
+    ///
+    /// let date = chrono::NaiveDate::from_ymd_opt(2019, 7, 20).unwrap();
+    /// assert_eq!(dbase::Date::try_from(date).unwrap(), dbase::Date::new(20, 7, 2019));
+    /// let date = chrono::NaiveDate::from_ymd_opt(-44, 3, 15).unwrap();
+    /// assert!(dbase::Date::try_from(date).is_err());
+    /// ```
+    impl TryFrom<chrono::NaiveDate> for Date {
+        type Error = Error;
+
+        fn try_from(d: chrono::NaiveDate) -> Result<Self, Self::Error> {
+            Ok(Self {
+                year: year_from_chrono(d.year())?,
+                month: d.month(),
+                day: d.day(),
+            })
+        }
+    }
+
+    /// Returns an error with the [InvalidDate](enum.ErrorKind.html#variant.InvalidDate) kind
+    /// if the year is not between 0 and 9999
+    #[allow(deprecated)]
+    impl<Tz: chrono::TimeZone> TryFrom<chrono::Date<Tz>> for Date {
+        type Error = Error;
+
+        fn try_from(d: chrono::Date<Tz>) -> Result<Self, Self::Error> {
+            Ok(Self {
+                year: year_from_chrono(d.year())?,
+                month: d.month(),
+                day: d.day(),
+            })
+        }
+    }
+
+    /// Returns an error with the [InvalidDate](enum.ErrorKind.html#variant.InvalidDate)
+    /// or [InvalidTime](enum.ErrorKind.html#variant.InvalidTime) kind
+    /// if the date or time does not exist
+    impl TryFrom<DateTime> for chrono::NaiveDateTime {
+        type Error = Error;
+
+        fn try_from(dt: DateTime) -> Result<Self, Self::Error> {
+            let date = chrono::NaiveDate::try_from(dt.date)?;
+            let time =
+                chrono::NaiveTime::from_hms_opt(dt.time.hours, dt.time.minutes, dt.time.seconds)
+                    .ok_or_else(|| invalid(ErrorKind::InvalidTime))?;
+            Ok(Self::new(date, time))
+        }
+    }
+
+    /// The fractions of seconds are dropped
+    ///
+    /// Returns an error with the [InvalidDate](enum.ErrorKind.html#variant.InvalidDate) kind
+    /// if the year is not between 0 and 9999
+    impl TryFrom<chrono::NaiveDateTime> for DateTime {
+        type Error = Error;
+
+        fn try_from(dt: chrono::NaiveDateTime) -> Result<Self, Self::Error> {
+            let time = Time {
+                hours: dt.hour(),
+                minutes: dt.minute(),
+                seconds: dt.second(),
+            };
+            Ok(Self::new(Date::try_from(dt.date())?, time))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+mod json {
+    use super::*;
+
+    use serde_json::{Number, Value};
+
+    fn number_from_f64(value: f64) -> Value {
+        Number::from_f64(value).map_or(Value::Null, Value::Number)
+    }
+
+    /// Converts the value to JSON:
+    ///
+    /// - `None` values are `null`
+    /// - Character, Varchar and Memo values are strings
+    /// - Numeric, Float, Integer, Currency and Double values are numbers
+    ///   (non finite floats are `null`)
+    /// - Logical values are booleans
+    /// - Date values are `"YYYY-MM-DD"` strings and DateTime values are `"YYYY-MM-DDTHH:MM:SS"` strings
+    /// - General, Picture and Varbinary values are arrays of bytes
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate dbase;
+    /// # extern crate serde_json;
+    /// let value = dbase::FieldValue::Numeric(Some(12.5));
+    /// assert_eq!(serde_json::Value::from(&value), serde_json::json!(12.5));
+    /// ```
+    impl<'a> From<&'a FieldValue> for Value {
+        fn from(value: &'a FieldValue) -> Self {
+            match value {
+                FieldValue::Character(Some(string)) => Value::String(string.clone()),
+                FieldValue::Memo(string) | FieldValue::Varchar(Some(string)) => {
+                    Value::String(string.clone())
+                }
+                FieldValue::General(bytes)
+                | FieldValue::Picture(bytes)
+                | FieldValue::Varbinary(Some(bytes)) => Value::from(bytes.as_slice()),
+                FieldValue::Numeric(Some(number)) => number_from_f64(*number),
+                // The f32 is converted through its shortest representation,
+                // (e.g. 12.345 and not 12.345000267028809)
+                FieldValue::Float(Some(number)) => number
+                    .to_string()
+                    .parse::<f64>()
+                    .map_or(Value::Null, number_from_f64),
+                FieldValue::Integer(Some(number)) => Value::Number(Number::from(*number)),
+                FieldValue::Currency(Some(number)) | FieldValue::Double(Some(number)) => {
+                    number_from_f64(*number)
+                }
+                FieldValue::Logical(Some(b)) => Value::Bool(*b),
+                FieldValue::Date(Some(date)) => Value::String(date.to_iso_string()),
+                FieldValue::DateTime(Some(datetime)) => Value::String(datetime.to_string()),
+                FieldValue::Character(None)
+                | FieldValue::Numeric(None)
+                | FieldValue::Float(None)
+                | FieldValue::Logical(None)
+                | FieldValue::Date(None)
+                | FieldValue::Integer(None)
+                | FieldValue::Currency(None)
+                | FieldValue::DateTime(None)
+                | FieldValue::Double(None)
+                | FieldValue::Varchar(None)
+                | FieldValue::Varbinary(None) => Value::Null,
+            }
+        }
+    }
+}
+
+/// Converts a julian day number read from a file, shifted by `offset`,
+/// a number that does not correspond to a date between the years 1 and 9999
+/// gives an `InvalidDate` error
+fn date_from_julian_day_number(jdn: i32, offset: i32) -> Result<Date, ErrorKind> {
+    jdn.checked_add(offset)
+        .and_then(|jdn| Date::from_julian_day_number(jdn).ok())
+        .ok_or(ErrorKind::InvalidDate)
+}
+
+/// Currency values are stored as a 64-bit integer scaled by this factor,
+/// giving them four decimal places
+const CURRENCY_SCALE: f64 = 10_000.0;
+
+/// Reads the value of the fields types that are stored in binary
+/// using the byte order `B`
+fn read_binary_value<B: ByteOrder, T: Read>(
+    source: &mut T,
+    field_type: FieldType,
+    options: &ReadingOptions,
+) -> Result<FieldValue, ErrorKind> {
+    let value = match field_type {
+        FieldType::Integer => FieldValue::Integer(Some(source.read_i32::<B>()?)),
+        FieldType::Double => FieldValue::Double(Some(source.read_f64::<B>()?)),
+        FieldType::Currency => {
+            let scaled = source.read_i64::<B>()?;
+            FieldValue::Currency(Some(scaled as f64 / CURRENCY_SCALE))
+        }
+        FieldType::DateTime => FieldValue::DateTime(DateTime::read_from::<B, T>(
+            source,
+            options.julian_day_offset,
+        )?),
+        _ => unreachable!("{} is not stored in binary", field_type),
+    };
+    Ok(value)
+}
+
+/// Reads the index of the memo block a Memo field refers to,
+/// returns `None` if the field is blank (or 0 when the index is stored in binary).
+pub(crate) fn read_memo_index<T: Read>(
+    source: &mut T,
+    field_info: &FieldInfo,
+) -> Result<Option<u32>, ErrorKind> {
+    if field_info.field_length > 4 {
+        let string = read_string_of_len(source, field_info.field_length)?;
+        let trimmed_str = string.trim();
+        if trimmed_str.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(trimmed_str.parse::<u32>()?))
+        }
+    } else {
+        // The first block is always the header of the memo file,
+        // so 0 means that there is no data
+        match source.read_u32::<LittleEndian>()? {
+            0 => Ok(None),
+            index => Ok(Some(index)),
+        }
+    }
+}
+
+/// Reads the trimmed text of a Numeric or Float field,
+/// returns `None` if the field is blank or overflowed.
+pub(crate) fn read_numeric_text<T: Read>(
+    source: &mut T,
+    field_info: &FieldInfo,
+    options: &ReadingOptions,
+) -> Result<Option<String>, ErrorKind> {
+    let value = read_string_of_len(source, field_info.field_length)?;
+    let trimmed_value = value.trim();
+    if trimmed_value.is_empty() || is_numeric_overflow(trimmed_value, options)? {
+        Ok(None)
+    } else {
+        Ok(Some(trimmed_value.to_owned()))
+    }
+}
+
+/// Numeric and Float values that did not fit in the field are written as `*`.
+///
+/// A value made only of `*` is an overflow, a value only partially made of `*`
+/// is corrupted and is considered as an overflow unless
+/// [strict_numeric_values](../../struct.ReadingOptions.html#method.strict_numeric_values) is set.
+fn is_numeric_overflow(trimmed_value: &str, options: &ReadingOptions) -> Result<bool, ErrorKind> {
+    if trimmed_value.chars().all(|c| c == '*') {
+        Ok(true)
+    } else if trimmed_value.contains('*') {
+        if options.strict_numeric_values {
+            Err(ErrorKind::InvalidNumericValue(trimmed_value.to_owned()))
+        } else {
+            Ok(true)
+        }
+    } else {
+        Ok(false)
+    }
+}
+
+fn read_bytes_of_len<T: Read>(source: &mut T, len: u16) -> Result<Vec<u8>, std::io::Error> {
+    let mut bytes = vec![0u8; len as usize];
     source.read_exact(&mut bytes)?;
     // Trims the null bytes: string cannot be properly trimmed otherwise
-    let trimmed_bytes = match bytes.split(|b| b == &b'\0').next() {
-        Some(trimmed_bytes) => trimmed_bytes,
-        None => &bytes,
-    };
-    Ok(String::from_utf8_lossy(&trimmed_bytes).into_owned())
+    if let Some(null_pos) = bytes.iter().position(|b| *b == b'\0') {
+        bytes.truncate(null_pos);
+    }
+    Ok(bytes)
+}
+
+fn read_string_of_len<T: Read>(source: &mut T, len: u16) -> Result<String, std::io::Error> {
+    let bytes = read_bytes_of_len(source, len)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Reads a string that is used as is if it is valid UTF-8,
+/// and that is decoded as Windows-1252 otherwise
+fn read_sniffed_string_of_len<T: Read>(source: &mut T, len: u16) -> Result<String, std::io::Error> {
+    let bytes = read_bytes_of_len(source, len)?;
+    match String::from_utf8(bytes) {
+        Ok(string) => Ok(string),
+        Err(error) => Ok(LanguageDriver::Cp1252.decode(error.as_bytes())),
+    }
 }
 
 #[cfg(test)]
@@ -893,11 +2088,14 @@ mod test {
             name: "".to_owned(),
             field_type,
             displacement_field: [0u8; 4],
-            field_length: len,
+            field_length: u16::from(len),
             num_decimal_places: 0,
-            flags: FieldFlags { 0: 0u8 },
+            flags: FieldFlags(0u8),
             autoincrement_next_val: [0u8; 5],
             autoincrement_step: 0u8,
+            reserved: [0u8; 7],
+            null_bit: None,
+            varlength_bit: None,
         }
     }
 
@@ -907,7 +2105,9 @@ mod test {
 
         out.set_position(0);
 
-        let read_value = FieldValue::read_from(&mut out, &mut None, field_info).unwrap();
+        let read_value =
+            FieldValue::read_from(&mut out, &mut None, field_info, &ReadingOptions::default())
+                .unwrap();
         assert_eq!(value, &read_value);
     }
 
@@ -915,8 +2115,8 @@ mod test {
     fn write_read_date() {
         let date = FieldValue::from(Date {
             year: 2019,
-            month: 01,
-            day: 01,
+            month: 1,
+            day: 1,
         });
 
         let field_info = create_temp_field_info(FieldType::Date, FieldType::Date.size().unwrap());
@@ -949,14 +2149,114 @@ mod test {
         let record_info = create_temp_field_info(FieldType::Character, out.position() as u8);
         out.set_position(0);
 
-        match FieldValue::read_from(&mut out, &mut None, &record_info).unwrap() {
+        match FieldValue::read_from(
+            &mut out,
+            &mut None,
+            &record_info,
+            &ReadingOptions::default(),
+        )
+        .unwrap()
+        {
             FieldValue::Character(s) => {
                 assert_eq!(s, Some(String::from("🤔")));
             }
-            _ => assert!(false, "Did not read a Character field ??"),
+            _ => panic!("Did not read a Character field ??"),
         }
     }
 
+    #[test]
+    fn time_add_seconds() {
+        let time = Time::new(23, 59, 30);
+        assert_eq!(time.add_seconds(0), (0, time));
+        assert_eq!(time.add_seconds(29), (0, Time::new(23, 59, 59)));
+        assert_eq!(time.add_seconds(30), (1, Time::new(0, 0, 0)));
+        assert_eq!(time.add_seconds(2 * 86_400 + 75), (3, Time::new(0, 0, 45)));
+
+        let time = Time::new(0, 0, 10);
+        assert_eq!(time.add_seconds(-20), (-1, Time::new(23, 59, 50)));
+        assert_eq!(time.add_seconds(-86_410), (-1, Time::new(0, 0, 0)));
+        assert_eq!(time.add_seconds(-86_411), (-2, Time::new(23, 59, 59)));
+    }
+
+    #[test]
+    fn typed_accessors_of_other_variants() {
+        let values = [
+            FieldValue::Character(Some("1".to_owned())),
+            FieldValue::Numeric(Some(1.0)),
+            FieldValue::Logical(Some(true)),
+            FieldValue::Date(Some(Date::new(1, 1, 2000))),
+            FieldValue::Integer(Some(1)),
+        ];
+        for (i, value) in values.iter().enumerate() {
+            assert_eq!(value.as_character().is_some(), i == 0);
+            assert_eq!(value.as_numeric().is_some(), i == 1);
+            assert_eq!(value.as_logical().is_some(), i == 2);
+            assert_eq!(value.as_date().is_some(), i == 3);
+            assert_eq!(value.as_integer().is_some(), i == 4);
+        }
+        assert_eq!(
+            FieldValue::Memo("notes".to_owned()).as_character(),
+            Some("notes")
+        );
+        assert_eq!(FieldValue::Float(None).as_numeric(), None);
+        assert_eq!(FieldValue::Logical(None).as_logical(), None);
+    }
+
+    #[test]
+    fn read_blank_char() {
+        let record_info = create_temp_field_info(FieldType::Character, 10);
+        let blank = b"          ";
+
+        let value = FieldValue::read_from(
+            &mut Cursor::new(blank.to_vec()),
+            &mut None,
+            &record_info,
+            &ReadingOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(value, FieldValue::Character(None));
+
+        let value = FieldValue::read_from(
+            &mut Cursor::new(blank.to_vec()),
+            &mut None,
+            &record_info,
+            &ReadingOptions::default().empty_character_as_none(false),
+        )
+        .unwrap();
+        assert_eq!(value, FieldValue::Character(Some(String::new())));
+    }
+
+    #[test]
+    fn read_partially_overflowed_numeric() {
+        let record_info = create_temp_field_info(FieldType::Numeric, 4);
+        let value = FieldValue::read_from(
+            &mut Cursor::new(b"12**".to_vec()),
+            &mut None,
+            &record_info,
+            &ReadingOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(value, FieldValue::Numeric(None));
+
+        let error = FieldValue::read_from(
+            &mut Cursor::new(b"12**".to_vec()),
+            &mut None,
+            &record_info,
+            &ReadingOptions::default().strict_numeric_values(true),
+        )
+        .unwrap_err();
+        assert!(matches!(error, ErrorKind::InvalidNumericValue(ref v) if v == "12**"));
+
+        let value = FieldValue::read_from(
+            &mut Cursor::new(b" ***".to_vec()),
+            &mut None,
+            &record_info,
+            &ReadingOptions::default().strict_numeric_values(true),
+        )
+        .unwrap();
+        assert_eq!(value, FieldValue::Numeric(None));
+    }
+
     #[test]
     fn write_read_float() {
         let field = FieldValue::Float(Some(12.43));
@@ -970,7 +2270,7 @@ mod test {
     fn test_write_read_integer_via_enum() {
         use crate::record::FieldName;
 
-        let value = FieldValue::Integer(1457);
+        let value = FieldValue::Integer(Some(1457));
 
         let field_info = FieldInfo::new(
             FieldName::try_from("Integer").unwrap(),
@@ -985,7 +2285,7 @@ mod test {
     fn test_from_julian_day_number() {
         let date = Date::julian_day_number_to_gregorian_date(2458685);
         assert_eq!(date.year, 2019);
-        assert_eq!(date.month, 07);
+        assert_eq!(date.month, 7);
         assert_eq!(date.day, 20);
     }
 
@@ -993,7 +2293,7 @@ mod test {
     fn test_to_julian_day_number() {
         let date = Date {
             year: 2019,
-            month: 07,
+            month: 7,
             day: 20,
         };
         assert_eq!(date.to_julian_day_number(), 2458685);
@@ -1,20 +1,24 @@
 use std::convert::TryFrom;
 use std::io::{Read, Write};
 
-use byteorder::{ReadBytesExt, WriteBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
 pub mod field;
 
 use self::field::{Date, DateTime, FieldType};
-use crate::{ErrorKind, FieldValue};
+use crate::header::Version;
+use crate::{ErrorKind, FieldNameNormalization, FieldValue, ReadingOptions};
 
 const DELETION_FLAG_NAME: &str = "DeletionFlag";
 const FIELD_NAME_LENGTH: usize = 11;
+/// Maximum length in bytes of a field name,
+/// the last byte of the stored name being the terminating 0
+pub(crate) const FIELD_NAME_MAX_LENGTH: usize = FIELD_NAME_LENGTH - 1;
 
 #[derive(Debug)]
 /// Wrapping struct to create a FieldName from a String.
 ///
-/// FieldNames in the dBase format cannot exceed 11 bytes (not char).
+/// FieldNames in the dBase format cannot exceed 10 bytes (not char).
 ///
 /// # Examples
 ///
@@ -23,7 +27,8 @@ const FIELD_NAME_LENGTH: usize = 11;
 /// use std::convert::TryFrom;
 ///
 /// let name = FieldName::try_from("Small Name");
-/// assert!(name.is_ok())
+/// assert!(name.is_ok());
+/// assert!(FieldName::try_from("Longer Name").is_err());
 /// ```
 pub struct FieldName(String);
 
@@ -31,12 +36,10 @@ impl TryFrom<&str> for FieldName {
     type Error = &'static str;
 
     fn try_from(name: &str) -> Result<Self, Self::Error> {
-        if name.as_bytes().len() > FIELD_NAME_LENGTH {
-            Err("FieldName byte representation cannot exceed 11 bytes")
+        if name.len() > FIELD_NAME_MAX_LENGTH {
+            Err("FieldName byte representation cannot exceed 10 bytes")
         } else {
-            Ok(Self {
-                0: name.to_string(),
-            })
+            Ok(Self(name.to_string()))
         }
     }
 }
@@ -49,42 +52,143 @@ pub struct FieldInfo {
     /// The field type
     pub(crate) field_type: FieldType,
     pub(crate) displacement_field: [u8; 4],
-    pub(crate) field_length: u8,
+    /// For Character fields, it includes the high byte stored in
+    /// place of the number of decimals (FoxPro & Clipper extension)
+    pub(crate) field_length: u16,
     pub(crate) num_decimal_places: u8,
     pub(crate) flags: FieldFlags,
     pub(crate) autoincrement_next_val: [u8; 5],
     pub(crate) autoincrement_step: u8,
+    pub(crate) reserved: [u8; 7],
+    /// Index of the bit of the `_NullFlags` field telling if this field is null
+    pub(crate) null_bit: Option<usize>,
+    /// Index of the bit of the `_NullFlags` field telling if the value of this
+    /// Varchar or Varbinary field is shorter than the field
+    pub(crate) varlength_bit: Option<usize>,
 }
 
 impl FieldInfo {
     pub(crate) const SIZE: usize = 32;
+    pub(crate) const MAX_NAME_LENGTH: usize = FIELD_NAME_LENGTH;
 
+    /// Returns the name of the field
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Returns the type of the field
     pub fn field_type(&self) -> FieldType {
         self.field_type
     }
 
+    /// Returns the length in bytes of the field, as stored in the length byte
+    /// of its descriptor.
+    ///
+    /// For Character fields longer than 255 bytes, only the low byte of the length
+    /// is returned, use [full_length](#method.full_length) to get their real length.
     pub fn length(&self) -> u8 {
+        self.field_length as u8
+    }
+
+    /// Returns the length in bytes of the field, including the high byte
+    /// that FoxPro (and Clipper) store in place of the number of decimals
+    /// of Character fields longer than 255 bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, Reader, Record, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut cursor = Cursor::new(Vec::<u8>::new());
+    /// TableWriterBuilder::new()
+    ///     .add_long_character_field(FieldName::try_from("Comment").unwrap(), 300)
+    ///     .build_with_dest(&mut cursor)
+    ///     .write_records(&Vec::<Record>::new())?;
+    /// cursor.set_position(0);
+    /// let reader = Reader::new(cursor)?;
+    /// assert_eq!(reader.fields()[1].full_length(), 300);
+    /// assert_eq!(reader.fields()[1].length(), 44);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn full_length(&self) -> u16 {
         self.field_length
     }
 
+    /// Returns the number of decimal places of the field,
+    /// only meaningful for Numeric, Float and Double fields
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let reader = dbase::Reader::from_path("tests/data/null_binary_fields.dbf")?;
+    /// let price = &reader.fields()[3];
+    /// assert_eq!(price.name(), "PRICE");
+    /// assert_eq!(price.num_decimal_places(), 4);
+    /// assert!(price.flags().is_nullable());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn num_decimal_places(&self) -> u8 {
+        self.num_decimal_places
+    }
+
+    /// Returns the flags of the field (Visual FoxPro)
+    pub fn flags(&self) -> FieldFlags {
+        self.flags
+    }
+
+    /// Returns whether the field has a tag in the production index (.mdx) of the file
+    /// (last byte of the descriptor, used by dBase IV and later)
+    pub fn is_indexed(&self) -> bool {
+        self.reserved[6] & 0x01 != 0
+    }
+
     pub(crate) fn new(name: FieldName, field_type: FieldType, length: u8) -> Self {
         Self {
             name: name.0,
             field_type,
             displacement_field: [0u8; 4],
-            field_length: length,
+            field_length: u16::from(length),
             num_decimal_places: 0,
             flags: FieldFlags::default(),
             autoincrement_next_val: [0u8; 5],
             autoincrement_step: 0u8,
+            reserved: [0u8; 7],
+            null_bit: None,
+            varlength_bit: None,
         }
     }
 
-    pub(crate) fn read_from<T: Read>(source: &mut T) -> Result<Self, ErrorKind> {
+    /// Sets the last 14 bytes of the descriptor (offsets 18 to 31).
+    ///
+    /// dBase III reserves them (multi-user, work area id at offset 20, set fields flag),
+    /// while FoxPro uses some of them for the flags and the autoincrement of the field.
+    pub(crate) fn set_reserved_bytes(&mut self, bytes: [u8; 14]) {
+        self.flags = FieldFlags(bytes[0]);
+        self.autoincrement_next_val.copy_from_slice(&bytes[1..6]);
+        self.autoincrement_step = bytes[6];
+        self.reserved.copy_from_slice(&bytes[7..]);
+    }
+
+    /// Returns the bytes at offsets 18 to 24 of the descriptor,
+    /// the flags and the autoincrement of the field in Visual FoxPro files.
+    pub(crate) fn flags_and_autoincrement_bytes(&self) -> [u8; 7] {
+        let mut bytes = [0u8; 7];
+        bytes[0] = self.flags.0;
+        bytes[1..6].copy_from_slice(&self.autoincrement_next_val);
+        bytes[6] = self.autoincrement_step;
+        bytes
+    }
+
+    pub(crate) fn read_from<T: Read>(
+        source: &mut T,
+        options: &ReadingOptions,
+        version: Version,
+    ) -> Result<Self, ErrorKind> {
         let mut name = [0u8; FIELD_NAME_LENGTH];
         source.read_exact(&mut name)?;
         let field_type = source.read_u8()?;
@@ -92,26 +196,46 @@ impl FieldInfo {
         let mut displacement_field = [0u8; 4];
         source.read_exact(&mut displacement_field)?;
 
-        let record_length = source.read_u8()?;
-        let num_decimal_places = source.read_u8()?;
+        let mut record_length = u16::from(source.read_u8()?);
+        let mut num_decimal_places = source.read_u8()?;
 
-        let flags = FieldFlags {
-            0: source.read_u8()?,
-        };
+        let flags = FieldFlags(source.read_u8()?);
 
         let mut autoincrement_next_val = [0u8; 5];
 
         source.read_exact(&mut autoincrement_next_val)?;
         let autoincrement_step = source.read_u8()?;
 
-        let mut _reserved = [0u8; 7];
-        source.read_exact(&mut _reserved)?;
+        let mut reserved = [0u8; 7];
+        source.read_exact(&mut reserved)?;
+
+        let s = match options.field_name_normalization {
+            FieldNameNormalization::Keep => String::from_utf8_lossy(&name)
+                .trim_matches(|c| c == '\u{0}')
+                .to_owned(),
+            FieldNameNormalization::Trim | FieldNameNormalization::TrimAndUppercase => {
+                let name = name.split(|b| *b == 0).next().unwrap_or(&name);
+                let trimmed_name = String::from_utf8_lossy(name).trim().to_owned();
+                if options.field_name_normalization == FieldNameNormalization::TrimAndUppercase {
+                    trimmed_name.to_uppercase()
+                } else {
+                    trimmed_name
+                }
+            }
+        };
 
-        let s = String::from_utf8_lossy(&name)
-            .trim_matches(|c| c == '\u{0}')
-            .to_owned();
+        let field_type = if options.strict_field_types {
+            FieldType::try_from(field_type as char)?
+        } else {
+            FieldType::try_from(field_type.to_ascii_uppercase() as char)?
+        };
 
-        let field_type = FieldType::try_from(field_type as char)?;
+        // Character fields longer than 255 bytes store the high byte
+        // of their length in place of the number of decimals
+        if field_type == FieldType::Character && version.has_long_character_fields() {
+            record_length |= u16::from(num_decimal_places) << 8;
+            num_decimal_places = 0;
+        }
 
         Ok(Self {
             name: s,
@@ -122,25 +246,30 @@ impl FieldInfo {
             flags,
             autoincrement_next_val,
             autoincrement_step,
+            reserved,
+            null_bit: None,
+            varlength_bit: None,
         })
     }
 
     pub(crate) fn write_to<T: Write>(&self, dest: &mut T) -> std::io::Result<()> {
-        let num_bytes = self.name.as_bytes().len();
+        let num_bytes = self.name.len();
         let mut name_bytes = [0u8; FIELD_NAME_LENGTH];
         name_bytes[..num_bytes.min(FIELD_NAME_LENGTH)].copy_from_slice(self.name.as_bytes());
         dest.write_all(&name_bytes)?;
 
         dest.write_u8(u8::from(self.field_type))?;
         dest.write_all(&self.displacement_field)?;
-        dest.write_u8(self.field_length)?;
-        dest.write_u8(self.num_decimal_places)?;
+        if self.field_type == FieldType::Character && self.field_length > u16::from(u8::MAX) {
+            dest.write_u16::<LittleEndian>(self.field_length)?;
+        } else {
+            dest.write_u8(self.field_length as u8)?;
+            dest.write_u8(self.num_decimal_places)?;
+        }
         dest.write_u8(self.flags.0)?;
         dest.write_all(&self.autoincrement_next_val)?;
         dest.write_u8(self.autoincrement_step)?;
-
-        let reserved = [0u8; 7];
-        dest.write_all(&reserved)?;
+        dest.write_all(&self.reserved)?;
 
         Ok(())
     }
@@ -152,15 +281,28 @@ impl FieldInfo {
             displacement_field: [0u8; 4],
             field_length: 1,
             num_decimal_places: 0,
-            flags: FieldFlags { 0: 0u8 },
+            flags: FieldFlags(0u8),
             autoincrement_next_val: [0u8; 5],
             autoincrement_step: 0u8,
+            reserved: [0u8; 7],
+            null_bit: None,
+            varlength_bit: None,
         }
     }
 
     pub(crate) fn is_deletion_flag(&self) -> bool {
         self.name == DELETION_FLAG_NAME
     }
+
+    /// Whether the field may be null (Visual FoxPro)
+    pub(crate) fn is_nullable(&self) -> bool {
+        self.flags.is_nullable()
+    }
+
+    /// Fields that are part of the record, but do not hold user values
+    pub(crate) fn is_hidden(&self) -> bool {
+        self.is_deletion_flag() || self.field_type == FieldType::NullFlags
+    }
 }
 
 impl std::fmt::Display for FieldInfo {
@@ -173,13 +315,30 @@ impl std::fmt::Display for FieldInfo {
     }
 }
 
-/// Flags describing a field
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub(crate) struct FieldFlags(u8);
+/// Flags describing a field, stored by Visual FoxPro in byte 18 of the field descriptor
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct FieldFlags(u8);
+
+impl FieldFlags {
+    /// Returns the byte of the flags
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
 
-impl Default for FieldFlags {
-    fn default() -> Self {
-        Self { 0: 0 }
+    /// Whether the field is a system column, hidden from the user (e.g. `_NullFlags`)
+    pub fn is_system(&self) -> bool {
+        self.0 & 0x01 != 0
+    }
+
+    /// Whether the field may be null
+    pub fn is_nullable(&self) -> bool {
+        self.0 & 0x02 != 0
+    }
+
+    /// Whether the field holds binary data (Character and Memo fields
+    /// whose content is not converted between code pages)
+    pub fn is_binary(&self) -> bool {
+        self.0 & 0x04 != 0
     }
 }
 
@@ -236,7 +395,19 @@ macro_rules! impl_try_from_field_value_for_ {
     };
 }
 
-impl_try_from_field_value_for_!(FieldValue::Numeric => Option<f64>);
+impl TryFrom<FieldValue> for Option<f64> {
+    type Error = FieldConversionError;
+
+    fn try_from(value: FieldValue) -> Result<Self, Self::Error> {
+        match value {
+            FieldValue::Numeric(v) | FieldValue::Currency(v) | FieldValue::Double(v) => Ok(v),
+            _ => Err(FieldConversionError::FieldTypeNotAsExpected {
+                expected: FieldType::Numeric,
+                actual: value.field_type(),
+            }),
+        }
+    }
+}
 
 impl_try_from_field_value_for_!(FieldValue::Float => Option<f32>);
 impl_try_from_field_value_for_!(FieldValue::Float(Some(v)) => f32);
@@ -250,24 +421,114 @@ impl_try_from_field_value_for_!(FieldValue::Character(Some(string)) => String);
 impl_try_from_field_value_for_!(FieldValue::Logical => Option<bool>);
 impl_try_from_field_value_for_!(FieldValue::Logical(Some(b)) => bool);
 
-impl_try_from_field_value_for_!(FieldValue::Integer => i32);
+impl_try_from_field_value_for_!(FieldValue::Integer => Option<i32>);
+impl_try_from_field_value_for_!(FieldValue::Integer(Some(v)) => i32);
 
 impl TryFrom<FieldValue> for f64 {
     type Error = FieldConversionError;
 
     fn try_from(value: FieldValue) -> Result<Self, Self::Error> {
         match value {
-            FieldValue::Numeric(Some(v)) => Ok(v),
-            FieldValue::Numeric(None) => Err(FieldConversionError::NoneValue),
-            FieldValue::Currency(c) => Ok(c),
-            FieldValue::Double(d) => Ok(d),
+            FieldValue::Numeric(Some(v))
+            | FieldValue::Currency(Some(v))
+            | FieldValue::Double(Some(v)) => Ok(v),
+            FieldValue::Numeric(None) | FieldValue::Currency(None) | FieldValue::Double(None) => {
+                Err(FieldConversionError::NoneValue)
+            }
             _ => Err(FieldConversionError::IncompatibleType),
         }
     }
 }
 
 // Fox Pro types
-impl_try_from_field_value_for_!(FieldValue::DateTime => DateTime);
+impl_try_from_field_value_for_!(FieldValue::DateTime => Option<DateTime>);
+impl_try_from_field_value_for_!(FieldValue::DateTime(Some(v)) => DateTime);
+impl_try_from_field_value_for_!(FieldValue::General => Vec<u8>);
+
+macro_rules! impl_try_from_field_value_ref_for_ {
+    (&FieldValue::$variant:ident => $out_type:ty, |$v:ident| $convert:expr) => {
+        impl<'a> TryFrom<&'a FieldValue> for $out_type {
+            type Error = FieldConversionError;
+
+            fn try_from(value: &'a FieldValue) -> Result<Self, Self::Error> {
+                if let FieldValue::$variant($v) = value {
+                    Ok($convert)
+                } else {
+                    Err(FieldConversionError::FieldTypeNotAsExpected {
+                        expected: FieldType::$variant,
+                        actual: value.field_type(),
+                    })
+                }
+            }
+        }
+    };
+    (&FieldValue::$variant:ident(Some($v:ident)) => $out_type:ty, $convert:expr) => {
+        impl<'a> TryFrom<&'a FieldValue> for $out_type {
+            type Error = FieldConversionError;
+
+            fn try_from(value: &'a FieldValue) -> Result<Self, Self::Error> {
+                match value {
+                    FieldValue::$variant(Some($v)) => Ok($convert),
+                    FieldValue::$variant(None) => Err(FieldConversionError::NoneValue),
+                    _ => Err(FieldConversionError::FieldTypeNotAsExpected {
+                        expected: FieldType::$variant,
+                        actual: value.field_type(),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+// Borrowing conversions, to read a value without moving it out of its record
+impl<'a> TryFrom<&'a FieldValue> for Option<f64> {
+    type Error = FieldConversionError;
+
+    fn try_from(value: &'a FieldValue) -> Result<Self, Self::Error> {
+        match value {
+            FieldValue::Numeric(v) | FieldValue::Currency(v) | FieldValue::Double(v) => Ok(*v),
+            _ => Err(FieldConversionError::FieldTypeNotAsExpected {
+                expected: FieldType::Numeric,
+                actual: value.field_type(),
+            }),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a FieldValue> for f64 {
+    type Error = FieldConversionError;
+
+    fn try_from(value: &'a FieldValue) -> Result<Self, Self::Error> {
+        match value {
+            FieldValue::Numeric(Some(v))
+            | FieldValue::Currency(Some(v))
+            | FieldValue::Double(Some(v)) => Ok(*v),
+            FieldValue::Numeric(None) | FieldValue::Currency(None) | FieldValue::Double(None) => {
+                Err(FieldConversionError::NoneValue)
+            }
+            _ => Err(FieldConversionError::IncompatibleType),
+        }
+    }
+}
+
+impl_try_from_field_value_ref_for_!(&FieldValue::Float => Option<f32>, |v| *v);
+impl_try_from_field_value_ref_for_!(&FieldValue::Float(Some(v)) => f32, *v);
+
+impl_try_from_field_value_ref_for_!(&FieldValue::Date => Option<field::Date>, |v| *v);
+impl_try_from_field_value_ref_for_!(&FieldValue::Date(Some(v)) => field::Date, *v);
+
+impl_try_from_field_value_ref_for_!(&FieldValue::Character => Option<&'a str>, |v| v.as_deref());
+impl_try_from_field_value_ref_for_!(&FieldValue::Character(Some(string)) => &'a str, string.as_str());
+
+impl_try_from_field_value_ref_for_!(&FieldValue::Logical => Option<bool>, |v| *v);
+impl_try_from_field_value_ref_for_!(&FieldValue::Logical(Some(b)) => bool, *b);
+
+impl_try_from_field_value_ref_for_!(&FieldValue::Integer => Option<i32>, |v| *v);
+impl_try_from_field_value_ref_for_!(&FieldValue::Integer(Some(v)) => i32, *v);
+
+impl_try_from_field_value_ref_for_!(&FieldValue::DateTime => Option<DateTime>, |v| *v);
+impl_try_from_field_value_ref_for_!(&FieldValue::DateTime(Some(v)) => DateTime, *v);
+impl_try_from_field_value_ref_for_!(&FieldValue::General => &'a [u8], |v| v.as_slice());
 
 macro_rules! impl_from_type_for_field_value (
     ($t:ty => FieldValue::$variant:ident) => {
@@ -302,7 +563,11 @@ impl_from_type_for_field_value!(Option<Date> => FieldValue::Date);
 impl_from_type_for_field_value!(Date => FieldValue::Date(Some(v)));
 
 // Fox Pro types
-impl_from_type_for_field_value!(DateTime => FieldValue::DateTime);
+impl_from_type_for_field_value!(Option<i32> => FieldValue::Integer);
+impl_from_type_for_field_value!(i32 => FieldValue::Integer(Some(v)));
+
+impl_from_type_for_field_value!(Option<DateTime> => FieldValue::DateTime);
+impl_from_type_for_field_value!(DateTime => FieldValue::DateTime(Some(v)));
 
 #[cfg(test)]
 mod test {
@@ -321,7 +586,11 @@ mod test {
 
         cursor.set_position(0);
 
-        let read_field_info = FieldInfo::read_from(&mut cursor).unwrap();
+        let version = Version::DBase3 {
+            supports_memo: false,
+        };
+        let read_field_info =
+            FieldInfo::read_from(&mut cursor, &ReadingOptions::default(), version).unwrap();
 
         assert_eq!(read_field_info, field_info);
     }
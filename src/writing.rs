@@ -1,18 +1,124 @@
 //! Module with all structs & functions charged of writing .dbf file content
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fs::File;
-use std::io::{BufWriter, Cursor, Seek, SeekFrom, Write};
+use std::io::{BufWriter, Cursor, Read, Seek, SeekFrom, Write};
 use std::path::Path;
 
-use byteorder::WriteBytesExt;
+use byteorder::{LittleEndian, WriteBytesExt};
 
-use crate::header::Header;
-use crate::reading::TERMINATOR_VALUE;
-use crate::record::{field::FieldType, FieldInfo, FieldName};
-use crate::{Error, ErrorKind, FieldIOError, Record};
+use crate::header::{Header, Version};
+use crate::reading::{find_duplicate_field_name, DeletedRecords, BACKLINK_SIZE, TERMINATOR_VALUE};
+use crate::record::{
+    field::{
+        FieldType, MemoFileType, MemoWriter, FOX_BASE_MEMO_PICTURE_TYPE, FOX_BASE_MEMO_TEXT_TYPE,
+    },
+    FieldInfo, FieldName, FIELD_NAME_MAX_LENGTH,
+};
+use crate::{Columns, Error, ErrorKind, FieldIOError, FieldValue, Reader, Record};
 use reading::TableInfo;
 
 /// A dbase file ends with this byte
-const FILE_TERMINATOR: u8 = 0x1A;
+pub(crate) const FILE_TERMINATOR: u8 = 0x1A;
+
+/// The flavour of dBase file a [TableWriterBuilder](struct.TableWriterBuilder.html)
+/// produces.
+///
+/// The dialect sets the version byte of the header, the field types that
+/// can be used, the limits of the fields and the format of the memo file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Dialect {
+    /// dBase III+ (version byte 0x03, or 0x83 with memo fields)
+    DbaseIII,
+    /// dBase IV (version byte 0x43, or 0x8B with memo fields)
+    DbaseIV,
+    /// Visual FoxPro (version byte 0x30)
+    VisualFoxPro,
+}
+
+impl Dialect {
+    /// Returns the format of the memo file that goes with the table
+    ///
+    /// The dBase IV memo format can be read but not written,
+    /// so Memo fields are refused with the dBase IV dialect.
+    pub fn memo_file_type(self) -> MemoFileType {
+        match self {
+            Dialect::DbaseIII => MemoFileType::DbaseMemo,
+            Dialect::DbaseIV => MemoFileType::DbaseMemo4,
+            Dialect::VisualFoxPro => MemoFileType::FoxBaseMemo,
+        }
+    }
+
+    fn version(self, has_memo: bool) -> Version {
+        match self {
+            Dialect::DbaseIII => Version::DBase3 {
+                supports_memo: has_memo,
+            },
+            Dialect::DbaseIV => Version::DBase4 {
+                supports_memo: has_memo,
+            },
+            Dialect::VisualFoxPro => Version::VisualFoxPro,
+        }
+    }
+
+    pub(crate) fn supports_field_type(self, field_type: FieldType) -> bool {
+        match field_type {
+            FieldType::Character
+            | FieldType::Date
+            | FieldType::Numeric
+            | FieldType::Logical
+            | FieldType::Memo => true,
+            FieldType::Float => self != Dialect::DbaseIII,
+            FieldType::Currency
+            | FieldType::DateTime
+            | FieldType::Integer
+            | FieldType::Double
+            | FieldType::NullFlags
+            | FieldType::General
+            | FieldType::Picture
+            | FieldType::Varchar
+            | FieldType::Varbinary => self == Dialect::VisualFoxPro,
+        }
+    }
+
+    /// Maximum length of a field of the given type, if it is limited by the dialect
+    fn max_field_length(self, field_type: FieldType) -> Option<u16> {
+        match field_type {
+            FieldType::Character | FieldType::Varchar | FieldType::Varbinary => Some(254),
+            FieldType::Numeric | FieldType::Float if self == Dialect::DbaseIII => Some(19),
+            FieldType::Numeric | FieldType::Float => Some(20),
+            _ => None,
+        }
+    }
+
+    fn check_fields(self, fields_info: &[FieldInfo]) -> Result<(), Error> {
+        for info in fields_info {
+            let kind = if !self.supports_field_type(info.field_type) {
+                ErrorKind::InvalidFieldType(u8::from(info.field_type) as char)
+            } else if info.field_type == FieldType::Memo && self == Dialect::DbaseIV {
+                // The memo writer does not support the dBase IV memo format
+                ErrorKind::Message(
+                    "Memo fields cannot be written with the dBase IV dialect, \
+                     use the dBase III one"
+                        .to_owned(),
+                )
+            } else if self
+                .max_field_length(info.field_type)
+                .is_some_and(|max| info.field_length > max)
+            {
+                ErrorKind::FieldTooLong
+            } else {
+                continue;
+            };
+            return Err(Error {
+                record_num: 0,
+                field: Some(info.clone()),
+                kind,
+            });
+        }
+        Ok(())
+    }
+}
 
 /// Builder to be used to create a [TableWriter](struct.TableWriter.html).
 ///
@@ -38,6 +144,10 @@ const FILE_TERMINATOR: u8 = 0x1A;
 pub struct TableWriterBuilder {
     v: Vec<FieldInfo>,
     hdr: Header,
+    write_eof_marker: bool,
+    numeric_none_fill_byte: u8,
+    numeric_zero_padding: bool,
+    dialect: Option<Dialect>,
 }
 
 impl TableWriterBuilder {
@@ -80,9 +190,12 @@ impl TableWriterBuilder {
         }
         let mut hdr = table_info.header;
         hdr.update_date();
+        // The records will be counted as they are written
+        hdr.num_records = 0;
         Self {
             v: fields_info,
             hdr,
+            ..Self::default()
         }
     }
 
@@ -94,6 +207,33 @@ impl TableWriterBuilder {
         self
     }
 
+    /// Adds a Character field that can be longer than 255 bytes.
+    ///
+    /// The high byte of the length is stored in place of the number of decimals,
+    /// which is an extension of FoxPro (and Clipper),
+    /// so the file version is set to FoxPro when the length exceeds 255.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    ///
+    /// let writer = TableWriterBuilder::new()
+    ///     .add_long_character_field(FieldName::try_from("Comment").unwrap(), 300)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    /// ```
+    pub fn add_long_character_field(mut self, name: FieldName, length: u16) -> Self {
+        let mut info = FieldInfo::new(name, FieldType::Character, 0);
+        info.field_length = length;
+        self.v.push(info);
+        if length > u16::from(u8::MAX) {
+            self.use_fox_pro_version();
+        }
+        self
+    }
+
     /// Adds a [Date](struct.Date.html) field
     pub fn add_date_field(mut self, name: FieldName) -> Self {
         self.v.push(FieldInfo::new(
@@ -141,9 +281,7 @@ impl TableWriterBuilder {
                 .size()
                 .expect("Internal error Integer field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
-        };
+        self.use_fox_pro_version();
         self
     }
 
@@ -156,9 +294,8 @@ impl TableWriterBuilder {
                 .size()
                 .expect("Internal error datetime field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
-        };
+        // DateTime fields are a Visual FoxPro type
+        self.hdr.file_type = Version::VisualFoxPro;
         self
     }
 
@@ -171,9 +308,7 @@ impl TableWriterBuilder {
                 .size()
                 .expect("Internal error Double field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
-        };
+        self.use_fox_pro_version();
         self
     }
 
@@ -186,30 +321,288 @@ impl TableWriterBuilder {
                 .size()
                 .expect("Internal error Currency field date should be known"),
         ));
-        self.hdr.file_type = crate::header::Version::FoxPro2 {
-            supports_memo: false,
-        };
+        self.use_fox_pro_version();
+        self
+    }
+
+    /// Adds a [Memo](enum.FieldValue.html#variant.Memo) field.
+    ///
+    /// The data of the Memo fields is written in a separate memo file,
+    /// see [build_with_dest_and_memo](#method.build_with_dest_and_memo).
+    /// dBase III memo files (.dbt) and FoxPro memo files (.fpt) can be written,
+    /// the type of memo file depends on the version of the table.
+    pub fn add_memo_field(mut self, name: FieldName) -> Self {
+        self.v.push(FieldInfo::new(name, FieldType::Memo, 10));
+        match self.hdr.file_type {
+            Version::DBase3 { .. } => {
+                self.hdr.file_type = Version::DBase3 {
+                    supports_memo: true,
+                }
+            }
+            Version::FoxPro2 { .. } => {
+                self.hdr.file_type = Version::FoxPro2 {
+                    supports_memo: true,
+                }
+            }
+            _ => {}
+        }
         self
     }
+
+    /// Makes the file a FoxPro one, unless it already is a Visual FoxPro one
+    fn use_fox_pro_version(&mut self) {
+        if !self.hdr.file_type.is_visual_fox_pro() {
+            self.hdr.file_type = Version::FoxPro2 {
+                supports_memo: self.v.iter().any(|info| info.field_type == FieldType::Memo),
+            };
+        }
+    }
+
+    /// Sets the last 14 bytes (offsets 18 to 31) of the descriptor of the field named `name`,
+    /// they are zeros by default.
+    ///
+    /// dBase III reserves these bytes, but some programs expect specific values
+    /// in them (e.g. the work area id at offset 20 to be 1).
+    /// In Visual FoxPro files the first 7 of them (offsets 18 to 24) hold the flags
+    /// and the autoincrement of the field, they cannot be changed.
+    ///
+    /// Returns an error if no field added so far has this name, or if the bytes
+    /// would change the flags or the autoincrement of a field of a Visual FoxPro file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut work_area = [0u8; 14];
+    /// work_area[2] = 1;
+    /// let builder = TableWriterBuilder::new()
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 50)
+    ///     .field_descriptor_reserved_bytes("Name", work_area)?;
+    /// assert!(builder
+    ///     .field_descriptor_reserved_bytes("Unknown", work_area)
+    ///     .is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn field_descriptor_reserved_bytes(
+        mut self,
+        name: &str,
+        bytes: [u8; 14],
+    ) -> Result<Self, Error> {
+        let is_visual_fox_pro = self.hdr.file_type.is_visual_fox_pro();
+        let info = self
+            .v
+            .iter_mut()
+            .find(|info| info.name == name)
+            .ok_or_else(|| Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::Message(format!("No field named '{}'", name)),
+            })?;
+        if is_visual_fox_pro && bytes[..7] != info.flags_and_autoincrement_bytes() {
+            return Err(Error {
+                record_num: 0,
+                field: Some(info.clone()),
+                kind: ErrorKind::Message(
+                    "The bytes 18 to 24 of the descriptor hold the flags and the autoincrement of the field"
+                        .to_string(),
+                ),
+            });
+        }
+        info.set_reserved_bytes(bytes);
+        Ok(self)
+    }
+
+    /// Sets whether the writer writes the end of file marker (0x1A)
+    /// after the last record, true by default.
+    ///
+    /// Not writing it is useful to produce fragments of a table
+    /// that will be concatenated later, the last fragment should have the marker.
+    pub fn write_eof_marker(mut self, value: bool) -> Self {
+        self.write_eof_marker = value;
+        self
+    }
+
+    /// Sets the byte used to fill Numeric and Float fields that have no value (None),
+    /// a space by default.
+    ///
+    /// Some programs expect these fields to be filled with `b'*'` or `b'\0'`.
+    pub fn numeric_none_fill_byte(mut self, byte: u8) -> Self {
+        self.numeric_none_fill_byte = byte;
+        self
+    }
+
+    /// Sets whether the Numeric and Float values are padded with zeros
+    /// to the full width of the field (e.g. `00012.50`),
+    /// instead of being preceded by spaces (the default, e.g. `   12.50`).
+    pub fn numeric_zero_padding(mut self, value: bool) -> Self {
+        self.numeric_zero_padding = value;
+        self
+    }
+
+    /// Sets the dialect of the file to write, instead of letting the builder
+    /// choose the version of the file from the types of the fields added.
+    ///
+    /// [validate](#method.validate) then also checks that the fields
+    /// are supported by the dialect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{Dialect, FieldName, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    ///
+    /// let builder = TableWriterBuilder::new()
+    ///     .dialect(Dialect::DbaseIII)
+    ///     .add_integer_field(FieldName::try_from("Count").unwrap());
+    /// // Integer fields are a Visual FoxPro type
+    /// assert!(builder.validate().is_err());
+    /// ```
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = Some(dialect);
+        self
+    }
+
+    /// Sets the version of the header from the dialect, if one was chosen
+    fn apply_dialect(&mut self) {
+        if let Some(dialect) = self.dialect {
+            let has_memo = self.v.iter().any(|info| info.field_type == FieldType::Memo);
+            self.hdr.file_type = dialect.version(has_memo);
+        }
+    }
+
     /// Builds the writer and set the dst as where the file data will be written
     pub fn build_with_dest<W: Write + Seek>(self, dst: W) -> TableWriter<W> {
-        TableWriter::new(dst, self.v, self.hdr)
+        self.build_writer(dst)
+    }
+
+    fn build_writer<W: Write + Seek, M: Write + Seek>(mut self, dst: W) -> TableWriter<W, M> {
+        self.apply_dialect();
+        let mut writer = TableWriter::new(dst, self.v, self.hdr);
+        writer.write_eof_marker = self.write_eof_marker;
+        writer.numeric_none_fill_byte = self.numeric_none_fill_byte;
+        writer.numeric_zero_padding = self.numeric_zero_padding;
+        writer.dialect = self.dialect;
+        writer
+    }
+
+    /// Builds the writer, the data of the Memo fields will be written using the `memo_writer`,
+    /// whose destination does not have to be of the same type as `dst`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, FieldValue, MemoFileType, MemoWriter, Record, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let memo_writer =
+    ///     MemoWriter::new(MemoFileType::DbaseMemo, Cursor::new(Vec::<u8>::new())).unwrap();
+    /// let mut writer = TableWriterBuilder::new()
+    ///     .add_memo_field(FieldName::try_from("Notes").unwrap())
+    ///     .build_with_dest_and_memo(Cursor::new(Vec::<u8>::new()), memo_writer);
+    ///
+    /// let mut record = Record::default();
+    /// record.insert("Notes".to_string(), FieldValue::Memo("Some notes".to_string()));
+    /// writer.write_record(&record)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_with_dest_and_memo<W: Write + Seek, M: Write + Seek>(
+        self,
+        dst: W,
+        memo_writer: MemoWriter<M>,
+    ) -> TableWriter<W, M> {
+        let mut writer = self.build_writer(dst);
+        writer.memo_writer = Some(memo_writer);
+        writer
     }
 
     /// Helper function to set create a file at the given path
     /// and make the writer write to the newly created file.
     ///
+    /// When there are Memo fields, the memo file is created next to it
+    /// (with the .dbt extension for dBase files, and .fpt for FoxPro files).
+    ///
     /// This function wraps the `File` in a `BufWriter` to increase performance.
     pub fn build_with_file_dest<P: AsRef<Path>>(
-        self,
+        mut self,
         path: P,
     ) -> Result<TableWriter<BufWriter<File>>, Error> {
-        let file = File::create(path).map_err(|err| Error::io_error(err, 0))?;
+        self.validate()?;
+        self.apply_dialect();
+        let file = File::create(path.as_ref()).map_err(|err| Error::io_error(err, 0))?;
         let dst = BufWriter::new(file);
-        Ok(self.build_with_dest(dst))
+        if self.v.iter().any(|info| info.field_type == FieldType::Memo) {
+            let memo_type = self
+                .hdr
+                .file_type
+                .supported_memo_type()
+                .unwrap_or(MemoFileType::DbaseMemo);
+            let extension = match memo_type {
+                MemoFileType::FoxBaseMemo => "fpt",
+                MemoFileType::DbaseMemo | MemoFileType::DbaseMemo4 => "dbt",
+            };
+            let memo_writer = File::create(path.as_ref().with_extension(extension))
+                .and_then(|file| MemoWriter::new(memo_type, BufWriter::new(file)))
+                .map_err(|error| Error {
+                    record_num: 0,
+                    field: None,
+                    kind: ErrorKind::ErrorOpeningMemoFile(error),
+                })?;
+            Ok(self.build_with_dest_and_memo(dst, memo_writer))
+        } else {
+            Ok(self.build_with_dest(dst))
+        }
     }
 
-    pub fn build_table_info(self) -> TableInfo {
+    /// Checks that the declared fields respect the limits of the file version
+    ///
+    /// Returns a `FieldNameTooLong` error for a field whose name is longer than 10 bytes,
+    /// an `EmptyField` error for a field of length 0, and a `DuplicateFieldName` error
+    /// if two fields have the same name (case-insensitive).
+    ///
+    /// Returns a `TooManyFields` error if there are more fields than the version allows,
+    /// and a `RecordTooLarge` error if the total size of a record exceeds the maximum.
+    ///
+    /// When a [dialect](#method.dialect) is set, returns an `InvalidFieldType` error
+    /// for fields whose type the dialect does not have, a `FieldTooLong` error
+    /// for fields longer than the dialect allows, and a `Message` error for Memo fields
+    /// with the dBase IV dialect, whose memo files cannot be written.
+    ///
+    /// This check is also done by the writer before the header is written,
+    /// even when there are no records.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    ///
+    /// let mut builder = TableWriterBuilder::new();
+    /// for i in 0..20 {
+    ///     let name = FieldName::try_from(format!("field_{}", i).as_str()).unwrap();
+    ///     builder = builder.add_character_field(name, 255);
+    /// }
+    /// // dBase III records cannot exceed 4000 bytes
+    /// assert!(builder.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), Error> {
+        check_fields(&self.v)?;
+        match self.dialect {
+            Some(dialect) => {
+                dialect.check_fields(&self.v)?;
+                let has_memo = self.v.iter().any(|info| info.field_type == FieldType::Memo);
+                check_version_limits(dialect.version(has_memo), &self.v)
+            }
+            None => check_version_limits(self.hdr.file_type, &self.v),
+        }
+    }
+
+    pub fn build_table_info(mut self) -> TableInfo {
+        self.apply_dialect();
         TableInfo {
             header: self.hdr,
             fields_info: self.v,
@@ -217,11 +610,60 @@ impl TableWriterBuilder {
     }
 }
 
+/// Checks the fields definitions, independently of the file version
+fn check_fields(fields_info: &[FieldInfo]) -> Result<(), Error> {
+    let field_error = |info: &FieldInfo, kind| Error {
+        record_num: 0,
+        field: Some(info.clone()),
+        kind,
+    };
+    for info in fields_info.iter().filter(|info| !info.is_deletion_flag()) {
+        if info.name.len() > FIELD_NAME_MAX_LENGTH {
+            return Err(field_error(info, ErrorKind::FieldNameTooLong));
+        }
+        if info.field_length == 0 {
+            return Err(field_error(info, ErrorKind::EmptyField));
+        }
+    }
+    if let Some(i) = find_duplicate_field_name(fields_info) {
+        let kind = ErrorKind::DuplicateFieldName(fields_info[i].name.clone());
+        return Err(field_error(&fields_info[i], kind));
+    }
+    Ok(())
+}
+
+fn check_version_limits(version: Version, fields_info: &[FieldInfo]) -> Result<(), Error> {
+    if fields_info.len() > version.max_num_fields() {
+        return Err(Error {
+            record_num: 0,
+            field: None,
+            kind: ErrorKind::TooManyFields,
+        });
+    }
+    // +1 for the deletion flag
+    let record_size = 1 + fields_info
+        .iter()
+        .map(|info| info.field_length as usize)
+        .sum::<usize>();
+    if record_size > version.max_record_size() {
+        return Err(Error {
+            record_num: 0,
+            field: None,
+            kind: ErrorKind::RecordTooLarge,
+        });
+    }
+    Ok(())
+}
+
 impl Default for TableWriterBuilder {
     fn default() -> Self {
         Self {
             v: vec![],
             hdr: Header::new(0, 0, 0),
+            write_eof_marker: true,
+            numeric_none_fill_byte: b' ',
+            numeric_zero_padding: false,
+            dialect: None,
         }
     }
 }
@@ -240,15 +682,18 @@ mod private {
     impl_sealed_for!(std::string::String);
     impl_sealed_for!(Option<std::string::String>);
     impl_sealed_for!(&str);
+    impl_sealed_for!(&[u8]);
     impl_sealed_for!(f64);
     impl_sealed_for!(f32);
     impl_sealed_for!(i32);
+    impl_sealed_for!(Option<i32>);
     impl_sealed_for!(Option<f64>);
     impl_sealed_for!(Option<f32>);
     impl_sealed_for!(crate::record::field::Date);
     impl_sealed_for!(Option<crate::record::field::Date>);
     impl_sealed_for!(crate::record::field::FieldValue);
     impl_sealed_for!(crate::record::field::DateTime);
+    impl_sealed_for!(Option<crate::record::field::DateTime>);
 }
 
 /// Trait implemented by types we can write as dBase types
@@ -256,6 +701,11 @@ mod private {
 /// This trait is 'private' and cannot be implemented on your custom types.
 pub trait WritableAsDbaseField: private::Sealed {
     fn write_as<W: Write>(&self, field_type: FieldType, dst: &mut W) -> Result<(), ErrorKind>;
+
+    /// Whether the value is the absence of a value (a `None`)
+    fn is_null(&self) -> bool {
+        false
+    }
 }
 
 /// Trait to be implemented by struct that you want to be able to write to (serialize)
@@ -289,6 +739,49 @@ impl WritableRecord for Record {
     }
 }
 
+/// Record whose fields missing in the map are written empty
+struct RecordMap<'a>(&'a HashMap<String, FieldValue>);
+
+impl<'a> WritableRecord for RecordMap<'a> {
+    fn write_using<'b, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'b, W>,
+    ) -> Result<(), FieldIOError> {
+        while let Some(name) = field_writer.next_field_name() {
+            match self.0.get(name) {
+                Some(value) => field_writer.write_next_field_value(value)?,
+                None => field_writer.write_next_field_empty()?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The record at `index` of columns, written with the values of the columns at `index`
+struct ColumnsRecord<'a> {
+    columns: &'a Columns,
+    index: usize,
+}
+
+impl<'a> WritableRecord for ColumnsRecord<'a> {
+    fn write_using<'b, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'b, W>,
+    ) -> Result<(), FieldIOError> {
+        while let Some(name) = field_writer.next_field_name() {
+            match self
+                .columns
+                .get(name)
+                .and_then(|column| column.get(self.index))
+            {
+                Some(value) => field_writer.write_next_field_value(&value)?,
+                None => field_writer.write_next_field_empty()?,
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Struct that knows how to write a record
 ///
 /// You give it the values you want to write and it writes them.
@@ -298,12 +791,83 @@ pub struct FieldWriter<'a, W: Write> {
     pub(crate) dst: &'a mut W,
     pub(crate) fields_info: std::iter::Peekable<std::slice::Iter<'a, FieldInfo>>,
     pub(crate) buffer: &'a mut Cursor<Vec<u8>>,
+    /// Byte used to fill Numeric & Float fields that have no value
+    pub(crate) numeric_none_fill_byte: u8,
+    /// Whether Numeric & Float values are padded with leading zeros
+    pub(crate) numeric_zero_padding: bool,
+    /// Where the data of Memo fields is written, if the table has a memo file
+    pub(crate) memo_writer: Option<&'a mut MemoWriter<dyn Write + 'a>>,
+    /// The bits of the _NullFlags field of the record, set as the fields are written
+    pub(crate) null_flags: Vec<u8>,
+    /// Whether the _NullFlags field was already written, its bits can no longer be set
+    pub(crate) null_flags_written: bool,
 }
 
 impl<'a, W: Write> FieldWriter<'a, W> {
     /// Returns the name of the next field that is expected to be written
     pub fn next_field_name(&mut self) -> Option<&'a str> {
-        self.fields_info.peek().map(|info| info.name.as_str())
+        self.next_field_info().map(|info| info.name.as_str())
+    }
+
+    /// Returns the next field whose value is expected,
+    /// the hidden fields (e.g. _NullFlags) are written by the writer itself
+    pub(crate) fn next_field_info(&self) -> Option<&'a FieldInfo> {
+        self.fields_info.clone().find(|info| !info.is_hidden())
+    }
+
+    /// Writes the hidden fields that come before the next field whose value is expected
+    pub(crate) fn write_hidden_fields(&mut self) -> Result<(), FieldIOError> {
+        while let Some(field_info) = self.fields_info.next_if(|info| info.is_hidden()) {
+            let mut bytes = vec![0u8; usize::from(field_info.field_length)];
+            if field_info.field_type == FieldType::NullFlags {
+                let len = self.null_flags.len().min(bytes.len());
+                bytes[..len].copy_from_slice(&self.null_flags[..len]);
+                self.null_flags_written = true;
+            }
+            self.dst.write_all(&bytes).map_err(|error| {
+                FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Sets the `bit` of the _NullFlags field of the record,
+    /// the field has no such bit when `bit` is `None`
+    fn set_null_flags_bit(
+        &mut self,
+        field_info: &FieldInfo,
+        bit: Option<usize>,
+    ) -> Result<(), FieldIOError> {
+        let bit = bit.filter(|_| !self.null_flags_written).ok_or_else(|| {
+            FieldIOError::new(ErrorKind::NoNullFlagsBit, Some(field_info.clone()))
+        })?;
+        if self.null_flags.len() <= bit / 8 {
+            self.null_flags.resize(bit / 8 + 1, 0);
+        }
+        self.null_flags[bit / 8] |= 1 << (bit % 8);
+        Ok(())
+    }
+
+    /// Writes the Varchar or Varbinary value that is in the buffer.
+    ///
+    /// A value shorter than the field is padded with zeros, its length is stored
+    /// in the last byte of the field and its varlength bit of the _NullFlags field is set.
+    fn write_variable_length_value(
+        &mut self,
+        field_info: &FieldInfo,
+        is_null: bool,
+    ) -> Result<(), FieldIOError> {
+        let field_length = usize::from(field_info.field_length);
+        let length = (self.buffer.position() as usize).min(field_length);
+        let mut bytes = self.buffer.get_ref()[..length].to_vec();
+        bytes.resize(field_length, 0);
+        if !is_null && length < field_length {
+            self.set_null_flags_bit(field_info, field_info.varlength_bit)?;
+            bytes[field_length - 1] = length as u8;
+        }
+        self.dst
+            .write_all(&bytes)
+            .map_err(|error| FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone())))
     }
 
     /// Writes the given `field_value` to the record.
@@ -322,6 +886,7 @@ impl<'a, W: Write> FieldWriter<'a, W> {
         &mut self,
         field_value: &T,
     ) -> Result<(), FieldIOError> {
+        self.write_hidden_fields()?;
         if let Some(field_info) = self.fields_info.next() {
             self.buffer.set_position(0);
 
@@ -329,32 +894,86 @@ impl<'a, W: Write> FieldWriter<'a, W> {
                 .write_as(field_info.field_type, &mut self.buffer)
                 .map_err(|kind| FieldIOError::new(kind, Some(field_info.clone())))?;
 
+            let is_null = field_value.is_null();
+            let is_variable_length = matches!(
+                field_info.field_type,
+                FieldType::Varchar | FieldType::Varbinary
+            );
+            // Binary and variable length values have no other way to tell they are null,
+            // fields that are not nullable cannot hold them
+            let needs_null_bit = is_variable_length
+                || matches!(
+                    field_info.field_type,
+                    FieldType::Integer
+                        | FieldType::Double
+                        | FieldType::Currency
+                        | FieldType::DateTime
+                );
+            if is_null && (needs_null_bit || field_info.null_bit.is_some()) {
+                self.set_null_flags_bit(field_info, field_info.null_bit)?;
+            }
+            if is_variable_length {
+                return self.write_variable_length_value(field_info, is_null);
+            }
+
+            if field_info.field_type.uses_memo_file() && self.buffer.position() > 0 {
+                self.replace_memo_data_by_index(field_info)
+                    .map_err(|kind| FieldIOError::new(kind, Some(field_info.clone())))?;
+            } else if field_info.field_type.uses_memo_file() && field_info.field_length == 4 {
+                // No data, the binary index is 0 (spaces would be read as an index)
+                self.buffer.write_u32::<LittleEndian>(0).map_err(|error| {
+                    FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
+                })?;
+            }
+
+            let is_numeric = field_info.field_type == FieldType::Float
+                || field_info.field_type == FieldType::Numeric;
+            if is_numeric && self.buffer.position() > 0 {
+                self.round_numeric_value(field_info).map_err(|error| {
+                    FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
+                })?;
+            }
+
             let mut bytes_written = self.buffer.position();
+            let is_overflow = is_numeric && bytes_written > u64::from(field_info.field_length);
+            if is_numeric && (bytes_written == 0 || is_overflow) {
+                // None values fill the whole field with the fill byte,
+                // values that do not fit with `*` (like dBase does)
+                let fill_byte = if is_overflow {
+                    b'*'
+                } else {
+                    self.numeric_none_fill_byte
+                };
+                self.buffer.set_position(0);
+                for _ in 0..field_info.field_length {
+                    self.buffer.write_u8(fill_byte).map_err(|error| {
+                        FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
+                    })?;
+                }
+                bytes_written = self.buffer.position();
+            }
             let mut bytes_to_pad = i64::from(field_info.field_length) - bytes_written as i64;
             if bytes_to_pad > 0 {
-                if field_info.field_type == FieldType::Float
-                    || field_info.field_type == FieldType::Numeric
-                {
-                    // Depending on the locale, the dot might not be the delimiter for floating point
-                    // but we are not yet ready to handle correctly codepages, etc
-                    let mut maybe_dot_pos = self.buffer.get_ref().iter().position(|b| *b == b'.');
-                    if maybe_dot_pos.is_none() {
-                        write!(self.buffer, ".").map_err(|error| {
-                            FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
-                        })?;
-                        bytes_written = self.buffer.position();
-                        maybe_dot_pos = Some(bytes_written as usize)
-                    }
-                    let dot_pos = maybe_dot_pos.unwrap();
-                    let missing_decimals =
-                        field_info.num_decimal_places - (bytes_written - dot_pos as u64) as u8;
-                    for _ in 0..missing_decimals {
-                        write!(self.buffer, "0").map_err(|error| {
+                if is_numeric {
+                    // Numbers are right aligned
+                    let value = self.buffer.get_ref()[..bytes_written as usize].to_vec();
+                    let (sign, digits) = match value.split_first() {
+                        Some((b'-', digits)) if self.numeric_zero_padding => (&value[..1], digits),
+                        _ => (&value[..0], &value[..]),
+                    };
+                    let pad_byte = if self.numeric_zero_padding {
+                        b'0'
+                    } else {
+                        b' '
+                    };
+                    self.buffer.set_position(0);
+                    let padding = vec![pad_byte; bytes_to_pad as usize];
+                    for part in &[sign, &padding, digits] {
+                        self.buffer.write_all(part).map_err(|error| {
                             FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
                         })?;
                     }
-                    bytes_written = self.buffer.position();
-                    bytes_to_pad = i64::from(field_info.field_length) - bytes_written as i64;
+                    bytes_to_pad = 0;
                 }
                 for _ in 0..bytes_to_pad {
                     write!(self.buffer, " ").map_err(|error| {
@@ -372,7 +991,7 @@ impl<'a, W: Write> FieldWriter<'a, W> {
                 // The current field value size exceeds the one one set
                 // when creating the writer, we just crop
                 let field_bytes = self.buffer.get_ref();
-                debug_assert_eq!(self.buffer.position(), field_info.field_length as u64);
+                debug_assert!(self.buffer.position() >= field_info.field_length as u64);
                 self.dst
                     .write_all(&field_bytes[..field_info.field_length as usize])
                     .map_err(|error| {
@@ -387,6 +1006,7 @@ impl<'a, W: Write> FieldWriter<'a, W> {
 
     #[cfg(feature = "serde")]
     pub(crate) fn write_next_field_raw(&mut self, value: &[u8]) -> Result<(), FieldIOError> {
+        self.write_hidden_fields()?;
         if let Some(field_info) = self.fields_info.next() {
             if value.len() == field_info.field_length as usize {
                 self.dst.write_all(value).map_err(|error| {
@@ -396,7 +1016,7 @@ impl<'a, W: Write> FieldWriter<'a, W> {
                 self.dst.write_all(value).map_err(|error| {
                     FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
                 })?;
-                for _ in 0..field_info.field_length - value.len() as u8 {
+                for _ in 0..field_info.field_length - value.len() as u16 {
                     write!(self.dst, " ").map_err(|error| {
                         FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
                     })?;
@@ -414,6 +1034,75 @@ impl<'a, W: Write> FieldWriter<'a, W> {
         }
     }
 
+    /// Rounds the number that is in the buffer to the number of decimals of the field
+    fn round_numeric_value(&mut self, field_info: &FieldInfo) -> std::io::Result<()> {
+        let length = self.buffer.position() as usize;
+        let rounded = rounded_numeric_text(&self.buffer.get_ref()[..length], field_info);
+
+        self.buffer.set_position(0);
+        self.buffer.write_all(rounded.as_bytes())
+    }
+
+    /// Writes the memo data that is in the buffer to the memo file,
+    /// and replaces it by the index of the block where it was written
+    fn replace_memo_data_by_index(&mut self, field_info: &FieldInfo) -> Result<(), ErrorKind> {
+        let memo_writer = self
+            .memo_writer
+            .as_mut()
+            .ok_or(ErrorKind::MissingMemoFile)?;
+        let length = self.buffer.position() as usize;
+        let block_type = match field_info.field_type {
+            FieldType::General | FieldType::Picture => FOX_BASE_MEMO_PICTURE_TYPE,
+            _ => FOX_BASE_MEMO_TEXT_TYPE,
+        };
+        let index =
+            memo_writer.write_data_with_type(&self.buffer.get_ref()[..length], block_type)?;
+
+        self.buffer.set_position(0);
+        if field_info.field_length > 4 {
+            write!(
+                self.buffer,
+                "{:>width$}",
+                index,
+                width = field_info.field_length as usize
+            )?;
+        } else {
+            self.buffer.write_u32::<LittleEndian>(index)?;
+        }
+        Ok(())
+    }
+
+    /// Writes an empty value for the next field: zeros for the fields stored in binary,
+    /// the fill byte for Numeric & Float fields and spaces for the other ones.
+    fn write_next_field_empty(&mut self) -> Result<(), FieldIOError> {
+        self.write_hidden_fields()?;
+        let field_info = self
+            .fields_info
+            .next()
+            .ok_or_else(|| FieldIOError::new(ErrorKind::TooManyFields, None))?;
+        let byte = match field_info.field_type {
+            FieldType::Integer
+            | FieldType::Double
+            | FieldType::Currency
+            | FieldType::DateTime
+            | FieldType::Varbinary
+            | FieldType::NullFlags => 0u8,
+            FieldType::Memo | FieldType::General | FieldType::Picture
+                if field_info.field_length == 4 =>
+            {
+                0u8
+            }
+            FieldType::Numeric | FieldType::Float => self.numeric_none_fill_byte,
+            _ => b' ',
+        };
+        for _ in 0..field_info.field_length {
+            self.dst.write_u8(byte).map_err(|error| {
+                FieldIOError::new(ErrorKind::IoError(error), Some(field_info.clone()))
+            })?;
+        }
+        Ok(())
+    }
+
     fn write_deletion_flag(&mut self) -> std::io::Result<()> {
         self.dst.write_u8(b' ')
     }
@@ -423,11 +1112,82 @@ impl<'a, W: Write> FieldWriter<'a, W> {
     }
 }
 
+/// Returns the text of a Numeric or Float value as it is written in its field,
+/// rounded to the number of decimals of the field (before being padded)
+fn rounded_numeric_text(text: &[u8], field_info: &FieldInfo) -> String {
+    round_to_decimals(
+        &String::from_utf8_lossy(text),
+        field_info.num_decimal_places as usize,
+    )
+}
+
+/// Rounds the decimal number written in `text` to `num_decimals` decimals,
+/// half away from zero (e.g. `12.345` gives `12.35` and `-12.345` gives `-12.35`),
+/// or pads it with zeros if it has fewer decimals.
+///
+/// The rounding is done on the text, not on the binary value, `12.345`
+/// is rounded as written even though its closest `f64` is slightly below it.
+///
+/// Texts that are not plain decimal numbers (e.g. `inf`) are returned as is.
+fn round_to_decimals(text: &str, num_decimals: usize) -> String {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, text),
+    };
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+    let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if int_part.is_empty() || !is_digits(int_part) || !is_digits(frac_part) {
+        return text.to_owned();
+    }
+
+    let mut kept = int_part
+        .bytes()
+        .chain(
+            frac_part
+                .bytes()
+                .chain(std::iter::repeat(b'0'))
+                .take(num_decimals),
+        )
+        .collect::<Vec<u8>>();
+    if frac_part
+        .as_bytes()
+        .get(num_decimals)
+        .is_some_and(|digit| *digit >= b'5')
+    {
+        let mut carry = true;
+        for digit in kept.iter_mut().rev() {
+            if *digit == b'9' {
+                *digit = b'0';
+            } else {
+                *digit += 1;
+                carry = false;
+                break;
+            }
+        }
+        if carry {
+            kept.insert(0, b'1');
+        }
+    }
+
+    let mut rounded = String::with_capacity(kept.len() + 2);
+    // No "-0.00"
+    if negative && kept.iter().any(|digit| *digit != b'0') {
+        rounded.push('-');
+    }
+    let int_len = kept.len() - num_decimals;
+    rounded.extend(kept[..int_len].iter().map(|b| *b as char));
+    if num_decimals > 0 {
+        rounded.push('.');
+        rounded.extend(kept[int_len..].iter().map(|b| *b as char));
+    }
+    rounded
+}
+
 /// Structs that writes dBase records to a destination
 ///
 /// The only way to create a TableWriter is to use its
 /// [TableWriterBuilder](struct.TableWriterBuilder.html)
-pub struct TableWriter<W: Write + Seek> {
+pub struct TableWriter<W: Write + Seek, M: Write + Seek = W> {
     dst: W,
     fields_info: Vec<FieldInfo>,
     /// contains the header of the input file
@@ -435,20 +1195,45 @@ pub struct TableWriter<W: Write + Seek> {
     header: Header,
     /// Buffer used by the FieldWriter
     buffer: Cursor<Vec<u8>>,
+    write_eof_marker: bool,
+    numeric_none_fill_byte: u8,
+    numeric_zero_padding: bool,
+    /// Where the data of the Memo fields is written
+    memo_writer: Option<MemoWriter<M>>,
+    /// Whether the records are added to an existing table,
+    /// whose header and field descriptors are already written
+    appending: bool,
+    /// The dialect chosen with the builder, whose limits the fields must respect
+    dialect: Option<Dialect>,
     closed: bool,
 }
 
-impl<W: Write + Seek> TableWriter<W> {
+impl<W: Write + Seek, M: Write + Seek> TableWriter<W, M> {
     fn new(dst: W, fields_info: Vec<FieldInfo>, origin_header: Header) -> Self {
         Self {
             dst,
             fields_info,
             header: origin_header,
             buffer: Cursor::new(vec![0u8; 255]),
+            write_eof_marker: true,
+            numeric_none_fill_byte: b' ',
+            numeric_zero_padding: false,
+            memo_writer: None,
+            appending: false,
+            dialect: None,
             closed: false,
         }
     }
 
+    /// Checks the fields before the header is written
+    fn check_table(&self) -> Result<(), Error> {
+        check_fields(&self.fields_info)?;
+        if let Some(dialect) = self.dialect {
+            dialect.check_fields(&self.fields_info)?;
+        }
+        check_version_limits(self.header.file_type, &self.fields_info)
+    }
+
     /// Writes a record the inner destination
     ///
     /// # Example
@@ -471,36 +1256,129 @@ impl<W: Write + Seek> TableWriter<W> {
     /// # }
     /// ```
     pub fn write_record<R: WritableRecord>(&mut self, record: &R) -> Result<(), Error> {
-        if self.header.num_records == 0 {
+        if self.header.num_records == 0 && !self.appending {
+            self.check_table()?;
             // reserve the header
             self.write_header()?;
         }
 
-        let mut field_writer = FieldWriter {
-            dst: &mut self.dst,
-            fields_info: self.fields_info.iter().peekable(),
-            buffer: &mut self.buffer,
-        };
-
         let current_record_num = self.header.num_records as usize;
+        if self.appending {
+            // The record is written in one go once complete, so that a record that fails
+            // does not leave some of its bytes at the end of the file
+            let mut record_bytes = Vec::with_capacity(self.header.size_of_record as usize);
+            let mut field_writer = FieldWriter {
+                dst: &mut record_bytes,
+                fields_info: self.fields_info.iter().peekable(),
+                buffer: &mut self.buffer,
+                numeric_none_fill_byte: self.numeric_none_fill_byte,
+                numeric_zero_padding: self.numeric_zero_padding,
+                memo_writer: None,
+                null_flags: Vec::new(),
+                null_flags_written: false,
+            };
+            write_record_using(&mut field_writer, record, current_record_num)?;
+            self.dst
+                .write_all(&record_bytes)
+                .map_err(|error| Error::io_error(error, current_record_num))?;
+        } else {
+            let mut field_writer = FieldWriter {
+                dst: &mut self.dst,
+                fields_info: self.fields_info.iter().peekable(),
+                buffer: &mut self.buffer,
+                numeric_none_fill_byte: self.numeric_none_fill_byte,
+                numeric_zero_padding: self.numeric_zero_padding,
+                memo_writer: self
+                    .memo_writer
+                    .as_mut()
+                    .map(|memo_writer| memo_writer as &mut MemoWriter<dyn Write>),
+                null_flags: Vec::new(),
+                null_flags_written: false,
+            };
+            write_record_using(&mut field_writer, record, current_record_num)?;
+        }
 
-        field_writer
-            .write_deletion_flag()
-            .map_err(|error| Error::io_error(error, current_record_num))?;
+        self.header.num_records += 1;
+        Ok(())
+    }
 
-        record
-            .write_using(&mut field_writer)
-            .map_err(|error| Error::new(error, current_record_num))?;
+    /// Writes a record given as a map of field names to values.
+    ///
+    /// The fields of the table that are not in the map are written empty
+    /// (`None` for the types that can represent it, zero for binary types),
+    /// the values of the map must have the type of their field.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, FieldValue, TableWriterBuilder};
+    /// use std::collections::HashMap;
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut writer = TableWriterBuilder::new()
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 50)
+    ///     .add_numeric_field(FieldName::try_from("Age").unwrap(), 3, 0)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    ///
+    /// let mut map = HashMap::new();
+    /// map.insert("Name".to_string(), FieldValue::Character(Some("Yoshi".to_string())));
+    /// writer.write_record_map(&map)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_record_map(&mut self, map: &HashMap<String, FieldValue>) -> Result<(), Error> {
+        self.write_record(&RecordMap(map))
+    }
 
-        if !field_writer.all_fields_were_written() {
+    /// Writes the records held in a columnar layout:
+    /// the record `i` is made of the value `i` of each column.
+    ///
+    /// The columns are matched to the fields by name, and like with
+    /// [write_record_map](#method.write_record_map) the fields without a column are written empty.
+    ///
+    /// Returns an error with the
+    /// [ColumnLengthMismatch](enum.ErrorKind.html#variant.ColumnLengthMismatch) kind,
+    /// before writing anything, if the columns do not all have the same number of values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{Column, Columns, FieldName, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let mut writer = TableWriterBuilder::new()
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 50)
+    ///     .add_numeric_field(FieldName::try_from("Age").unwrap(), 3, 0)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    ///
+    /// let mut columns = Columns::default();
+    /// columns.insert("Name".to_owned(), Column::Character(vec![Some("Yoshi".to_owned()), None]));
+    /// columns.insert("Age".to_owned(), Column::Numeric(vec![Some(32.0), Some(7.0)]));
+    /// writer.write_columnar(&columns)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_columnar(&mut self, columns: &Columns) -> Result<(), Error> {
+        let num_records = columns.num_records();
+        if let Some((name, column)) = columns
+            .iter()
+            .find(|(_, column)| column.len() != num_records)
+        {
             return Err(Error {
-                record_num: current_record_num,
+                record_num: 0,
                 field: None,
-                kind: ErrorKind::NotEnoughFields,
+                kind: ErrorKind::ColumnLengthMismatch {
+                    field_name: name.to_owned(),
+                    expected: num_records as u32,
+                    actual: column.len() as u32,
+                },
             });
         }
-
-        self.header.num_records += 1;
+        for index in 0..num_records {
+            self.write_record(&ColumnsRecord { columns, index })?;
+        }
         Ok(())
     }
 
@@ -547,6 +1425,32 @@ impl<W: Write + Seek> TableWriter<W> {
         Ok(())
     }
 
+    /// Finishes writing the table: the header is updated, the end of file marker is written,
+    /// the header of the memo file (if any) is updated, and the destinations are flushed.
+    ///
+    /// This is also done when the writer is dropped, but errors are then ignored.
+    ///
+    /// Both the table and the memo file are finished even if one of them fails,
+    /// the first error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldName, TableWriterBuilder};
+    /// use std::convert::TryFrom;
+    /// use std::io::Cursor;
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// let writer = TableWriterBuilder::new()
+    ///     .add_character_field(FieldName::try_from("Name").unwrap(), 50)
+    ///     .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    /// writer.finish()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn finish(mut self) -> Result<(), Error> {
+        self.close()
+    }
+
     /// Close the writer
     ///
     /// Automatically closed when the writer is dropped,
@@ -554,33 +1458,83 @@ impl<W: Write + Seek> TableWriter<W> {
     ///
     /// Calling close on an already closed writer is a no-op
     fn close(&mut self) -> Result<(), Error> {
-        if !self.closed {
-            self.dst
-                .seek(SeekFrom::Start(0))
-                .map_err(|error| Error::io_error(error, self.header.num_records as usize))?;
-            self.update_header();
+        if self.closed {
+            return Ok(());
+        }
+        // Whatever happens, closing is not retried
+        self.closed = true;
+        let table_result = self.close_table();
+        let memo_result = match self.memo_writer.as_mut() {
+            Some(memo_writer) => memo_writer
+                .finish()
+                .map_err(|error| Error::io_error(error, self.header.num_records as usize)),
+            None => Ok(()),
+        };
+        table_result.and(memo_result)
+    }
+
+    fn close_table(&mut self) -> Result<(), Error> {
+        self.dst
+            .seek(SeekFrom::Start(0))
+            .map_err(|error| Error::io_error(error, self.header.num_records as usize))?;
+        if self.appending {
+            // Only the record count and the date changed
+            self.header
+                .write_to(&mut self.dst)
+                .map_err(|error| Error::io_error(error, 0))?;
+        } else {
+            if self.header.num_records == 0 {
+                // The checks were not done by write_record
+                self.check_table()?;
+            }
+            self.update_header()?;
             self.write_header()?;
-            self.dst
-                .seek(SeekFrom::End(0))
-                .map_err(|error| Error::io_error(error, self.header.num_records as usize))?;
+        }
+        let end = if self.appending {
+            // The marker goes right after the records, replacing the previous one
+            SeekFrom::Start(
+                u64::from(self.header.offset_to_first_record)
+                    + u64::from(self.header.num_records) * u64::from(self.header.size_of_record),
+            )
+        } else {
+            SeekFrom::End(0)
+        };
+        self.dst
+            .seek(end)
+            .map_err(|error| Error::io_error(error, self.header.num_records as usize))?;
+        if self.write_eof_marker {
             self.dst
                 .write_u8(FILE_TERMINATOR)
                 .map_err(|error| Error::io_error(error, self.header.num_records as usize))?;
-            self.closed = true;
         }
-        Ok(())
+        self.dst
+            .flush()
+            .map_err(|error| Error::io_error(error, self.header.num_records as usize))
     }
 
-    fn update_header(&mut self) {
-        let offset_to_first_record =
+    fn update_header(&mut self) -> Result<(), Error> {
+        let error = |kind| Error {
+            record_num: self.header.num_records as usize,
+            field: None,
+            kind,
+        };
+        let mut offset_to_first_record =
             Header::SIZE + (self.fields_info.len() * FieldInfo::SIZE) + std::mem::size_of::<u8>();
+        if self.header.file_type.is_visual_fox_pro() {
+            offset_to_first_record += BACKLINK_SIZE as usize;
+        }
+        let offset_to_first_record =
+            u16::try_from(offset_to_first_record).map_err(|_| error(ErrorKind::TooManyFields))?;
+        // The deletion flag is part of the record
         let size_of_record = self
             .fields_info
             .iter()
-            .fold(0u16, |s, ref info| s + info.field_length as u16);
+            .try_fold(1u16, |s, info| s.checked_add(info.field_length))
+            .ok_or_else(|| error(ErrorKind::RecordTooLarge))?;
 
-        self.header.offset_to_first_record = offset_to_first_record as u16;
+        self.header.offset_to_first_record = offset_to_first_record;
         self.header.size_of_record = size_of_record;
+        Ok(())
     }
 
     fn write_header(&mut self) -> Result<(), Error> {
@@ -594,12 +1548,452 @@ impl<W: Write + Seek> TableWriter<W> {
         }
         self.dst
             .write_u8(TERMINATOR_VALUE)
-            .map_err(|error| Error::io_error(error, 0))
+            .map_err(|error| Error::io_error(error, 0))?;
+        if self.header.file_type.is_visual_fox_pro() {
+            // No database container
+            self.dst
+                .write_all(&[0u8; BACKLINK_SIZE as usize])
+                .map_err(|error| Error::io_error(error, 0))?;
+        }
+        Ok(())
     }
 }
 
-impl<T: Write + Seek> Drop for TableWriter<T> {
+impl TableWriter<BufWriter<File>> {
+    /// Opens an existing file to add records after the ones it has.
+    ///
+    /// The fields are read from the file, the records written must match them.
+    /// When the writer is closed, the record count and the date of last update
+    /// of the header are updated, and the end of file marker is written again.
+    ///
+    /// The data of Memo fields cannot be appended, as the memo file is not opened.
+    ///
+    /// The length of the file must match the number of records of its header
+    /// (with or without the end of file marker), otherwise a `FileLengthMismatch`
+    /// error is returned and the file is left untouched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dbase::{FieldValue, Record, TableWriter};
+    /// # fn main() -> Result<(), dbase::Error> {
+    /// # std::fs::copy("tests/data/line.dbf", "appended.dbf").unwrap();
+    /// let mut writer = TableWriter::append("appended.dbf")?;
+    /// let mut record = Record::default();
+    /// record.insert("name".to_owned(), FieldValue::Character(Some("new line".to_owned())));
+    /// writer.write_record(&record)?;
+    /// writer.finish()?;
+    ///
+    /// let records = dbase::read("appended.dbf")?;
+    /// assert_eq!(records.len(), 2);
+    /// # std::fs::remove_file("appended.dbf").unwrap();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn append<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|error| Error::io_error(error, 0))?;
+        let table_info = Reader::new(&mut file)?.into_table_info();
+        let mut header = table_info.header;
+        let mut fields_info = table_info.fields_info;
+        if fields_info.first().is_some_and(FieldInfo::is_deletion_flag) {
+            fields_info.remove(0);
+        }
+
+        // The header must agree with the file, otherwise the new records
+        // would be written past the end of the file or over existing records
+        let end_of_records = u64::from(header.offset_to_first_record)
+            + u64::from(header.num_records) * u64::from(header.size_of_record);
+        let file_len = file
+            .metadata()
+            .map_err(|error| Error::io_error(error, header.num_records as usize))?
+            .len();
+        let has_eof_marker = file_len == end_of_records + 1 && {
+            let mut marker = [0u8; 1];
+            file.seek(SeekFrom::Start(end_of_records))
+                .and_then(|_| file.read_exact(&mut marker))
+                .map_err(|error| Error::io_error(error, header.num_records as usize))?;
+            marker[0] == FILE_TERMINATOR
+        };
+        if file_len != end_of_records && !has_eof_marker {
+            return Err(Error {
+                record_num: header.num_records as usize,
+                field: None,
+                kind: ErrorKind::FileLengthMismatch {
+                    expected: end_of_records,
+                    actual: file_len,
+                },
+            });
+        }
+        // The first new record replaces the end of file marker
+        file.seek(SeekFrom::Start(end_of_records))
+            .map_err(|error| Error::io_error(error, header.num_records as usize))?;
+        header.update_date();
+
+        let mut writer = Self::new(BufWriter::new(file), fields_info, header);
+        writer.appending = true;
+        Ok(writer)
+    }
+}
+
+/// Writes the deletion flag and the fields of the `record` using the `field_writer`
+fn write_record_using<W: Write, R: WritableRecord>(
+    field_writer: &mut FieldWriter<W>,
+    record: &R,
+    current_record_num: usize,
+) -> Result<(), Error> {
+    field_writer
+        .write_deletion_flag()
+        .map_err(|error| Error::io_error(error, current_record_num))?;
+
+    record
+        .write_using(field_writer)
+        .map_err(|error| Error::new(error, current_record_num))?;
+
+    field_writer
+        .write_hidden_fields()
+        .map_err(|error| Error::new(error, current_record_num))?;
+    if !field_writer.all_fields_were_written() {
+        return Err(Error {
+            record_num: current_record_num,
+            field: None,
+            kind: ErrorKind::NotEnoughFields,
+        });
+    }
+    Ok(())
+}
+
+impl<T: Write + Seek, M: Write + Seek> Drop for TableWriter<T, M> {
     fn drop(&mut self) {
         let _ = self.close();
     }
 }
+
+/// Checks that the `record` can be written in a table with the given fields,
+/// without writing anything.
+///
+/// The record must have a value for each field (and no other value),
+/// each value must have the type of its field, and must fit in the field's length
+/// (values that are too long would be truncated by the writer).
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+/// let mut record = reader.read()?.remove(0);
+/// dbase::validate_record(&record, reader.fields())?;
+///
+/// record.insert("name".to_owned(), dbase::FieldValue::Character(Some("x".repeat(100))));
+/// assert!(dbase::validate_record(&record, reader.fields()).is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub fn validate_record(record: &Record, schema: &[FieldInfo]) -> Result<(), Error> {
+    let error = |kind, field: Option<&FieldInfo>| Error {
+        record_num: 0,
+        field: field.cloned(),
+        kind,
+    };
+
+    let num_fields = schema.iter().filter(|info| !info.is_hidden()).count();
+    let num_values = record.as_ref().len();
+    if num_values < num_fields {
+        return Err(error(ErrorKind::NotEnoughFields, None));
+    } else if num_values > num_fields {
+        return Err(error(ErrorKind::TooManyFields, None));
+    }
+
+    let mut buffer = Vec::<u8>::new();
+    for info in schema.iter().filter(|info| !info.is_hidden()) {
+        let value = record
+            .get(info.name())
+            .ok_or_else(|| error(ErrorKind::NotEnoughFields, Some(info)))?;
+        if value.field_type() != info.field_type {
+            return Err(error(ErrorKind::IncompatibleType, Some(info)));
+        }
+        if let FieldValue::Memo(_) = value {
+            continue;
+        }
+        buffer.clear();
+        value
+            .write_as(info.field_type, &mut buffer)
+            .map_err(|kind| error(kind, Some(info)))?;
+        let length = match info.field_type {
+            // Like the writer does, the numbers are rounded before checking their width
+            FieldType::Numeric | FieldType::Float if !buffer.is_empty() => {
+                rounded_numeric_text(&buffer, info).len()
+            }
+            _ => buffer.len(),
+        };
+        if length > info.field_length as usize {
+            return Err(error(ErrorKind::ValueTooLong, Some(info)));
+        }
+    }
+    Ok(())
+}
+
+/// Builds a [Record](struct.Record.html) of a schema field by field
+///
+/// The fields that are not set are filled with their blank value when
+/// [build](#method.build) is called. Integer, Double, Currency and DateTime fields
+/// are stored in binary and cannot be blank: they are required,
+/// unless they are nullable (Visual FoxPro).
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), dbase::Error> {
+/// let reader = dbase::Reader::from_path("tests/data/line.dbf")?;
+/// let record = dbase::RecordBuilder::new(reader.fields())
+///     .set("name", "Elm street".to_owned())
+///     .build()?;
+/// assert_eq!(
+///     record.get("name"),
+///     Some(&dbase::FieldValue::Character(Some("Elm street".to_owned())))
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct RecordBuilder<'a> {
+    schema: &'a [FieldInfo],
+    record: Record,
+}
+
+impl<'a> RecordBuilder<'a> {
+    /// Creates a builder for records of the given fields
+    pub fn new(schema: &'a [FieldInfo]) -> Self {
+        Self {
+            schema,
+            record: Record::default(),
+        }
+    }
+
+    /// Sets the value of a field, replacing the previous value
+    pub fn set<V: Into<FieldValue>>(mut self, name: &str, value: V) -> Self {
+        self.record.insert(name.to_owned(), value.into());
+        self
+    }
+
+    /// Fills the fields that were not set with their blank value,
+    /// and validates the record (see [validate_record](fn.validate_record.html))
+    ///
+    /// Returns an error with the `NotEnoughFields` kind if a required field was not set.
+    pub fn build(mut self) -> Result<Record, Error> {
+        for info in self.schema.iter().filter(|info| !info.is_hidden()) {
+            if self.record.get(info.name()).is_some() {
+                continue;
+            }
+            let is_binary = matches!(
+                info.field_type,
+                FieldType::Integer | FieldType::Double | FieldType::Currency | FieldType::DateTime
+            );
+            let value = match info.field_type {
+                FieldType::Memo => Some(FieldValue::Memo(String::new())),
+                FieldType::General => Some(FieldValue::General(Vec::new())),
+                FieldType::Picture => Some(FieldValue::Picture(Vec::new())),
+                FieldType::Varchar if !info.is_nullable() => {
+                    Some(FieldValue::Varchar(Some(String::new())))
+                }
+                FieldType::Varbinary if !info.is_nullable() => {
+                    Some(FieldValue::Varbinary(Some(Vec::new())))
+                }
+                _ if is_binary && !info.is_nullable() => None,
+                field_type => FieldValue::null_value(field_type),
+            }
+            .ok_or_else(|| Error {
+                record_num: 0,
+                field: Some(info.clone()),
+                kind: ErrorKind::NotEnoughFields,
+            })?;
+            self.record.insert(info.name().to_owned(), value);
+        }
+        validate_record(&self.record, self.schema)?;
+        Ok(self.record)
+    }
+}
+
+/// Writes all the records of the `tables` one after the other into `dst`
+///
+/// All the tables must have the same fields (same names, types and lengths),
+/// the header of the first table is used as the base for the new header.
+///
+/// Only the records that are not marked as deleted are written, whatever the
+/// [ReadingOptions::include_deleted](struct.ReadingOptions.html#method.include_deleted)
+/// option of the tables: the writer cannot mark records as deleted, deleted records
+/// would come back as live ones.
+///
+/// Tables with fields stored in a memo file (Memo, General or Picture) are rejected
+/// before anything is written to `dst`, use [concat_with_memo](fn.concat_with_memo.html)
+/// to concatenate them.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut tables = vec![
+///     dbase::Reader::from_path("tests/data/stations.dbf")?,
+///     dbase::Reader::from_path("tests/data/stations.dbf")?,
+/// ];
+/// let mut dst = Cursor::new(Vec::<u8>::new());
+/// dbase::concat(&mut tables, &mut dst)?;
+///
+/// dst.set_position(0);
+/// let mut reader = dbase::Reader::new(dst)?;
+/// assert_eq!(reader.read()?.len(), 12);
+/// # Ok(())
+/// # }
+/// ```
+pub fn concat<T: Read + Seek, W: Write + Seek>(
+    tables: &mut [Reader<T>],
+    dst: W,
+) -> Result<(), Error> {
+    let first = check_same_fields(tables)?;
+    if let Some(memo_field) = first
+        .fields()
+        .iter()
+        .find(|info| info.field_type.uses_memo_file())
+    {
+        return Err(Error {
+            record_num: 0,
+            field: Some(memo_field.clone()),
+            kind: ErrorKind::Message(
+                "Tables with fields stored in a memo file (Memo, General, Picture) must be concatenated with concat_with_memo"
+                    .to_string(),
+            ),
+        });
+    }
+
+    let mut writer = TableWriterBuilder::from_table_info(first.table_info()).build_with_dest(dst);
+    for table in tables.iter_mut() {
+        table.seek(0)?;
+        for record in table.iter_records_with::<Record>(DeletedRecords::Skip) {
+            writer.write_record(&record?)?;
+        }
+    }
+    writer.close()
+}
+
+/// Writes all the records of the `tables` one after the other into `dst`,
+/// and the data of their memo fields into `memo_dst`.
+///
+/// Like [concat](fn.concat.html), all the tables must have the same fields
+/// and the records marked as deleted are not written.
+/// The records are copied as they are, only the block indices of their memo fields
+/// are changed to where their data is written in `memo_dst`.
+/// The memo file has the type of the one of the first table.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// # fn main() -> Result<(), dbase::Error> {
+/// let mut tables = vec![
+///     dbase::Reader::from_path("tests/data/shared_memo.dbf")?,
+///     dbase::Reader::from_path("tests/data/shared_memo.dbf")?,
+/// ];
+/// let mut dbf = Cursor::new(Vec::<u8>::new());
+/// let mut fpt = Cursor::new(Vec::<u8>::new());
+/// dbase::concat_with_memo(&mut tables, &mut dbf, &mut fpt)?;
+///
+/// dbf.set_position(0);
+/// fpt.set_position(0);
+/// let mut reader = dbase::Reader::new_with_memo(dbf, fpt, dbase::MemoFileType::FoxBaseMemo)?;
+/// let records = reader.read()?;
+/// assert_eq!(records.len(), 2 * dbase::read("tests/data/shared_memo.dbf")?.len());
+/// # Ok(())
+/// # }
+/// ```
+pub fn concat_with_memo<T: Read + Seek, W: Write + Seek, M: Write + Seek>(
+    tables: &mut [Reader<T>],
+    mut dst: W,
+    memo_dst: M,
+) -> Result<(), Error> {
+    let first = check_same_fields(tables)?;
+    // The records are copied as they are
+    if tables
+        .iter()
+        .any(|table| table.header().size_of_record != first.header().size_of_record)
+    {
+        return Err(Error {
+            record_num: 0,
+            field: None,
+            kind: ErrorKind::Message(
+                "The records of the tables do not have the same size".to_string(),
+            ),
+        });
+    }
+    let memo_type = first.memo_file_type().ok_or(Error {
+        record_num: 0,
+        field: None,
+        kind: ErrorKind::MissingMemoFile,
+    })?;
+    let mut header = *first.header();
+    let mut memo_writer =
+        MemoWriter::new(memo_type, memo_dst).map_err(|error| Error::io_error(error, 0))?;
+
+    // The header is written again once the records are counted
+    let header_bytes = tables[0].read_raw_header()?;
+    dst.write_all(&header_bytes)
+        .map_err(|error| Error::io_error(error, 0))?;
+    let mut num_records = 0u32;
+    for table in tables.iter_mut() {
+        num_records +=
+            table.copy_records_with_memo(&mut dst, &mut memo_writer, DeletedRecords::Skip)?;
+    }
+    let num_records_usize = num_records as usize;
+    dst.write_u8(FILE_TERMINATOR)
+        .map_err(|error| Error::io_error(error, num_records_usize))?;
+
+    header.num_records = num_records;
+    header.update_date();
+    dst.seek(SeekFrom::Start(0))
+        .and_then(|_| header.write_to(&mut dst))
+        .and_then(|_| dst.flush())
+        .map_err(|error| Error::io_error(error, num_records_usize))?;
+    memo_writer
+        .finish()
+        .map_err(|error| Error::io_error(error, num_records_usize))
+}
+
+/// Returns the first of the tables, checking that all the tables have its fields
+fn check_same_fields<T: Read + Seek>(tables: &[Reader<T>]) -> Result<&Reader<T>, Error> {
+    let first = match tables.first() {
+        Some(first) => first,
+        None => {
+            return Err(Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::Message("No tables to concatenate".to_string()),
+            })
+        }
+    };
+
+    let same_schema = |a: &FieldInfo, b: &FieldInfo| {
+        a.name == b.name
+            && a.field_type == b.field_type
+            && a.field_length == b.field_length
+            && a.num_decimal_places == b.num_decimal_places
+    };
+    for table in tables.iter().skip(1) {
+        if table.fields().len() != first.fields().len()
+            || !table
+                .fields()
+                .iter()
+                .zip(first.fields())
+                .all(|(a, b)| same_schema(a, b))
+        {
+            return Err(Error {
+                record_num: 0,
+                field: None,
+                kind: ErrorKind::Message("The tables do not have the same fields".to_string()),
+            });
+        }
+    }
+
+    Ok(first)
+}
@@ -1,6 +1,9 @@
 extern crate dbase;
 #[cfg(feature = "serde")]
 extern crate serde_derive;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_json;
 
 #[cfg(feature = "serde")]
 mod serde_tests {
@@ -42,7 +45,7 @@ mod serde_tests {
         let records = vec![DeserializableRecord {
             name: "Holy Fawn".to_string(),
             price: 10.2,
-            date: dbase::Date::new(01, 01, 2012),
+            date: dbase::Date::new(1, 1, 2012),
             available: true,
             score: 79.87,
         }];
@@ -52,7 +55,7 @@ mod serde_tests {
             .add_numeric_field(FieldName::try_from("price").unwrap(), 7, 4)
             .add_date_field(FieldName::try_from("date").unwrap())
             .add_logical_field(FieldName::try_from("available").unwrap())
-            .add_float_field(FieldName::try_from("score").unwrap(), 7, 5);
+            .add_float_field(FieldName::try_from("score").unwrap(), 8, 5);
 
         write_read_compare(&records, writer_builder);
     }
@@ -102,14 +105,8 @@ mod serde_tests {
             .add_date_field(FieldName::try_from("date").unwrap());
 
         let records = vec![
-            Record {
-                0: true,
-                1: dbase::Date::new(12, 10, 2012),
-            },
-            Record {
-                0: false,
-                1: dbase::Date::new(12, 11, 2005),
-            },
+            Record(true, dbase::Date::new(12, 10, 2012)),
+            Record(false, dbase::Date::new(12, 11, 2005)),
         ];
         write_read_compare(&records, writer_builder);
     }
@@ -137,16 +134,13 @@ mod serde_tests {
 
         let writer = TableWriterBuilder::new()
             .add_logical_field(FieldName::try_from("yes").unwrap())
-            .add_character_field(FieldName::try_from("not present").unwrap(), 50)
+            .add_character_field(FieldName::try_from("absent").unwrap(), 50)
             .build_with_dest(Cursor::new(Vec::<u8>::new()));
 
         let error = writer
             .write_records(&records)
             .expect_err("We expected an Error");
-        match error.kind() {
-            ErrorKind::NotEnoughFields => assert!(true),
-            _ => assert!(false),
-        }
+        assert!(matches!(error.kind(), ErrorKind::NotEnoughFields));
     }
 
     #[test]
@@ -164,10 +158,11 @@ mod serde_tests {
             .write_records(&records)
             .expect_err("Expected an error");
 
-        match error.kind() {
-            ErrorKind::TooManyFields => assert!(true),
-            kind => assert!(false, "The kind is not the expected one: {}", kind),
-        }
+        assert!(
+            matches!(error.kind(), ErrorKind::TooManyFields),
+            "The kind is not the expected one: {}",
+            error.kind()
+        );
     }
 
     #[test]
@@ -188,14 +183,85 @@ mod serde_tests {
 
         let records = vec![Record {
             datetime: dbase::DateTime::new(
-                dbase::Date::new(12, 05, 2130),
+                dbase::Date::new(12, 5, 2130),
                 dbase::Time::new(15, 52, 12),
             ),
-            currency: 79841.156846,
+            currency: 79841.1568,
             double: 976114.1846,
             integer: -15315,
         }];
 
         write_read_compare(&records, writer_builder);
     }
+
+    #[test]
+    fn test_field_value_to_json() {
+        use dbase::{Date, DateTime, FieldValue, Time};
+        use serde_json::Value;
+
+        let values = vec![
+            (
+                FieldValue::Character(Some("Yoshi".to_string())),
+                json!("Yoshi"),
+            ),
+            (FieldValue::Character(None), Value::Null),
+            (FieldValue::Numeric(Some(12.5)), json!(12.5)),
+            (FieldValue::Numeric(None), Value::Null),
+            (FieldValue::Float(Some(12.345)), json!(12.345)),
+            (FieldValue::Float(None), Value::Null),
+            (FieldValue::Logical(Some(true)), json!(true)),
+            (FieldValue::Logical(None), Value::Null),
+            (
+                FieldValue::Date(Some(Date::new(5, 3, 2021))),
+                json!("2021-03-05"),
+            ),
+            (FieldValue::Date(None), Value::Null),
+            (FieldValue::Integer(Some(-42)), json!(-42)),
+            (FieldValue::Currency(Some(3.25)), json!(3.25)),
+            (FieldValue::Double(Some(f64::NAN)), Value::Null),
+            (
+                FieldValue::DateTime(Some(DateTime::new(
+                    Date::new(5, 3, 2021),
+                    Time::new(13, 4, 9),
+                ))),
+                json!("2021-03-05T13:04:09"),
+            ),
+            (
+                FieldValue::Memo("Some notes".to_string()),
+                json!("Some notes"),
+            ),
+        ];
+        for (value, expected) in values {
+            assert_eq!(Value::from(&value), expected, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn test_to_ndjson() {
+        use serde_json::Value;
+
+        let mut ndjson = Vec::<u8>::new();
+        let mut reader = Reader::from_path("tests/data/stations.dbf").unwrap();
+        reader.to_ndjson(&mut ndjson).unwrap();
+
+        let records = Reader::from_path("tests/data/stations.dbf")
+            .unwrap()
+            .read()
+            .unwrap();
+        let text = String::from_utf8(ndjson).unwrap();
+        assert!(text.ends_with('\n'));
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), records.len());
+        for (line, record) in lines.iter().zip(&records) {
+            let object = match serde_json::from_str::<Value>(line).unwrap() {
+                Value::Object(object) => object,
+                value => panic!("Unexpected JSON value {}", value),
+            };
+            assert_eq!(object.len(), record.as_ref().len());
+            for (name, value) in record.as_ref() {
+                assert_eq!(object[name], Value::from(value), "{}", name);
+            }
+        }
+        assert!(lines[0].contains("\"name\":\"Van Dorn Street\""));
+    }
 }
@@ -1,18 +1,60 @@
 #[macro_use]
 extern crate dbase;
+#[cfg(feature = "chrono")]
+extern crate chrono;
 
 use std::io::{Cursor, Read, Seek, Write};
 
 use dbase::{
-    Date, DateTime, FieldIOError, FieldIterator, FieldName, FieldValue, FieldWriter,
-    ReadableRecord, Reader, Record, TableWriterBuilder, Time, WritableRecord,
+    Column, Date, DateTime, DeletionFlag, Dialect, DuplicateFieldNames, Encoding, ErrorKind,
+    FieldIOError, FieldIterator, FieldName, FieldNameNormalization, FieldType, FieldValue,
+    FieldWriter, LanguageDriver, MemoFileType, MemoReader, MemoWriter, NdxIndex, NdxKeyType,
+    ReadableRecord, Reader, ReadingOptions, Record, RecordBuilder, TableWriter, TableWriterBuilder,
+    Time, TrimMode, Version, WritableRecord,
 };
 use std::convert::{TryFrom, TryInto};
 use std::fmt::Debug;
 
 const LINE_DBF: &str = "./tests/data/line.dbf";
+const STATIONS_DBF: &str = "./tests/data/stations.dbf";
+const PEOPLE_DBF: &str = "./tests/data/people.dbf";
+const PEOPLE_NAME_NDX: &str = "./tests/data/people_name.ndx";
+const PEOPLE_AGE_NDX: &str = "./tests/data/people_age.ndx";
 const NONE_FLOAT_DBF: &str = "./tests/data/contain_none_float.dbf";
 const NULL_PADDED_NUMERIC_DBF: &str = "./tests/data/contain_null_padded_numeric.dbf";
+const BINARY_JULIAN_DATE_DBF: &str = "./tests/data/binary_julian_date.dbf";
+const VFP_NO_CODE_PAGE_DBF: &str = "./tests/data/visual_fox_pro_no_code_page.dbf";
+const LOWERCASE_FIELD_TYPES_DBF: &str = "./tests/data/lowercase_field_types.dbf";
+const BIG_ENDIAN_BINARY_FIELDS_DBF: &str = "./tests/data/big_endian_binary_fields.dbf";
+const BOGUS_MEMO_LENGTH_DBF: &str = "./tests/data/bogus_memo_length.dbf";
+const DUPLICATE_FIELD_NAMES_DBF: &str = "./tests/data/duplicate_field_names.dbf";
+const SHIFTED_DATETIME_DBF: &str = "./tests/data/shifted_datetime.dbf";
+const DBASE3_MEMO_DBF: &str = "./tests/data/dbase3_memo.dbf";
+const INDEXED_DBF: &str = "./tests/data/indexed.dbf";
+const SHARED_MEMO_DBF: &str = "./tests/data/shared_memo.dbf";
+const SHARED_MEMO_FPT: &str = "./tests/data/shared_memo.fpt";
+const ZERO_BLOCK_SIZE_FPT: &str = "./tests/data/zero_block_size.fpt";
+const FIELD_OVERRUNS_RECORD_DBF: &str = "./tests/data/field_overruns_record.dbf";
+const PADDED_RECORDS_DBF: &str = "./tests/data/padded_records.dbf";
+const NULL_FLAGS_DBF: &str = "./tests/data/null_flags.dbf";
+const NULL_BINARY_FIELDS_DBF: &str = "./tests/data/null_binary_fields.dbf";
+const VARCHAR_VARBINARY_DBF: &str = "./tests/data/varchar_varbinary.dbf";
+const PADDED_FIELD_NAME_DBF: &str = "./tests/data/padded_field_name.dbf";
+const INDEXED_FIELD_DBF: &str = "./tests/data/indexed_field.dbf";
+const CP1252_NO_CODE_PAGE_DBF: &str = "./tests/data/cp1252_no_code_page.dbf";
+const LONG_NUMERIC_DBF: &str = "./tests/data/long_numeric.dbf";
+const DELETED_RECORDS_DBF: &str = "./tests/data/deleted_records.dbf";
+const INFLATED_RECORD_COUNT_DBF: &str = "./tests/data/inflated_record_count.dbf";
+const LEADING_SPACES_DBF: &str = "./tests/data/leading_spaces.dbf";
+const CP850_DBF: &str = "./tests/data/cp850.dbf";
+const CP1250_DBF: &str = "./tests/data/cp1250.dbf";
+const NO_FIELDS_DBF: &str = "./tests/data/no_fields.dbf";
+const GENERAL_DBF: &str = "./tests/data/general.dbf";
+const PICTURE_DBF: &str = "./tests/data/picture.dbf";
+const PRODUCTION_MDX_DBF: &str = "./tests/data/production_mdx.dbf";
+const PENDING_TRANSACTION_DBF: &str = "./tests/data/pending_transaction.dbf";
+const TRUNCATED_MEMO_DBF: &str = "./tests/data/truncated_memo.dbf";
+const ZERO_DELETION_FLAG_DBF: &str = "./tests/data/zero_deletion_flag.dbf";
 
 fn write_read_compare<R: WritableRecord + ReadableRecord + Debug + PartialEq>(
     records: &Vec<R>,
@@ -30,6 +72,31 @@ fn write_read_compare<R: WritableRecord + ReadableRecord + Debug + PartialEq>(
     assert_eq!(&read_records, records);
 }
 
+/// Writes a table without records, the returned cursor is at its start
+fn write_empty_table(writer_builder: TableWriterBuilder) -> Cursor<Vec<u8>> {
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    writer_builder
+        .build_with_dest(&mut dst)
+        .write_records(&Vec::<Record>::new())
+        .unwrap();
+    dst.set_position(0);
+    dst
+}
+
+/// Returns the offset of the first record of the written table
+fn offset_to_first_record(bytes: &[u8]) -> usize {
+    let reader = Reader::new(Cursor::new(bytes)).unwrap();
+    usize::from(reader.header().offset_to_first_record)
+}
+
+/// Returns the fields of the table the builder writes
+fn empty_table_fields(writer_builder: TableWriterBuilder) -> Vec<dbase::FieldInfo> {
+    Reader::new(write_empty_table(writer_builder))
+        .unwrap()
+        .fields()
+        .to_vec()
+}
+
 #[test]
 fn test_none_float() {
     let records = dbase::read(NONE_FLOAT_DBF).unwrap();
@@ -94,6 +161,186 @@ fn test_read_numeric_value_null_padded() {
     assert_eq!(records[0], expected_fields);
 }
 
+#[test]
+fn test_read_binary_julian_date() {
+    let records = dbase::read(BINARY_JULIAN_DATE_DBF).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[0].get("date"),
+        Some(&FieldValue::Date(Some(Date::new(20, 7, 2019))))
+    );
+    assert_eq!(
+        records[0].get("name"),
+        Some(&FieldValue::Character(Some("alpha".to_owned())))
+    );
+    assert_eq!(records[1].get("date"), Some(&FieldValue::Date(None)));
+}
+
+#[test]
+fn test_read_out_of_range_julian_day_number() {
+    let first_record = |bytes: &[u8]| {
+        let reader = Reader::new(Cursor::new(bytes.to_vec())).unwrap();
+        usize::from(reader.header().offset_to_first_record)
+    };
+    let read_error = |bytes: Vec<u8>| Reader::new(Cursor::new(bytes)).unwrap().read().unwrap_err();
+
+    let mut date_bytes = std::fs::read(BINARY_JULIAN_DATE_DBF).unwrap();
+    // After the deletion flag and the 10 bytes of the name
+    let date_start = first_record(&date_bytes) + 11;
+
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let mut record = Record::default();
+    let stamp = DateTime::new(Date::new(1, 6, 2006), Time::new(12, 50, 20));
+    record.insert("Stamp".to_owned(), FieldValue::DateTime(Some(stamp)));
+    TableWriterBuilder::new()
+        .add_datetime_field("Stamp".try_into().unwrap())
+        .build_with_dest(&mut cursor)
+        .write_records(&[record])
+        .unwrap();
+    let mut datetime_bytes = cursor.into_inner();
+    let datetime_start = first_record(&datetime_bytes) + 1;
+
+    for &jdn in &[i32::MAX, i32::MIN, -1, 1] {
+        date_bytes[date_start..date_start + 4].copy_from_slice(&jdn.to_le_bytes());
+        let error = read_error(date_bytes.clone());
+        assert!(matches!(error.kind(), ErrorKind::InvalidDate), "{}", jdn);
+
+        datetime_bytes[datetime_start..datetime_start + 4].copy_from_slice(&jdn.to_le_bytes());
+        let error = read_error(datetime_bytes.clone());
+        assert!(matches!(error.kind(), ErrorKind::InvalidDate), "{}", jdn);
+    }
+}
+
+#[test]
+fn test_code_page_inferred_from_version() {
+    let mut reader = Reader::from_path(VFP_NO_CODE_PAGE_DBF).unwrap();
+    assert_eq!(reader.header().code_page_mark, 0);
+    assert_eq!(reader.header().code_page(), Some(1252));
+
+    let records = reader.read().unwrap();
+    assert_eq!(
+        records[0].get("name"),
+        Some(&FieldValue::Character(Some("foxpro".to_owned())))
+    );
+
+    // dBase III has no default code page
+    let reader = Reader::from_path(LINE_DBF).unwrap();
+    assert_eq!(reader.header().code_page(), None);
+}
+
+#[test]
+fn test_lowercase_field_types() {
+    let mut reader = Reader::from_path(LOWERCASE_FIELD_TYPES_DBF).unwrap();
+    let records = reader.read().unwrap();
+    assert_eq!(
+        records[0].get("name"),
+        Some(&FieldValue::Character(Some("lower".to_owned())))
+    );
+    assert_eq!(
+        records[0].get("value"),
+        Some(&FieldValue::Numeric(Some(12.5)))
+    );
+
+    let options = ReadingOptions::default().strict_field_types(true);
+    let error = Reader::from_path_with_options(LOWERCASE_FIELD_TYPES_DBF, options).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::InvalidFieldType('c')
+    ));
+}
+
+#[test]
+fn test_big_endian_binary_fields() {
+    let options = ReadingOptions::default().big_endian_binary_fields(true);
+    let mut reader = Reader::from_path_with_options(BIG_ENDIAN_BINARY_FIELDS_DBF, options).unwrap();
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[0].get("integer"),
+        Some(&FieldValue::Integer(Some(17)))
+    );
+    assert_eq!(
+        records[0].get("double"),
+        Some(&FieldValue::Double(Some(54621.154)))
+    );
+    assert_eq!(
+        records[1].get("integer"),
+        Some(&FieldValue::Integer(Some(-42069)))
+    );
+    assert_eq!(
+        records[1].get("double"),
+        Some(&FieldValue::Double(Some(-1.5)))
+    );
+
+    let records = dbase::read(BIG_ENDIAN_BINARY_FIELDS_DBF).unwrap();
+    assert_ne!(
+        records[0].get("integer"),
+        Some(&FieldValue::Integer(Some(17)))
+    );
+}
+
+#[test]
+fn test_duplicate_field_names() {
+    let options = ReadingOptions::default().duplicate_field_names(DuplicateFieldNames::Error);
+    let error = Reader::from_path_with_options(DUPLICATE_FIELD_NAMES_DBF, options).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::DuplicateFieldName(name) if name == "amount"
+    ));
+
+    let options = ReadingOptions::default().duplicate_field_names(DuplicateFieldNames::Rename);
+    let mut reader = Reader::from_path_with_options(DUPLICATE_FIELD_NAMES_DBF, options).unwrap();
+    assert_eq!(
+        reader.renamed_fields(),
+        &[("amount".to_owned(), "amount_2".to_owned())]
+    );
+    let records = reader.read().unwrap();
+    assert_eq!(
+        records[0].get("AMOUNT"),
+        Some(&FieldValue::Numeric(Some(1.0)))
+    );
+    assert_eq!(
+        records[0].get("amount_2"),
+        Some(&FieldValue::Numeric(Some(2.0)))
+    );
+    assert_eq!(
+        records[0].get("AMOUNT_1"),
+        Some(&FieldValue::Numeric(Some(3.0)))
+    );
+
+    let reader = Reader::from_path(DUPLICATE_FIELD_NAMES_DBF).unwrap();
+    assert!(reader.renamed_fields().is_empty());
+}
+
+#[test]
+fn test_record_set() {
+    let mut records = dbase::read(LINE_DBF).unwrap();
+    let record = &mut records[0];
+
+    record
+        .set(
+            "name",
+            FieldValue::Character(Some("linestring2".to_owned())),
+        )
+        .unwrap();
+    assert_eq!(
+        record.get("name"),
+        Some(&FieldValue::Character(Some("linestring2".to_owned())))
+    );
+
+    let error = record
+        .set("name", FieldValue::Numeric(Some(1.0)))
+        .unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IncompatibleType));
+    assert!(record
+        .set("not_a_field", FieldValue::Character(None))
+        .is_err());
+    assert_eq!(
+        record.get("name"),
+        Some(&FieldValue::Character(Some("linestring2".to_owned())))
+    );
+}
+
 #[derive(Debug, PartialEq, Clone)]
 struct Album {
     artist: String,
@@ -170,15 +417,18 @@ fn from_scratch_fox_pro_record() {
         .add_datetime_field(FieldName::try_from("datetime").unwrap());
 
     let mut record = Record::default();
-    record.insert(String::from("integer"), FieldValue::Integer(17));
-    record.insert(String::from("double"), FieldValue::Double(54621.154));
-    record.insert(String::from("currency"), FieldValue::Currency(4567.134));
+    record.insert(String::from("integer"), FieldValue::Integer(Some(17)));
+    record.insert(String::from("double"), FieldValue::Double(Some(54621.154)));
+    record.insert(
+        String::from("currency"),
+        FieldValue::Currency(Some(4567.134)),
+    );
     record.insert(
         String::from("datetime"),
-        FieldValue::DateTime(DateTime::new(
-            Date::new(01, 06, 2006),
+        FieldValue::DateTime(Some(DateTime::new(
+            Date::new(1, 6, 2006),
             Time::new(12, 50, 20),
-        )),
+        ))),
     );
 
     let records = vec![record];
@@ -204,9 +454,9 @@ fn from_scratch_fox_pro_struct_record() {
         .add_integer_field(FieldName::try_from("integer").unwrap());
 
     let records = vec![FoxProRecord {
-        datetime: DateTime::new(Date::new(12, 02, 1999), Time::new(21, 20, 35)),
+        datetime: DateTime::new(Date::new(12, 2, 1999), Time::new(21, 20, 35)),
         double: 8649.48851,
-        currency: 3489.9612314,
+        currency: 3489.9612,
         integer: 42069,
     }];
 
@@ -259,3 +509,3100 @@ fn the_classical_user_record_example() {
 
     assert_eq!(read_records, users);
 }
+
+#[test]
+fn test_concat_tables() {
+    let stations = dbase::read(STATIONS_DBF).unwrap();
+
+    let mut tables = vec![
+        Reader::from_path(STATIONS_DBF).unwrap(),
+        Reader::from_path(STATIONS_DBF).unwrap(),
+    ];
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    dbase::concat(&mut tables, &mut dst).unwrap();
+    dst.set_position(0);
+
+    let mut reader = Reader::new(dst).unwrap();
+    assert_eq!(reader.header().num_records as usize, 2 * stations.len());
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 2 * stations.len());
+    assert_eq!(&records[..stations.len()], &stations[..]);
+    assert_eq!(&records[stations.len()..], &stations[..]);
+}
+
+#[test]
+fn test_concat_tables_with_different_fields() {
+    let mut tables = vec![
+        Reader::from_path(STATIONS_DBF).unwrap(),
+        Reader::from_path(LINE_DBF).unwrap(),
+    ];
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    assert!(dbase::concat(&mut tables, &mut dst).is_err());
+}
+
+#[test]
+fn test_concat_tables_with_memo_fields() {
+    let mut tables = vec![
+        Reader::from_path(GENERAL_DBF).unwrap(),
+        Reader::from_path(GENERAL_DBF).unwrap(),
+    ];
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    let error = dbase::concat(&mut tables, &mut dst).unwrap_err();
+    assert_eq!(
+        error.field().as_ref().unwrap().field_type(),
+        FieldType::General
+    );
+    assert!(dst.get_ref().is_empty());
+
+    for path in &[SHARED_MEMO_DBF, GENERAL_DBF] {
+        let mut tables = vec![
+            Reader::from_path(path).unwrap(),
+            Reader::from_path(path).unwrap(),
+        ];
+        let mut dbf = Cursor::new(Vec::<u8>::new());
+        let mut fpt = Cursor::new(Vec::<u8>::new());
+        dbase::concat_with_memo(&mut tables, &mut dbf, &mut fpt).unwrap();
+
+        let records = Reader::from_path(path).unwrap().read().unwrap();
+        dbf.set_position(0);
+        fpt.set_position(0);
+        let mut reader = Reader::new_with_memo(dbf, fpt, MemoFileType::FoxBaseMemo).unwrap();
+        assert_eq!(reader.header().num_records as usize, 2 * records.len());
+        let concatenated = reader.read().unwrap();
+        assert_eq!(&concatenated[..records.len()], &records[..]);
+        assert_eq!(&concatenated[records.len()..], &records[..]);
+    }
+}
+
+#[test]
+fn test_dbase3_record_too_large() {
+    let mut builder = TableWriterBuilder::new();
+    for i in 0..16 {
+        let name = format!("field_{}", i);
+        builder = builder.add_character_field(FieldName::try_from(name.as_str()).unwrap(), 255);
+    }
+    // 1 + 16 * 255 = 4081 bytes, more than dBase III allows
+    let error = builder.validate().unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::RecordTooLarge));
+
+    let mut writer = builder.build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.write_record(&Record::default()).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::RecordTooLarge));
+}
+
+#[test]
+fn test_dbase3_too_many_fields() {
+    let mut builder = TableWriterBuilder::new();
+    for i in 0..129 {
+        let name = format!("field_{}", i);
+        builder = builder.add_logical_field(FieldName::try_from(name.as_str()).unwrap());
+    }
+    let error = builder.validate().unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::TooManyFields));
+}
+
+#[test]
+fn test_validate_field_definitions() {
+    let name = |name: &str| FieldName::try_from(name).unwrap();
+    let builder = TableWriterBuilder::new()
+        .add_character_field(name("Name"), 20)
+        .add_numeric_field(name("Age"), 3, 0)
+        .add_date_field(name("Birthday"))
+        .add_logical_field(name("Available"))
+        .add_memo_field(name("Notes"));
+    builder.validate().unwrap();
+
+    // 11 bytes, the name would not be null-terminated
+    assert!(FieldName::try_from("Description").is_err());
+    assert!(FieldName::try_from("Descriptio").is_ok());
+
+    let error = TableWriterBuilder::new()
+        .add_character_field(name("Name"), 20)
+        .add_numeric_field(name("Age"), 0, 0)
+        .validate()
+        .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::EmptyField));
+    assert_eq!(error.field().as_ref().unwrap().name(), "Age");
+
+    let error = TableWriterBuilder::new()
+        .add_character_field(name("Name"), 20)
+        .add_character_field(name("NAME"), 10)
+        .validate()
+        .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::DuplicateFieldName(name) if name == "NAME"));
+
+    // The check is also done before writing
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field(name("Name"), 0)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.write_record(&Record::default()).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::EmptyField));
+}
+
+#[test]
+fn test_julian_day_number_round_trip() {
+    let date = Date::new(24, 10, 2014);
+    let jdn = date.to_julian_day_number();
+    assert_eq!(Date::from_julian_day_number(jdn).unwrap(), date);
+
+    assert_eq!(
+        Date::from_julian_day_number(1_721_426).unwrap(),
+        Date::new(1, 1, 1)
+    );
+    let error = Date::from_julian_day_number(0).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidDate));
+    assert!(Date::from_julian_day_number(i32::MAX).is_err());
+
+    // The year 0 is accepted by Date, and must not underflow
+    let date = "00000101".parse::<Date>().unwrap();
+    assert_eq!(date.to_julian_day_number(), 1_721_060);
+    assert_eq!(Date::new(1, 3, 0).to_julian_day_number(), 1_721_120);
+    assert_eq!(date.days_between(&Date::new(1, 1, 1)), 366);
+    assert!(date.add_days(1).is_err());
+}
+
+#[test]
+fn test_record_typed_fields() {
+    let mut reader = Reader::from_path(STATIONS_DBF).unwrap();
+    let records = reader.read().unwrap();
+
+    let typed_fields = records[0].typed_fields(reader.fields()).collect::<Vec<_>>();
+    let names = typed_fields
+        .iter()
+        .map(|(info, _)| info.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["name", "marker-col", "marker-sym", "line"]);
+    for (info, value) in typed_fields {
+        assert_eq!(info.field_type(), value.field_type());
+        assert_eq!(records[0].get(info.name()), Some(value));
+    }
+}
+
+#[test]
+fn test_concat_fragments_without_eof_marker() {
+    let write_fragment = |users: &[User]| {
+        let mut cursor = Cursor::new(Vec::<u8>::new());
+        let writer = TableWriterBuilder::new()
+            .add_character_field("First Name".try_into().unwrap(), 50)
+            .add_character_field("Last Name".try_into().unwrap(), 50)
+            .write_eof_marker(false)
+            .build_with_dest(&mut cursor);
+        writer.write_records(users).unwrap();
+        cursor.into_inner()
+    };
+
+    let users = vec![
+        User {
+            first_name: "Ferrys".to_string(),
+            last_name: "Rust".to_string(),
+        },
+        User {
+            first_name: "Alex".to_string(),
+            last_name: "Rider".to_string(),
+        },
+        User {
+            first_name: "Jamie".to_string(),
+            last_name: "Oliver".to_string(),
+        },
+    ];
+
+    let first_fragment = write_fragment(&users[..2]);
+    let second_fragment = write_fragment(&users[2..]);
+    assert_ne!(first_fragment.last(), Some(&0x1A));
+
+    // Keep the header of the first fragment, only the records of the second one
+    let header_size = u16::from_le_bytes([first_fragment[8], first_fragment[9]]) as usize;
+    let mut combined = first_fragment;
+    combined.extend_from_slice(&second_fragment[header_size..]);
+    combined.push(0x1A);
+    combined[4..8].copy_from_slice(&(users.len() as u32).to_le_bytes());
+
+    let mut reader = Reader::new(Cursor::new(combined)).unwrap();
+    let read_users = reader.read_as::<User>().unwrap();
+    assert_eq!(read_users, users);
+}
+
+#[test]
+fn test_written_record_size_counts_deletion_flag() {
+    let bytes = write_empty_table(
+        TableWriterBuilder::new()
+            .add_character_field("Name".try_into().unwrap(), 10)
+            .add_numeric_field("Age".try_into().unwrap(), 3, 0),
+    )
+    .into_inner();
+    assert_eq!(&bytes[10..12], &(1u16 + 10 + 3).to_le_bytes());
+}
+
+#[test]
+fn test_check_fields_without_records() {
+    // The fields are checked even when no record is written
+    let writer = TableWriterBuilder::new()
+        .dialect(Dialect::DbaseIII)
+        .add_float_field("Height".try_into().unwrap(), 5, 2)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.finish().unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidFieldType('F')));
+
+    let builder = (0..300).fold(TableWriterBuilder::new(), |builder, i| {
+        builder.add_character_field(format!("F{}", i).as_str().try_into().unwrap(), 1)
+    });
+    let error = builder
+        .build_with_dest(Cursor::new(Vec::<u8>::new()))
+        .finish()
+        .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::TooManyFields));
+}
+
+#[test]
+fn test_written_record_size_overflow() {
+    // The size of the record does not fit in its u16
+    let builder = TableWriterBuilder::new()
+        .add_long_character_field("Text1".try_into().unwrap(), 40000)
+        .add_long_character_field("Text2".try_into().unwrap(), 40000);
+    let writer = builder.build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.finish().unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::RecordTooLarge));
+}
+
+#[test]
+fn test_count_where_eq() {
+    let mut reader = Reader::from_path(STATIONS_DBF).unwrap();
+    let blue = FieldValue::Character(Some("blue".to_owned()));
+    let red = FieldValue::Character(Some("red".to_owned()));
+    assert_eq!(reader.count_where_eq("line", &blue).unwrap(), 4);
+    assert_eq!(reader.count_where_eq("line", &red).unwrap(), 2);
+    assert!(reader.count_where_eq("not_a_field", &red).is_err());
+
+    // The reader can still read the records after counting
+    assert_eq!(reader.read().unwrap().len(), 6);
+}
+
+#[test]
+fn test_count_where_eq_on_written_file() {
+    let users = vec![
+        User {
+            first_name: "Alex".to_string(),
+            last_name: "Rider".to_string(),
+        },
+        User {
+            first_name: "Jamie".to_string(),
+            last_name: "Oliver".to_string(),
+        },
+    ];
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::new()
+        .add_character_field("First Name".try_into().unwrap(), 50)
+        .add_character_field("Last Name".try_into().unwrap(), 50)
+        .build_with_dest(&mut cursor);
+    writer.write_records(&users).unwrap();
+    cursor.set_position(0);
+
+    let mut reader = Reader::new(cursor).unwrap();
+    let oliver = FieldValue::Character(Some("Oliver".to_owned()));
+    assert_eq!(reader.count_where_eq("Last Name", &oliver).unwrap(), 1);
+}
+
+#[test]
+fn test_record_size_without_deletion_flag() {
+    // The header of this file says 1016 bytes per record,
+    // but its fields (with the deletion flag) take 1017 bytes
+    let bytes = std::fs::read(STATIONS_DBF).unwrap();
+    assert_eq!(&bytes[10..12], &1016u16.to_le_bytes());
+
+    let mut reader = Reader::new(Cursor::new(bytes)).unwrap();
+    assert_eq!(reader.header().size_of_record, 1017);
+    let records = reader.read().unwrap();
+    reader.seek(3).unwrap();
+    let fourth = reader.iter_records().next().unwrap().unwrap();
+    assert_eq!(fourth, records[3]);
+}
+
+#[test]
+fn test_write_numeric_none_fills_the_field() {
+    for &fill_byte in b" *" {
+        let mut record = Record::default();
+        record.insert("number".to_owned(), FieldValue::Numeric(None));
+        record.insert("float".to_owned(), FieldValue::Float(None));
+
+        let mut cursor = Cursor::new(Vec::<u8>::new());
+        let writer = TableWriterBuilder::new()
+            .add_numeric_field("number".try_into().unwrap(), 10, 2)
+            .add_float_field("float".try_into().unwrap(), 8, 3)
+            .numeric_none_fill_byte(fill_byte)
+            .build_with_dest(&mut cursor);
+        writer.write_records(&vec![record.clone()]).unwrap();
+
+        let bytes = cursor.get_ref();
+        // after the deletion flag
+        let record_start = offset_to_first_record(bytes) + 1;
+        assert!(bytes[record_start..record_start + 18]
+            .iter()
+            .all(|b| *b == fill_byte));
+        assert_eq!(bytes[record_start + 18], 0x1A);
+
+        cursor.set_position(0);
+        let mut reader = Reader::new(cursor).unwrap();
+        assert_eq!(reader.read().unwrap(), vec![record]);
+    }
+}
+
+#[test]
+fn test_estimated_memory() {
+    let mut reader = Reader::from_path(STATIONS_DBF).unwrap();
+    let estimation = reader.estimated_memory();
+
+    let records = reader.read().unwrap();
+    let loaded_size = records
+        .iter()
+        .map(|record| {
+            std::mem::size_of::<Record>()
+                + record
+                    .as_ref()
+                    .iter()
+                    .map(|(name, value)| {
+                        let value_size = match value {
+                            FieldValue::Character(Some(s)) => s.capacity(),
+                            _ => 0,
+                        };
+                        std::mem::size_of::<String>()
+                            + name.capacity()
+                            + std::mem::size_of::<FieldValue>()
+                            + value_size
+                    })
+                    .sum::<usize>()
+        })
+        .sum::<usize>() as u64;
+
+    assert!(estimation >= loaded_size);
+    // Character fields are estimated with their full length
+    assert!(estimation <= 5 * loaded_size);
+}
+
+#[test]
+fn test_iter_records_in_index_order() {
+    let mut reader = Reader::from_path(INDEXED_DBF).unwrap();
+    assert!(reader.header().table_flags.has_structural_cdx());
+    assert_eq!(reader.index_tags(), vec!["AGE", "NAME"]);
+
+    let ages = reader
+        .iter_records_in_index_order()
+        .unwrap()
+        .map(|record| record.unwrap().get("AGE").cloned().unwrap())
+        .collect::<Vec<_>>();
+    let expected_ages = [19.0, 28.0, 28.0, 35.0, 42.0]
+        .iter()
+        .map(|&age| FieldValue::Numeric(Some(age)))
+        .collect::<Vec<_>>();
+    assert_eq!(ages, expected_ages);
+
+    let names = reader
+        .iter_records_in_tag_order_as::<Record>("name")
+        .unwrap()
+        .map(|record| record.unwrap().get("NAME").cloned().unwrap())
+        .collect::<Vec<_>>();
+    let expected_names = ["Alice", "Bob", "Mark", "Paul", "Zoe"]
+        .iter()
+        .map(|&name| FieldValue::Character(Some(name.to_owned())))
+        .collect::<Vec<_>>();
+    assert_eq!(names, expected_names);
+
+    assert!(reader
+        .iter_records_in_tag_order_as::<Record>("BIRTH")
+        .is_none());
+
+    // The file order is still available
+    let records = reader.read().unwrap();
+    assert_eq!(
+        records[0].get("NAME"),
+        Some(&FieldValue::Character(Some("Paul".to_owned())))
+    );
+}
+
+#[test]
+fn test_bogus_index() {
+    let dbf_path = std::env::temp_dir().join("dbase_test_bogus_index.dbf");
+    let cdx_path = dbf_path.with_extension("cdx");
+    std::fs::copy(INDEXED_DBF, &dbf_path).unwrap();
+    std::fs::write(&cdx_path, b"not an index").unwrap();
+
+    let reader = Reader::from_path(&dbf_path);
+    let _ = std::fs::remove_file(&dbf_path);
+    let _ = std::fs::remove_file(&cdx_path);
+
+    // Only the index order is lost
+    let mut reader = reader.unwrap();
+    assert!(reader.index_error().is_some());
+    assert!(reader.index_tags().is_empty());
+    assert!(reader.iter_records_in_index_order().is_none());
+    assert_eq!(reader.read().unwrap().len(), 5);
+
+    assert!(Reader::from_path(INDEXED_DBF)
+        .unwrap()
+        .index_error()
+        .is_none());
+}
+
+#[test]
+fn test_no_index() {
+    let mut reader = Reader::from_path(STATIONS_DBF).unwrap();
+    assert!(reader.index_tags().is_empty());
+    assert!(reader.iter_records_in_index_order().is_none());
+}
+
+#[test]
+fn test_field_descriptor_reserved_bytes() {
+    let mut reserved_bytes = [0u8; 14];
+    // work area id, at offset 20 of the descriptor
+    reserved_bytes[2] = 1;
+
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::new()
+        .add_character_field("First Name".try_into().unwrap(), 50)
+        .add_character_field("Last Name".try_into().unwrap(), 50)
+        .field_descriptor_reserved_bytes("Last Name", reserved_bytes)
+        .unwrap()
+        .build_with_dest(&mut cursor);
+    let users = vec![User {
+        first_name: "Ferrys".to_string(),
+        last_name: "Rust".to_string(),
+    }];
+    writer.write_records(&users).unwrap();
+
+    let bytes = cursor.into_inner();
+    let first_descriptor = &bytes[32..64];
+    let second_descriptor = &bytes[64..96];
+    assert_eq!(&first_descriptor[18..], &[0u8; 14]);
+    assert_eq!(&second_descriptor[18..], &reserved_bytes);
+
+    // The bytes are kept when re-writing a file
+    let reader = Reader::new(Cursor::new(bytes)).unwrap();
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::from_reader(reader).build_with_dest(&mut cursor);
+    writer.write_records(&users).unwrap();
+    assert_eq!(&cursor.get_ref()[64 + 18..96], &reserved_bytes);
+
+    assert!(TableWriterBuilder::new()
+        .add_character_field("First Name".try_into().unwrap(), 50)
+        .field_descriptor_reserved_bytes("Last Name", reserved_bytes)
+        .is_err());
+
+    // In Visual FoxPro files, the flags and autoincrement bytes cannot be changed
+    let builder = TableWriterBuilder::new()
+        .add_datetime_field("Created".try_into().unwrap())
+        .add_character_field("Name".try_into().unwrap(), 50);
+    let mut last_bytes = [0u8; 14];
+    last_bytes[13] = 1;
+    let builder = builder
+        .field_descriptor_reserved_bytes("Name", last_bytes)
+        .unwrap();
+    assert!(builder
+        .field_descriptor_reserved_bytes("Name", reserved_bytes)
+        .is_err());
+}
+
+#[test]
+fn test_collect_memos() {
+    let mut reader = Reader::from_path(SHARED_MEMO_DBF).unwrap();
+    let memos = reader.collect_memos().unwrap();
+
+    let mut expected = std::collections::HashMap::new();
+    expected.insert(8, "Shared notes".to_owned());
+    expected.insert(9, "Alice notes".to_owned());
+    expected.insert(10, "Bob notes".to_owned());
+    assert_eq!(memos, expected);
+
+    // The position of the reader is kept
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 4);
+    assert_eq!(
+        records[2].get("NOTES"),
+        Some(&FieldValue::Memo("Shared notes".to_owned()))
+    );
+}
+
+#[test]
+fn test_pack_memo() {
+    let long_text = "0123456789".repeat(150);
+    let records = ["Old note", &long_text, "Kept note"]
+        .iter()
+        .map(|memo| {
+            let mut record = Record::default();
+            record.insert("Notes".to_owned(), FieldValue::Memo(memo.to_string()));
+            record
+        })
+        .collect::<Vec<_>>();
+    let mut dbf = Cursor::new(Vec::<u8>::new());
+    let mut dbt = Cursor::new(Vec::<u8>::new());
+    {
+        let memo_writer = MemoWriter::new(MemoFileType::DbaseMemo, &mut dbt).unwrap();
+        TableWriterBuilder::new()
+            .add_memo_field("Notes".try_into().unwrap())
+            .build_with_dest_and_memo(&mut dbf, memo_writer)
+            .write_records(&records)
+            .unwrap();
+    }
+    // The first two records no longer reference their memo, their blocks are orphaned
+    let first_record = offset_to_first_record(dbf.get_ref());
+    for i in 0..2 {
+        let start = first_record + i * 11 + 1;
+        dbf.get_mut()[start..start + 10].copy_from_slice(b"          ");
+    }
+
+    dbf.set_position(0);
+    dbt.set_position(0);
+    let mut reader = Reader::new_with_memo(dbf, dbt, MemoFileType::DbaseMemo).unwrap();
+    let mut packed_dbf = Cursor::new(Vec::<u8>::new());
+    let mut packed_dbt = Cursor::new(Vec::<u8>::new());
+    reader.pack_memo(&mut packed_dbf, &mut packed_dbt).unwrap();
+    // Header and 1 block for the kept note, instead of 6 blocks
+    assert_eq!(packed_dbt.get_ref().len(), 2 * 512);
+
+    packed_dbf.set_position(0);
+    packed_dbt.set_position(0);
+    let mut packed =
+        Reader::new_with_memo(packed_dbf, packed_dbt, MemoFileType::DbaseMemo).unwrap();
+    let values = packed
+        .read()
+        .unwrap()
+        .iter()
+        .map(|record| record.get("Notes").unwrap().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        vec![
+            FieldValue::Memo(String::new()),
+            FieldValue::Memo(String::new()),
+            FieldValue::Memo("Kept note".to_owned()),
+        ]
+    );
+
+    // The memo shared by two records is written once
+    let mut reader = Reader::from_path(SHARED_MEMO_DBF).unwrap();
+    let mut packed_dbf = Cursor::new(Vec::<u8>::new());
+    let mut packed_fpt = Cursor::new(Vec::<u8>::new());
+    reader.pack_memo(&mut packed_dbf, &mut packed_fpt).unwrap();
+    packed_dbf.set_position(0);
+    packed_fpt.set_position(0);
+    let mut packed =
+        Reader::new_with_memo(packed_dbf, packed_fpt, MemoFileType::FoxBaseMemo).unwrap();
+    assert_eq!(packed.collect_memos().unwrap().len(), 3);
+    assert_eq!(packed.read().unwrap(), reader.read().unwrap());
+
+    // Files without memo file cannot be packed
+    let mut reader = Reader::from_path(LINE_DBF).unwrap();
+    let error = reader
+        .pack_memo(Cursor::new(Vec::<u8>::new()), Cursor::new(Vec::<u8>::new()))
+        .unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::MissingMemoFile));
+}
+
+#[test]
+fn test_collect_memos_without_memo_fields() {
+    let mut reader = Reader::from_path(STATIONS_DBF).unwrap();
+    assert!(reader.collect_memos().unwrap().is_empty());
+}
+
+#[test]
+fn test_write_read_long_character_field() {
+    let long_text = "a".repeat(250) + &"b".repeat(50);
+
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::new()
+        .add_long_character_field("Comment".try_into().unwrap(), 300)
+        .add_character_field("Author".try_into().unwrap(), 10)
+        .build_with_dest(&mut cursor);
+    let mut record = Record::default();
+    record.insert("Comment".to_owned(), long_text.clone().into());
+    record.insert("Author".to_owned(), "Ferris".to_owned().into());
+    writer.write_records(&[record]).unwrap();
+
+    cursor.set_position(0);
+    let mut reader = Reader::new(cursor).unwrap();
+    assert_eq!(reader.fields()[1].full_length(), 300);
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(
+        records[0].get("Comment"),
+        Some(&FieldValue::Character(Some(long_text)))
+    );
+    assert_eq!(
+        records[0].get("Author"),
+        Some(&FieldValue::Character(Some("Ferris".to_owned())))
+    );
+}
+
+#[test]
+fn test_standalone_memo_reader() {
+    let mut memo_reader =
+        MemoReader::from_path(SHARED_MEMO_FPT, MemoFileType::FoxBaseMemo).unwrap();
+    assert_eq!(memo_reader.block_size(), 64);
+    // The header takes the 8 first blocks
+    assert_eq!(memo_reader.read_data_at(8).unwrap(), b"Shared notes");
+    assert_eq!(memo_reader.read_data_at(9).unwrap(), b"Alice notes");
+
+    let file = std::fs::File::open(SHARED_MEMO_FPT).unwrap();
+    let mut memo_reader = MemoReader::new(MemoFileType::FoxBaseMemo, file).unwrap();
+    assert_eq!(memo_reader.read_data_at(10).unwrap(), b"Bob notes");
+}
+
+#[test]
+fn test_field_overruns_record_size() {
+    let error = Reader::from_path(FIELD_OVERRUNS_RECORD_DBF).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        dbase::ErrorKind::FieldsExceedRecordSize
+    ));
+}
+
+#[test]
+fn test_read_records_with_unused_bytes() {
+    let records = dbase::read(PADDED_RECORDS_DBF).unwrap();
+    let names = records
+        .iter()
+        .map(|record| record.get("NAME").cloned().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            FieldValue::Character(Some("Paul".to_owned())),
+            FieldValue::Character(Some("Alice".to_owned())),
+        ]
+    );
+}
+
+#[test]
+fn test_datetime_field_makes_visual_fox_pro_file() {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::new()
+        .add_character_field("Name".try_into().unwrap(), 10)
+        .add_datetime_field("Created".try_into().unwrap())
+        .add_integer_field("Count".try_into().unwrap())
+        .build_with_dest(&mut cursor);
+    let created = DateTime::new(Date::new(3, 11, 2021), Time::new(8, 15, 42));
+    let mut record = Record::default();
+    record.insert("Name".to_owned(), "Ferris".to_owned().into());
+    record.insert("Created".to_owned(), FieldValue::DateTime(Some(created)));
+    record.insert("Count".to_owned(), FieldValue::Integer(Some(3)));
+    writer.write_records(&[record.clone()]).unwrap();
+
+    let bytes = cursor.into_inner();
+    assert_eq!(bytes[0], 0x30);
+
+    let mut reader = Reader::new(Cursor::new(bytes)).unwrap();
+    let datetime_info = &reader.fields()[2];
+    assert_eq!(datetime_info.name(), "Created");
+    assert_eq!(datetime_info.length(), 8);
+    assert_eq!(reader.read().unwrap(), vec![record]);
+    assert_eq!(created.date(), Date::new(3, 11, 2021));
+    assert_eq!(created.time().minutes(), 15);
+}
+
+#[test]
+fn test_read_field_at() {
+    let reader = Reader::from_path(SHARED_MEMO_DBF).unwrap();
+    let header = reader.header();
+    // The NOTES field of the 2nd record: after the deletion flag and the NAME field
+    let offset =
+        u64::from(header.offset_to_first_record) + u64::from(header.size_of_record) + 1 + 10;
+    let info = reader.fields()[2].clone();
+    assert_eq!(info.name(), "NOTES");
+
+    let mut file = std::fs::File::open(SHARED_MEMO_DBF).unwrap();
+    let mut memo_reader = Some(
+        MemoReader::new(
+            MemoFileType::FoxBaseMemo,
+            std::fs::File::open(SHARED_MEMO_FPT).unwrap(),
+        )
+        .unwrap(),
+    );
+    let value = dbase::read_field_at(&mut file, offset, &info, &mut memo_reader).unwrap();
+    assert_eq!(value, FieldValue::Memo("Alice notes".to_owned()));
+
+    let error = dbase::read_field_at(&mut file, offset, &info, &mut None).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::MissingMemoFile));
+}
+
+#[test]
+fn test_read_null_flags_of_many_bytes() {
+    let records = dbase::read(NULL_FLAGS_DBF).unwrap();
+    assert_eq!(records.len(), 3);
+    // The _NullFlags field is not a value of the record
+    assert!(records.iter().all(|record| record.as_ref().len() == 10));
+
+    let null_fields = |record: &Record| {
+        (1..=10)
+            .filter(|i| record.get(&format!("F{}", i)) == Some(&FieldValue::Numeric(None)))
+            .collect::<Vec<_>>()
+    };
+    // The 9th field's bit is in the second byte of the flags
+    assert_eq!(null_fields(&records[0]), vec![9]);
+    assert_eq!(records[0].get("F8"), Some(&FieldValue::Numeric(Some(8.0))));
+    assert_eq!(
+        records[0].get("F10"),
+        Some(&FieldValue::Numeric(Some(10.0)))
+    );
+    assert_eq!(null_fields(&records[1]), Vec::<usize>::new());
+    assert_eq!(null_fields(&records[2]), vec![1, 10]);
+}
+
+#[test]
+fn test_read_null_binary_fields() {
+    let records = dbase::read(NULL_BINARY_FIELDS_DBF).unwrap();
+    let stamp = DateTime::new(Date::new(5, 3, 2021), Time::new(13, 4, 9));
+    let values = |record: &Record| {
+        ["ID", "COUNT", "RATIO", "STAMP"]
+            .iter()
+            .map(|name| record.get(name).unwrap().clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        values(&records[0]),
+        vec![
+            FieldValue::Integer(Some(1)),
+            FieldValue::Integer(Some(10)),
+            FieldValue::Double(Some(0.75)),
+            FieldValue::DateTime(Some(stamp)),
+        ]
+    );
+    assert_eq!(
+        values(&records[1]),
+        vec![
+            FieldValue::Integer(Some(2)),
+            FieldValue::Integer(None),
+            FieldValue::Double(Some(1.5)),
+            FieldValue::DateTime(None),
+        ]
+    );
+    assert_eq!(
+        values(&records[2]),
+        vec![
+            FieldValue::Integer(Some(3)),
+            FieldValue::Integer(Some(30)),
+            FieldValue::Double(None),
+            FieldValue::DateTime(Some(stamp)),
+        ]
+    );
+    let prices = records
+        .iter()
+        .map(|record| record.get("PRICE").unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        prices,
+        vec![
+            &FieldValue::Currency(Some(2.5)),
+            &FieldValue::Currency(Some(3.0)),
+            &FieldValue::Currency(None),
+        ]
+    );
+    assert_eq!(
+        Option::<i32>::try_from(records[1].get("COUNT").unwrap().clone()).unwrap(),
+        None
+    );
+    assert!(matches!(
+        i32::try_from(records[1].get("COUNT").unwrap().clone()),
+        Err(dbase::FieldConversionError::NoneValue)
+    ));
+}
+
+#[test]
+fn test_write_null_binary_fields() {
+    let mut reader = Reader::from_path(NULL_BINARY_FIELDS_DBF).unwrap();
+    let records = reader.read().unwrap();
+    let table_info = reader.into_table_info();
+
+    // The null values get their bit of the _NullFlags field
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer =
+        TableWriterBuilder::from_table_info(table_info.clone()).build_with_dest(&mut cursor);
+    writer.write_records(&records).unwrap();
+    cursor.set_position(0);
+    assert_eq!(Reader::new(cursor).unwrap().read().unwrap(), records);
+
+    // ID is not nullable, it cannot be written as null
+    let mut record = records[0].clone();
+    record.insert("ID".to_owned(), FieldValue::Integer(None));
+    let mut writer = TableWriterBuilder::from_table_info(table_info)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.write_record(&record).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::NoNullFlagsBit));
+}
+
+#[test]
+fn test_read_varchar_varbinary_fields() {
+    let records = dbase::read(VARCHAR_VARBINARY_DBF).unwrap();
+    let values = |record: &Record| {
+        (
+            record.get("NAME").unwrap().clone(),
+            record.get("DATA").unwrap().clone(),
+        )
+    };
+    assert_eq!(
+        values(&records[0]),
+        (
+            FieldValue::Varchar(Some("Ferris".to_owned())),
+            FieldValue::Varbinary(Some(vec![1, 2, 0, 3, 4, 5])),
+        )
+    );
+    assert_eq!(
+        values(&records[1]),
+        (
+            FieldValue::Varchar(Some("0123456789".to_owned())),
+            FieldValue::Varbinary(Some(vec![0xAA, 0xBB])),
+        )
+    );
+    assert_eq!(
+        values(&records[2]),
+        (
+            FieldValue::Varchar(None),
+            FieldValue::Varbinary(Some(vec![])),
+        )
+    );
+}
+
+#[test]
+fn test_write_varchar_varbinary_fields() {
+    let mut reader = Reader::from_path(VARCHAR_VARBINARY_DBF).unwrap();
+    let mut records = reader.read().unwrap();
+    let table_info = reader.into_table_info();
+
+    // Shorter values keep their length, null values stay null
+    let mut record = records[0].clone();
+    record.insert(
+        "NAME".to_owned(),
+        FieldValue::Varchar(Some("abc".to_owned())),
+    );
+    records.push(record);
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer =
+        TableWriterBuilder::from_table_info(table_info.clone()).build_with_dest(&mut cursor);
+    writer.write_records(&records).unwrap();
+    cursor.set_position(0);
+    assert_eq!(Reader::new(cursor).unwrap().read().unwrap(), records);
+
+    // DATA is not nullable
+    let mut record = records[0].clone();
+    record.insert("DATA".to_owned(), FieldValue::Varbinary(None));
+    let mut writer = TableWriterBuilder::from_table_info(table_info)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.write_record(&record).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::NoNullFlagsBit));
+}
+
+#[test]
+fn test_field_name_normalization() {
+    let reader = Reader::from_path(PADDED_FIELD_NAME_DBF).unwrap();
+    assert_eq!(reader.fields()[1].name(), " naMe  ");
+
+    let options = ReadingOptions::default().field_name_normalization(FieldNameNormalization::Trim);
+    let reader = Reader::from_path_with_options(PADDED_FIELD_NAME_DBF, options).unwrap();
+    assert_eq!(reader.fields()[1].name(), "naMe");
+
+    let options = ReadingOptions::default()
+        .field_name_normalization(FieldNameNormalization::TrimAndUppercase);
+    let mut reader = Reader::from_path_with_options(PADDED_FIELD_NAME_DBF, options).unwrap();
+    assert_eq!(reader.fields()[1].name(), "NAME");
+    let records = reader.read().unwrap();
+    assert_eq!(
+        records[0].get("NAME"),
+        Some(&FieldValue::Character(Some("Paul".to_owned())))
+    );
+}
+
+#[test]
+fn test_write_record_map() {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("Name".try_into().unwrap(), 10)
+        .add_numeric_field("Age".try_into().unwrap(), 3, 0)
+        .add_integer_field("Count".try_into().unwrap())
+        .build_with_dest(&mut cursor);
+
+    let mut map = std::collections::HashMap::new();
+    map.insert(
+        "Name".to_owned(),
+        FieldValue::Character(Some("Ferris".to_owned())),
+    );
+    map.insert("Count".to_owned(), FieldValue::Integer(Some(7)));
+    writer.write_record_map(&map).unwrap();
+    drop(writer);
+
+    cursor.set_position(0);
+    let mut reader = Reader::new(cursor).unwrap();
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(
+        records[0].get("Name"),
+        Some(&FieldValue::Character(Some("Ferris".to_owned())))
+    );
+    assert_eq!(records[0].get("Age"), Some(&FieldValue::Numeric(None)));
+    assert_eq!(records[0].get("Count"), Some(&FieldValue::Integer(Some(7))));
+
+    let mut writer = TableWriterBuilder::new()
+        .add_numeric_field("Age".try_into().unwrap(), 3, 0)
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    map.insert(
+        "Age".to_owned(),
+        FieldValue::Character(Some("old".to_owned())),
+    );
+    let error = writer.write_record_map(&map).unwrap_err();
+    assert!(matches!(error.kind(), dbase::ErrorKind::IncompatibleType));
+}
+
+#[test]
+fn test_field_is_indexed() {
+    let reader = Reader::from_path(INDEXED_FIELD_DBF).unwrap();
+    assert!(reader.header().table_flags.has_structural_cdx());
+    let indexed = reader
+        .fields()
+        .iter()
+        .skip(1)
+        .map(|info| (info.name(), info.is_indexed()))
+        .collect::<Vec<_>>();
+    assert_eq!(indexed, vec![("ID", true), ("NAME", false)]);
+}
+
+#[test]
+fn test_iter_record_pairs() {
+    let mut reader = Reader::from_path(STATIONS_DBF).unwrap();
+    let field_names = reader
+        .fields()
+        .iter()
+        .skip(1)
+        .map(|info| info.name().to_owned())
+        .collect::<Vec<_>>();
+    let first_record = reader.iter_record_pairs().next().unwrap().unwrap();
+
+    let names = first_record
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(names, field_names);
+    assert_eq!(
+        first_record[0],
+        (
+            "name".to_owned(),
+            FieldValue::Character(Some("Van Dorn Street".to_owned()))
+        )
+    );
+}
+
+#[test]
+fn test_numeric_zero_padding() {
+    let write_values = |zero_padding: bool| {
+        let mut cursor = Cursor::new(Vec::<u8>::new());
+        let writer = TableWriterBuilder::new()
+            .add_numeric_field("Amount".try_into().unwrap(), 8, 2)
+            .numeric_zero_padding(zero_padding)
+            .build_with_dest(&mut cursor);
+        let records = [12.5, -3.25, 1234.0]
+            .iter()
+            .map(|&amount| {
+                let mut record = Record::default();
+                record.insert("Amount".to_owned(), FieldValue::Numeric(Some(amount)));
+                record
+            })
+            .collect::<Vec<_>>();
+        writer.write_records(&records).unwrap();
+
+        let bytes = cursor.into_inner();
+        let first_record = offset_to_first_record(&bytes);
+        (0..3)
+            .map(|i| {
+                let start = first_record + i * 9 + 1;
+                String::from_utf8(bytes[start..start + 8].to_vec()).unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(write_values(true), vec!["00012.50", "-0003.25", "01234.00"]);
+    assert_eq!(
+        write_values(false),
+        vec!["   12.50", "   -3.25", " 1234.00"]
+    );
+}
+
+#[test]
+#[cfg(feature = "encoding")]
+fn test_sniff_character_encoding() {
+    let read_names = |options: ReadingOptions| {
+        let mut reader = Reader::from_path_with_options(CP1252_NO_CODE_PAGE_DBF, options).unwrap();
+        assert_eq!(reader.header().code_page_mark, 0);
+        reader
+            .read()
+            .unwrap()
+            .into_iter()
+            .map(|record| match record.get("NAME") {
+                Some(FieldValue::Character(Some(name))) => name.clone(),
+                value => panic!("Unexpected value {:?}", value),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let names = read_names(ReadingOptions::default().sniff_character_encoding(true));
+    assert_eq!(
+        names,
+        vec!["Café Müller", "€uro \u{201C}quoted\u{201D}", "Zoë"]
+    );
+
+    let names = read_names(ReadingOptions::default());
+    assert_eq!(names[0], "Caf\u{FFFD} M\u{FFFD}ller");
+    assert_eq!(names[2], "Zoë");
+}
+
+#[test]
+fn test_validate_record() {
+    let schema = empty_table_fields(
+        TableWriterBuilder::new()
+            .add_character_field("Name".try_into().unwrap(), 5)
+            .add_numeric_field("Age".try_into().unwrap(), 3, 0),
+    );
+
+    let mut record = Record::default();
+    record.insert(
+        "Name".to_owned(),
+        FieldValue::Character(Some("Yoshi".to_owned())),
+    );
+    record.insert("Age".to_owned(), FieldValue::Numeric(Some(32.0)));
+    dbase::validate_record(&record, &schema).unwrap();
+
+    let mut too_wide = record.clone();
+    too_wide.insert(
+        "Name".to_owned(),
+        FieldValue::Character(Some("Yoshimi".to_owned())),
+    );
+    let error = dbase::validate_record(&too_wide, &schema).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::ValueTooLong));
+    assert_eq!(error.field().as_ref().unwrap().name(), "Name");
+
+    let mut wrong_type = record.clone();
+    wrong_type.insert("Age".to_owned(), FieldValue::Logical(Some(true)));
+    let error = dbase::validate_record(&wrong_type, &schema).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::IncompatibleType));
+
+    let mut wrong_name = record.clone();
+    let age = wrong_name.remove("Age").unwrap();
+    wrong_name.insert("Height".to_owned(), age);
+    let error = dbase::validate_record(&wrong_name, &schema).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::NotEnoughFields));
+
+    record.remove("Age");
+    let error = dbase::validate_record(&record, &schema).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::NotEnoughFields));
+
+    // Numbers are rounded to the decimals of their field, like the writer does
+    let schema = empty_table_fields(TableWriterBuilder::new().add_numeric_field(
+        "V".try_into().unwrap(),
+        10,
+        2,
+    ));
+    let record = RecordBuilder::new(&schema)
+        .set("V", FieldValue::Numeric(Some(1.0 / 3.0)))
+        .build()
+        .unwrap();
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::new()
+        .add_numeric_field("V".try_into().unwrap(), 10, 2)
+        .build_with_dest(&mut cursor)
+        .write_records(&[record])
+        .unwrap();
+    let bytes = cursor.into_inner();
+    let start = offset_to_first_record(&bytes) + 1;
+    assert_eq!(&bytes[start..start + 10], b"      0.33");
+    let error = RecordBuilder::new(&schema)
+        .set("V", FieldValue::Numeric(Some(123456789.0)))
+        .build()
+        .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::ValueTooLong));
+}
+
+#[test]
+fn test_writer_dialect() {
+    let builder = |dialect: Dialect| {
+        TableWriterBuilder::new()
+            .dialect(dialect)
+            .add_character_field("Name".try_into().unwrap(), 20)
+            .add_numeric_field("Age".try_into().unwrap(), 3, 0)
+            .add_date_field("Birth".try_into().unwrap())
+    };
+
+    for &(dialect, version_byte) in &[(Dialect::DbaseIII, 0x03), (Dialect::VisualFoxPro, 0x30)] {
+        let b = builder(dialect);
+        b.validate().unwrap();
+        let mut cursor = Cursor::new(Vec::<u8>::new());
+        let mut writer = b.build_with_dest(&mut cursor);
+        let mut record = Record::default();
+        record.insert(
+            "Name".to_owned(),
+            FieldValue::Character(Some("Yoshi".to_owned())),
+        );
+        record.insert("Age".to_owned(), FieldValue::Numeric(Some(32.0)));
+        record.insert("Birth".to_owned(), FieldValue::Date(None));
+        writer.write_record(&record).unwrap();
+        drop(writer);
+
+        assert_eq!(cursor.get_ref()[0], version_byte);
+        cursor.set_position(0);
+        let records = Reader::new(cursor).unwrap().read().unwrap();
+        assert_eq!(records, vec![record]);
+    }
+
+    // Float fields do not exist in dBase III
+    let b = builder(Dialect::DbaseIII).add_float_field("Height".try_into().unwrap(), 5, 2);
+    let error = b.validate().unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidFieldType('F')));
+    // The same check is done before the first record is written
+    let b = builder(Dialect::DbaseIII).add_float_field("Height".try_into().unwrap(), 5, 2);
+    let mut writer = b.build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.write_record(&Record::default()).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidFieldType('F')));
+    let b = builder(Dialect::DbaseIV).add_float_field("Height".try_into().unwrap(), 5, 2);
+    b.validate().unwrap();
+
+    let b = builder(Dialect::VisualFoxPro).add_character_field("Long".try_into().unwrap(), 255);
+    let error = b.validate().unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::FieldTooLong));
+
+    assert_eq!(Dialect::DbaseIV.memo_file_type(), MemoFileType::DbaseMemo4);
+    // The dBase IV memo files cannot be written
+    let b = builder(Dialect::DbaseIV).add_memo_field("Notes".try_into().unwrap());
+    let error = b.validate().unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Message(_)));
+    let b = builder(Dialect::DbaseIII).add_memo_field("Notes".try_into().unwrap());
+    b.validate().unwrap();
+}
+
+#[test]
+fn test_preview() {
+    let mut reader = Reader::from_path(STATIONS_DBF).unwrap();
+    let (fields, first_record) = reader.preview().unwrap();
+    let names = fields.iter().map(|info| info.name()).collect::<Vec<_>>();
+    assert_eq!(names, vec!["name", "marker-col", "marker-sym", "line"]);
+    let first_record = first_record.unwrap();
+    assert_eq!(
+        first_record.get("name"),
+        Some(&FieldValue::Character(Some("Van Dorn Street".to_owned())))
+    );
+    // The reader was not moved
+    assert_eq!(reader.read().unwrap()[0], first_record);
+
+    let cursor = write_empty_table(
+        TableWriterBuilder::new().add_character_field("Name".try_into().unwrap(), 10),
+    );
+    let mut reader = Reader::new(cursor).unwrap();
+    let (fields, first_record) = reader.preview().unwrap();
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].name(), "Name");
+    assert!(first_record.is_none());
+}
+
+#[test]
+fn test_column_raw() {
+    let mut reader = Reader::from_path(LONG_NUMERIC_DBF).unwrap();
+    let accounts = reader.column_raw("ACCOUNT").unwrap();
+    let rates = reader.column_raw("RATE").unwrap();
+    assert_eq!(
+        accounts,
+        vec![
+            Some("123456789012345678".to_owned()),
+            Some("-987654321098765432".to_owned()),
+            None
+        ]
+    );
+    assert_eq!(rates, vec![Some("12.5".to_owned()), None, None]);
+    assert_eq!(
+        accounts[0].as_ref().unwrap().parse::<i128>().unwrap(),
+        123_456_789_012_345_678
+    );
+
+    // Parsed as f64, the last digits are lost
+    let records = reader.read().unwrap();
+    match records[0].get("ACCOUNT") {
+        Some(FieldValue::Numeric(Some(account))) => {
+            assert_ne!(*account as i128, 123_456_789_012_345_678)
+        }
+        value => panic!("Unexpected value {:?}", value),
+    }
+    // The records keep the types of their fields, so they can be written back
+    let writer =
+        TableWriterBuilder::from_reader(reader).build_with_dest(Cursor::new(Vec::<u8>::new()));
+    writer.write_records(&records).unwrap();
+
+    let mut reader = Reader::from_path(STATIONS_DBF).unwrap();
+    let error = reader.column_raw("line").unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::IncompatibleType));
+}
+
+#[test]
+fn test_column_as() {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let records = [Some(1.5), None, Some(-2.0)]
+        .iter()
+        .map(|&amount| {
+            let mut record = Record::default();
+            record.insert("Amount".to_owned(), FieldValue::Numeric(amount));
+            record.insert("Name".to_owned(), FieldValue::Character(None));
+            record
+        })
+        .collect::<Vec<_>>();
+    TableWriterBuilder::new()
+        .add_character_field("Name".try_into().unwrap(), 10)
+        .add_numeric_field("Amount".try_into().unwrap(), 8, 2)
+        .build_with_dest(&mut cursor)
+        .write_records(&records)
+        .unwrap();
+    cursor.set_position(0);
+    let mut reader = Reader::new(cursor).unwrap();
+
+    let amounts = reader.column_as::<f64>("Amount").unwrap();
+    assert_eq!(amounts, vec![Some(1.5), None, Some(-2.0)]);
+    let names = reader.column_as::<String>("Name").unwrap();
+    assert_eq!(names, vec![None, None, None]);
+
+    let error = reader.column_as::<bool>("Amount").unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::BadConversion(_)));
+    assert!(reader.column_as::<f64>("Height").is_err());
+
+    // The reader was not moved
+    assert_eq!(reader.read().unwrap(), records);
+}
+
+#[test]
+fn test_write_dbase3_memo() {
+    let long_text = "0123456789".repeat(150);
+    let memos = ["Short note".to_owned(), long_text.clone(), String::new()];
+    let records = memos
+        .iter()
+        .enumerate()
+        .map(|(i, memo)| {
+            let mut record = Record::default();
+            record.insert("Id".to_owned(), FieldValue::Numeric(Some(i as f64)));
+            record.insert("Notes".to_owned(), FieldValue::Memo(memo.clone()));
+            record
+        })
+        .collect::<Vec<_>>();
+
+    let mut dbf = Cursor::new(Vec::<u8>::new());
+    let mut dbt = Cursor::new(Vec::<u8>::new());
+    {
+        let memo_writer = MemoWriter::new(MemoFileType::DbaseMemo, &mut dbt).unwrap();
+        let writer = TableWriterBuilder::new()
+            .add_numeric_field("Id".try_into().unwrap(), 3, 0)
+            .add_memo_field("Notes".try_into().unwrap())
+            .build_with_dest_and_memo(&mut dbf, memo_writer);
+        writer.write_records(&records).unwrap();
+    }
+
+    // dBase III with memo
+    assert_eq!(dbf.get_ref()[0], 0x83);
+    // Header, 1 block for the short note, 3 blocks for the long one, nothing for the empty one
+    assert_eq!(dbt.get_ref().len(), 5 * 512);
+    assert_eq!(&dbt.get_ref()[..4], &5u32.to_le_bytes());
+    assert_eq!(&dbt.get_ref()[512 + 10..512 + 12], &[0x1A, 0x1A]);
+
+    // The memo fields hold the index of the first block of their data
+    let first_record = offset_to_first_record(dbf.get_ref());
+    let indices = (0..3)
+        .map(|i| {
+            let start = first_record + i * 14 + 4;
+            String::from_utf8(dbf.get_ref()[start..start + 10].to_vec()).unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(indices, vec!["         1", "         2", "          "]);
+
+    dbt.set_position(0);
+    let mut memo_reader = MemoReader::new(MemoFileType::DbaseMemo, dbt).unwrap();
+    assert_eq!(memo_reader.read_data_at(1).unwrap(), b"Short note");
+    assert_eq!(memo_reader.read_data_at(2).unwrap(), long_text.as_bytes());
+
+    // Round trip through files, the .dbt is created and found next to the .dbf
+    let path = std::env::temp_dir().join("dbase_test_write_dbase3_memo.dbf");
+    TableWriterBuilder::new()
+        .add_numeric_field("Id".try_into().unwrap(), 3, 0)
+        .add_memo_field("Notes".try_into().unwrap())
+        .build_with_file_dest(&path)
+        .unwrap()
+        .write_records(&records)
+        .unwrap();
+    let read_records = dbase::read(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(path.with_extension("dbt"));
+    assert_eq!(read_records, records);
+}
+
+#[test]
+fn test_write_fox_pro_memo() {
+    let long_text = "0123456789".repeat(10);
+    let memos = ["Short note".to_owned(), long_text.clone(), String::new()];
+    let records = memos
+        .iter()
+        .enumerate()
+        .map(|(i, memo)| {
+            let mut record = Record::default();
+            record.insert("Id".to_owned(), FieldValue::Integer(Some(i as i32)));
+            record.insert("Notes".to_owned(), FieldValue::Memo(memo.clone()));
+            record
+        })
+        .collect::<Vec<_>>();
+
+    let mut dbf = Cursor::new(Vec::<u8>::new());
+    let mut fpt = Vec::<u8>::new();
+    {
+        // The memo writer does not write to the same type of destination as the table
+        let memo_writer =
+            MemoWriter::new(MemoFileType::FoxBaseMemo, Cursor::new(&mut fpt)).unwrap();
+        let writer = TableWriterBuilder::new()
+            .add_integer_field("Id".try_into().unwrap())
+            .add_memo_field("Notes".try_into().unwrap())
+            .build_with_dest_and_memo(&mut dbf, memo_writer);
+        writer.write_records(&records).unwrap();
+    }
+
+    // FoxPro 2 with memo
+    assert_eq!(dbf.get_ref()[0], 0xF5);
+    // Header (8 blocks), 1 block for the short note, 2 blocks for the long one
+    assert_eq!(fpt.len(), 11 * 64);
+    assert_eq!(&fpt[..4], &11u32.to_be_bytes());
+    assert_eq!(&fpt[6..8], &64u16.to_be_bytes());
+    // The data of a block is preceded by its type (text) and length
+    assert_eq!(&fpt[512..520], &[0, 0, 0, 1, 0, 0, 0, 10]);
+
+    let reader = Reader::new_with_memo(
+        Cursor::new(dbf.into_inner()),
+        Cursor::new(fpt),
+        MemoFileType::FoxBaseMemo,
+    );
+    assert_eq!(reader.unwrap().read().unwrap(), records);
+
+    // Round trip through files, the .fpt is created and found next to the .dbf
+    let path = std::env::temp_dir().join("dbase_test_write_fox_pro_memo.dbf");
+    TableWriterBuilder::new()
+        .add_integer_field("Id".try_into().unwrap())
+        .add_memo_field("Notes".try_into().unwrap())
+        .build_with_file_dest(&path)
+        .unwrap()
+        .write_records(&records)
+        .unwrap();
+    let read_records = dbase::read(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let _ = std::fs::remove_file(path.with_extension("fpt"));
+    assert_eq!(read_records, records);
+}
+
+#[test]
+fn test_is_deleted() {
+    let mut reader = Reader::from_path(DELETED_RECORDS_DBF).unwrap();
+    let flags = (0..4)
+        .map(|i| reader.is_deleted(i).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(flags, vec![false, true, false, true]);
+    assert!(reader.is_deleted(4).is_err());
+
+    // The reader was not moved
+    assert_eq!(reader.iter_all().count(), 4);
+}
+
+#[test]
+fn test_include_deleted_records() {
+    let mut reader = Reader::from_path(DELETED_RECORDS_DBF).unwrap();
+    let records = reader.iter_all().collect::<Result<Vec<_>, _>>().unwrap();
+    let flags = records.iter().map(Record::is_deleted).collect::<Vec<_>>();
+    assert_eq!(flags, vec![false, true, false, true]);
+
+    // The deleted records are skipped by default
+    reader.seek(0).unwrap();
+    let live_records = reader.read().unwrap();
+    assert_eq!(live_records, vec![records[0].clone(), records[2].clone()]);
+    reader.seek(0).unwrap();
+    let deleted_records = reader
+        .iter_deleted()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        deleted_records,
+        vec![records[1].clone(), records[3].clone()]
+    );
+
+    let options = ReadingOptions::default().include_deleted(true);
+    let mut reader = Reader::from_path_with_options(DELETED_RECORDS_DBF, options).unwrap();
+    assert_eq!(reader.read().unwrap(), records);
+
+    // The deletion mark is not part of the equality
+    let mut record = Record::default();
+    for (name, value) in records[1].clone() {
+        record.insert(name, value);
+    }
+    assert!(!record.is_deleted());
+    assert_eq!(record, records[1]);
+}
+
+#[test]
+fn test_deleted_records_in_other_apis() {
+    let mut reader = Reader::from_path(DELETED_RECORDS_DBF).unwrap();
+    let records = reader.iter_all().collect::<Result<Vec<_>, _>>().unwrap();
+
+    // These follow the include_deleted option
+    let (_, first_record) = reader.preview().unwrap();
+    assert_eq!(first_record, Some(records[0].clone()));
+    reader.seek(0).unwrap();
+    assert_eq!(reader.records_with_schema().count(), 2);
+    reader.seek(0).unwrap();
+    assert_eq!(reader.read_columnar().unwrap().num_records(), 2);
+    let names = reader.column_as::<String>("NAME").unwrap();
+    assert_eq!(
+        names,
+        vec![Some("Alpha".to_owned()), Some("Charlie".to_owned())]
+    );
+    let bravo = FieldValue::Character(Some("Bravo".to_owned()));
+    assert_eq!(reader.count_where_eq("NAME", &bravo).unwrap(), 0);
+
+    let options = ReadingOptions::default().include_deleted(true);
+    let mut reader = Reader::from_path_with_options(DELETED_RECORDS_DBF, options).unwrap();
+    assert_eq!(reader.records_with_schema().count(), 4);
+    reader.seek(0).unwrap();
+    assert_eq!(reader.read_columnar().unwrap().num_records(), 4);
+    assert_eq!(reader.column_as::<String>("NAME").unwrap().len(), 4);
+    assert_eq!(reader.count_where_eq("NAME", &bravo).unwrap(), 1);
+
+    // concat never brings deleted records back
+    let mut tables = vec![reader];
+    let mut dst = Cursor::new(Vec::<u8>::new());
+    dbase::concat(&mut tables, &mut dst).unwrap();
+    dst.set_position(0);
+    let mut reader = Reader::new(dst).unwrap();
+    assert_eq!(
+        reader.iter_all().collect::<Result<Vec<_>, _>>().unwrap(),
+        vec![records[0].clone(), records[2].clone()]
+    );
+}
+
+#[test]
+fn test_collect_memos_skips_deleted_records() {
+    let mut dbf = std::fs::read(SHARED_MEMO_DBF).unwrap();
+    let header = *Reader::new(Cursor::new(dbf.clone())).unwrap().header();
+    for i in 0..header.num_records as usize {
+        let pos = header.offset_to_first_record as usize + i * header.size_of_record as usize;
+        dbf[pos] = b'*';
+    }
+    let fpt = std::fs::read(SHARED_MEMO_FPT).unwrap();
+
+    let mut reader = Reader::new_with_memo(
+        Cursor::new(dbf.clone()),
+        Cursor::new(fpt.clone()),
+        MemoFileType::FoxBaseMemo,
+    )
+    .unwrap();
+    assert!(reader.collect_memos().unwrap().is_empty());
+
+    let mut reader = Reader::new_with_memo_and_options(
+        Cursor::new(dbf),
+        Cursor::new(fpt),
+        MemoFileType::FoxBaseMemo,
+        ReadingOptions::default().include_deleted(true),
+    )
+    .unwrap();
+    assert_eq!(reader.collect_memos().unwrap().len(), 3);
+}
+
+#[test]
+fn test_index_order_skips_deleted_records() {
+    let dir = std::env::temp_dir();
+    let dbf_path = dir.join("dbase_test_index_order_deleted.dbf");
+    let cdx_path = dbf_path.with_extension("cdx");
+    let mut dbf = std::fs::read(INDEXED_DBF).unwrap();
+    // Marks the first record, "Paul", as deleted
+    let offset_to_first_record = u16::from_le_bytes([dbf[8], dbf[9]]) as usize;
+    dbf[offset_to_first_record] = b'*';
+    std::fs::write(&dbf_path, dbf).unwrap();
+    std::fs::copy("./tests/data/indexed.cdx", &cdx_path).unwrap();
+
+    let names = |reader: &mut Reader<_>| {
+        reader
+            .iter_records_in_tag_order_as::<Record>("NAME")
+            .unwrap()
+            .map(|record| match record.unwrap().get("NAME") {
+                Some(FieldValue::Character(Some(name))) => name.clone(),
+                value => panic!("Unexpected value {:?}", value),
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut reader = Reader::from_path(&dbf_path).unwrap();
+    let live_names = names(&mut reader);
+    let options = ReadingOptions::default().include_deleted(true);
+    let mut reader = Reader::from_path_with_options(&dbf_path, options).unwrap();
+    let all_names = names(&mut reader);
+    let _ = std::fs::remove_file(&dbf_path);
+    let _ = std::fs::remove_file(&cdx_path);
+
+    assert_eq!(live_names, vec!["Alice", "Bob", "Mark", "Zoe"]);
+    assert_eq!(all_names, vec!["Alice", "Bob", "Mark", "Paul", "Zoe"]);
+}
+
+#[test]
+fn test_deletion_flag_convention() {
+    let names = |records: &[Record]| {
+        records
+            .iter()
+            .map(|record| match record.get("NAME") {
+                Some(FieldValue::Character(Some(name))) => name.clone(),
+                value => panic!("Unexpected value {:?}", value),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // With the standard convention, no record is deleted
+    let mut reader = Reader::from_path(ZERO_DELETION_FLAG_DBF).unwrap();
+    assert!(!reader.is_deleted(1).unwrap());
+
+    let options = ReadingOptions::default()
+        .deletion_flag(DeletionFlag::ZeroIsLive)
+        .include_deleted(true);
+    let mut reader = Reader::from_path_with_options(ZERO_DELETION_FLAG_DBF, options).unwrap();
+    let flags = (0..3)
+        .map(|i| reader.is_deleted(i).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(flags, vec![false, true, false]);
+    let records = reader.read().unwrap();
+    assert!(records[1].is_deleted());
+    assert_eq!(names(&records), vec!["Alpha", "Bravo", "Charlie"]);
+
+    let options = options.include_deleted(false);
+    let mut reader = Reader::from_path_with_options(ZERO_DELETION_FLAG_DBF, options).unwrap();
+    assert_eq!(names(&reader.read().unwrap()), vec!["Alpha", "Charlie"]);
+}
+
+/// Destination whose flush fails, when asked to
+struct FailingFlushDest {
+    inner: Cursor<Vec<u8>>,
+    fail_flush: bool,
+}
+
+impl Write for FailingFlushDest {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.fail_flush {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "flush failed",
+            ))
+        } else {
+            self.inner.flush()
+        }
+    }
+}
+
+impl Seek for FailingFlushDest {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn test_finish_reports_memo_errors() {
+    let mut dbf = FailingFlushDest {
+        inner: Cursor::new(Vec::new()),
+        fail_flush: false,
+    };
+    let mut dbt = FailingFlushDest {
+        inner: Cursor::new(Vec::new()),
+        fail_flush: true,
+    };
+    let memo_writer = MemoWriter::new(MemoFileType::DbaseMemo, &mut dbt).unwrap();
+    let mut writer = TableWriterBuilder::new()
+        .add_memo_field("Notes".try_into().unwrap())
+        .build_with_dest_and_memo(&mut dbf, memo_writer);
+    let mut record = Record::default();
+    record.insert(
+        "Notes".to_owned(),
+        FieldValue::Memo("Some notes".to_owned()),
+    );
+    writer.write_record(&record).unwrap();
+
+    let error = writer.finish().unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::IoError(_)));
+
+    // The table was still finished
+    dbf.inner.set_position(0);
+    let reader = Reader::new(dbf.inner).unwrap();
+    assert_eq!(reader.header().num_records, 1);
+    // As was the header of the memo file
+    assert_eq!(&dbt.inner.get_ref()[..4], &2u32.to_le_bytes());
+}
+
+#[test]
+fn test_cap_record_count() {
+    let options = ReadingOptions::default().cap_record_count(true);
+    let mut reader = Reader::from_path_with_options(INFLATED_RECORD_COUNT_DBF, options).unwrap();
+    assert_eq!(reader.header().num_records, 3);
+    let names = reader
+        .read()
+        .unwrap()
+        .into_iter()
+        .map(|record| record.get("NAME").cloned().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            FieldValue::Character(Some("Alpha".to_owned())),
+            FieldValue::Character(Some("Bravo".to_owned())),
+            FieldValue::Character(Some("Charlie".to_owned())),
+        ]
+    );
+
+    // Files that are fine are not affected
+    let mut reader = Reader::from_path_with_options(STATIONS_DBF, options).unwrap();
+    assert_eq!(reader.read().unwrap().len(), 6);
+
+    let mut reader = Reader::from_path(INFLATED_RECORD_COUNT_DBF).unwrap();
+    assert_eq!(reader.header().num_records, 2_000_000_000);
+    // Without the option, the iteration stops at the end of file marker
+    assert_eq!(reader.iter_records().count(), 3);
+}
+
+#[test]
+fn test_character_trim_mode() {
+    let read_names = |options: ReadingOptions| {
+        Reader::from_path_with_options(LEADING_SPACES_DBF, options)
+            .unwrap()
+            .read()
+            .unwrap()
+            .into_iter()
+            .map(|record| record.get("NAME").cloned().unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        read_names(ReadingOptions::default()),
+        vec![
+            FieldValue::Character(Some("  ab".to_owned())),
+            FieldValue::Character(Some("ab".to_owned())),
+            FieldValue::Character(None),
+        ]
+    );
+    assert_eq!(
+        read_names(ReadingOptions::default().character_trim(TrimMode::Both)),
+        vec![
+            FieldValue::Character(Some("ab".to_owned())),
+            FieldValue::Character(Some("ab".to_owned())),
+            FieldValue::Character(None),
+        ]
+    );
+}
+
+#[test]
+fn test_field_byte_range() {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let mut record = Record::default();
+    record.insert(
+        "Name".to_owned(),
+        FieldValue::Character(Some("Yoshi".to_owned())),
+    );
+    record.insert(
+        "Town".to_owned(),
+        FieldValue::Character(Some("Paris".to_owned())),
+    );
+    record.insert("Vegan".to_owned(), FieldValue::Logical(Some(true)));
+    TableWriterBuilder::new()
+        .add_character_field("Name".try_into().unwrap(), 10)
+        .add_character_field("Town".try_into().unwrap(), 6)
+        .add_logical_field("Vegan".try_into().unwrap())
+        .build_with_dest(&mut cursor)
+        .write_records(&[record])
+        .unwrap();
+    let bytes = cursor.get_ref().clone();
+    cursor.set_position(0);
+    let reader = Reader::new(cursor).unwrap();
+
+    assert_eq!(reader.field_byte_range("Name"), Some(1..11));
+    let town_range = reader.field_byte_range("Town").unwrap();
+    assert_eq!(town_range, 11..17);
+    assert_eq!(reader.field_byte_range("Vegan"), Some(17..18));
+    assert_eq!(reader.field_byte_range("Height"), None);
+    assert_eq!(reader.field_byte_range("DeletionFlag"), None);
+
+    let record_start = reader.header().offset_to_first_record as usize;
+    let town = &bytes[record_start + town_range.start..record_start + town_range.end];
+    assert_eq!(town, b"Paris ");
+}
+
+#[test]
+fn test_numeric_rounding() {
+    let write_values = |num_decimals: u8, values: &[f64]| {
+        let mut cursor = Cursor::new(Vec::<u8>::new());
+        let records = values
+            .iter()
+            .map(|&value| {
+                let mut record = Record::default();
+                record.insert("Value".to_owned(), FieldValue::Numeric(Some(value)));
+                record
+            })
+            .collect::<Vec<_>>();
+        TableWriterBuilder::new()
+            .add_numeric_field("Value".try_into().unwrap(), 8, num_decimals)
+            .build_with_dest(&mut cursor)
+            .write_records(&records)
+            .unwrap();
+
+        let bytes = cursor.into_inner();
+        let first_record = offset_to_first_record(&bytes);
+        (0..values.len())
+            .map(|i| {
+                let start = first_record + i * 9 + 1;
+                String::from_utf8(bytes[start..start + 8].to_vec())
+                    .unwrap()
+                    .trim_start()
+                    .to_owned()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        write_values(2, &[12.345, 12.3456, 12.344, -12.345, 9.999, 3.0, -0.001]),
+        vec!["12.35", "12.35", "12.34", "-12.35", "10.00", "3.00", "0.00"]
+    );
+    assert_eq!(write_values(0, &[32.0, 2.5, -7.49]), vec!["32", "3", "-7"]);
+    // The number of decimals is kept, values that do not fit with them overflow
+    assert_eq!(write_values(2, &[123456.789]), vec!["********"]);
+}
+
+#[test]
+fn test_numeric_field_width() {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let records = [1234.5, -0.5, 12345678901.0]
+        .iter()
+        .map(|&value| {
+            let mut record = Record::default();
+            record.insert("Value".to_owned(), FieldValue::Numeric(Some(value)));
+            record
+        })
+        .collect::<Vec<_>>();
+    TableWriterBuilder::new()
+        .add_numeric_field("Value".try_into().unwrap(), 10, 2)
+        .build_with_dest(&mut cursor)
+        .write_records(&records)
+        .unwrap();
+
+    let bytes = cursor.get_ref();
+    let first_record = offset_to_first_record(bytes);
+    let values = (0..3)
+        .map(|i| {
+            let start = first_record + i * 11 + 1;
+            String::from_utf8(bytes[start..start + 10].to_vec()).unwrap()
+        })
+        .collect::<Vec<_>>();
+    // The last value does not fit, even without its decimals
+    assert_eq!(values, vec!["   1234.50", "     -0.50", "**********"]);
+
+    cursor.set_position(0);
+    let read_values = Reader::new(cursor)
+        .unwrap()
+        .read()
+        .unwrap()
+        .into_iter()
+        .map(|record| record.get("Value").unwrap().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        read_values,
+        vec![
+            FieldValue::Numeric(Some(1234.5)),
+            FieldValue::Numeric(Some(-0.5)),
+            FieldValue::Numeric(None)
+        ]
+    );
+}
+
+#[test]
+fn test_write_integral_numeric_without_decimals() {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let records = [123.0, -4.0, 99.6]
+        .iter()
+        .map(|&value| {
+            let mut record = Record::default();
+            record.insert("Value".to_owned(), FieldValue::Numeric(Some(value)));
+            record
+        })
+        .collect::<Vec<_>>();
+    TableWriterBuilder::new()
+        .add_numeric_field("Value".try_into().unwrap(), 5, 0)
+        .build_with_dest(&mut cursor)
+        .write_records(&records)
+        .unwrap();
+
+    let bytes = cursor.into_inner();
+    let first_record = offset_to_first_record(&bytes);
+    let values = (0..3)
+        .map(|i| {
+            let start = first_record + i * 6 + 1;
+            String::from_utf8(bytes[start..start + 5].to_vec()).unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(values, vec!["  123", "   -4", "  100"]);
+}
+
+#[test]
+fn test_new_with_memo() {
+    // Simulates the entries of an archive, which are not files on disk
+    let dbf = Cursor::new(std::fs::read(SHARED_MEMO_DBF).unwrap());
+    let fpt = Cursor::new(std::fs::read(SHARED_MEMO_FPT).unwrap());
+    let mut reader = Reader::new_with_memo(dbf, fpt, MemoFileType::FoxBaseMemo).unwrap();
+    let notes = reader
+        .read()
+        .unwrap()
+        .into_iter()
+        .map(|record| record.get("NOTES").cloned().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(notes[1], FieldValue::Memo("Alice notes".to_owned()));
+    // Memo blocks can be shared between records
+    assert_eq!(notes[0], notes[2]);
+}
+
+#[test]
+fn test_reencode() {
+    let mut reader = Reader::from_path(CP850_DBF).unwrap();
+    assert_eq!(reader.header().code_page(), Some(850));
+    let mut out = Cursor::new(Vec::<u8>::new());
+    reader.reencode(&mut out, LanguageDriver::Cp1252).unwrap();
+
+    let bytes = out.into_inner();
+    let reencoded = Reader::new(Cursor::new(bytes.clone())).unwrap();
+    assert_eq!(reencoded.header().code_page(), Some(1252));
+    assert_eq!(reencoded.header().num_records, 3);
+    let driver = LanguageDriver::from_code_page(1252).unwrap();
+    let offset = reencoded.header().offset_to_first_record as usize;
+    let size_of_record = reencoded.header().size_of_record as usize;
+    let name_range = reencoded.field_byte_range("NAME").unwrap();
+    let city_range = reencoded.field_byte_range("CITY").unwrap();
+    let texts = (0..3)
+        .map(|i| {
+            let record = &bytes[offset + i * size_of_record..][..size_of_record];
+            (
+                driver
+                    .decode(&record[name_range.clone()])
+                    .trim_end()
+                    .to_owned(),
+                driver
+                    .decode(&record[city_range.clone()])
+                    .trim_end()
+                    .to_owned(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        texts,
+        vec![
+            ("François".to_owned(), "Besançon".to_owned()),
+            ("Jürgen".to_owned(), "Köln".to_owned()),
+            ("Ñuño".to_owned(), "Málaga".to_owned()),
+        ]
+    );
+    assert_eq!(bytes.last(), Some(&0x1A));
+
+    // The reader can still be used after
+    assert_eq!(reader.read().unwrap().len(), 3);
+}
+
+#[test]
+fn test_reencode_rejects_memo_fields() {
+    let mut reader = Reader::from_path(DBASE3_MEMO_DBF).unwrap();
+    let mut out = Cursor::new(Vec::<u8>::new());
+    let error = reader
+        .reencode(&mut out, LanguageDriver::Cp1252)
+        .unwrap_err();
+    assert_eq!(
+        error.field().as_ref().unwrap().field_type(),
+        FieldType::Memo
+    );
+    assert!(out.get_ref().is_empty());
+}
+
+#[test]
+fn test_fox_pro_memo_zero_block_size() {
+    let mut memo_reader =
+        MemoReader::from_path(ZERO_BLOCK_SIZE_FPT, MemoFileType::FoxBaseMemo).unwrap();
+    assert_eq!(memo_reader.block_size(), 64);
+    assert_eq!(memo_reader.read_data_at(8).unwrap(), b"Shared notes");
+    assert_eq!(memo_reader.read_data_at(10).unwrap(), b"Bob notes");
+}
+
+#[test]
+fn test_debug_record() {
+    let mut reader = Reader::from_path(SHARED_MEMO_DBF).unwrap();
+    let dump = reader.debug_record(1).unwrap();
+    assert!(dump.contains("(not deleted)"));
+    for name in ["NAME", "NOTES"] {
+        assert!(dump.contains(name));
+    }
+    assert!(dump.contains("Memo(\"Alice notes\")"));
+    let num_records = reader.header().num_records;
+    assert!(reader.debug_record(num_records).is_err());
+
+    // The reader was not moved
+    assert_eq!(reader.read().unwrap().len(), num_records as usize);
+}
+
+#[test]
+fn test_time_try_new() {
+    let time = Time::try_new(23, 59, 59).unwrap();
+    assert_eq!((time.hours(), time.minutes(), time.seconds()), (23, 59, 59));
+    assert_eq!(time, Time::new(23, 59, 59));
+
+    for (hours, minutes, seconds) in [(24, 0, 0), (0, 60, 0), (0, 0, 60)] {
+        let error = Time::try_new(hours, minutes, seconds).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidTime));
+    }
+}
+
+#[test]
+fn test_integer_checked() {
+    assert_eq!(
+        FieldValue::integer_checked(-7).unwrap(),
+        FieldValue::Integer(Some(-7))
+    );
+    let too_big = i64::from(i32::MAX) + 1;
+    let error = FieldValue::integer_checked(too_big).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::IntegerOutOfRange(v) if *v == too_big));
+    assert!(FieldValue::integer_checked(i64::from(i32::MIN) - 1).is_err());
+}
+
+#[test]
+fn test_records_with_schema() {
+    // Only needs what is yielded by the iterator
+    fn describe(schema: &[dbase::FieldInfo], record: &Record, name: &str) -> String {
+        let info = schema.iter().find(|info| info.name() == name).unwrap();
+        format!("{:?}={:?}", info.field_type(), record.get(name).unwrap())
+    }
+
+    let mut reader = Reader::from_path(SHARED_MEMO_DBF).unwrap();
+    let records = reader
+        .records_with_schema()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(records.len(), reader.header().num_records as usize);
+
+    let (schema, record) = &records[1];
+    assert!(schema.iter().all(|info| info.name() != "DeletionFlag"));
+    assert_eq!(
+        describe(schema, record, "NOTES"),
+        "Memo=Memo(\"Alice notes\")"
+    );
+    assert!(std::sync::Arc::ptr_eq(schema, &records[0].0));
+}
+
+#[test]
+fn test_read_table_without_fields() {
+    let mut reader = Reader::from_path(NO_FIELDS_DBF).unwrap();
+    assert_eq!(reader.header().num_records, 3);
+    assert_eq!(reader.header().size_of_record, 1);
+    assert!(reader
+        .fields()
+        .iter()
+        .all(|info| info.name() == "DeletionFlag"));
+    let records = reader.iter_all().collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(records.iter().all(|record| record.as_ref().is_empty()));
+    let flags = records.iter().map(Record::is_deleted).collect::<Vec<_>>();
+    assert_eq!(flags, vec![false, true, false]);
+    assert!(reader.is_deleted(1).unwrap());
+
+    // Some writers do not count the deletion flag in the record size
+    let mut bytes = std::fs::read(NO_FIELDS_DBF).unwrap();
+    bytes[10] = 0;
+    let options = ReadingOptions::default().cap_record_count(true);
+    let mut reader = Reader::new_with_options(Cursor::new(bytes), options).unwrap();
+    assert_eq!(reader.header().size_of_record, 1);
+    assert_eq!(reader.iter_all().count(), 3);
+}
+
+#[test]
+fn test_record_content_hash() {
+    let make_record = |name: &str, count: f64| {
+        let mut record = Record::default();
+        record.insert(
+            "Name".to_owned(),
+            FieldValue::Character(Some(name.to_owned())),
+        );
+        record.insert("Count".to_owned(), FieldValue::Numeric(Some(count)));
+        record.insert("Valid".to_owned(), FieldValue::Logical(Some(true)));
+        record
+    };
+    let hash = make_record("Ferris", 3.0).content_hash();
+    assert_eq!(make_record("Ferris    ", 3.0).content_hash(), hash);
+    assert_ne!(make_record("Ferri", 3.0).content_hash(), hash);
+    assert_ne!(make_record("Ferris", 4.0).content_hash(), hash);
+    // The hash does not change between runs
+    assert_eq!(hash, 9043252483418699587);
+
+    let mut empty = Record::default();
+    empty.insert("Name".to_owned(), FieldValue::Character(None));
+    let mut blank = Record::default();
+    blank.insert(
+        "Name".to_owned(),
+        FieldValue::Character(Some("   ".to_owned())),
+    );
+    assert_eq!(empty.content_hash(), blank.content_hash());
+}
+
+#[test]
+fn test_pack_memo_keeps_block_type() {
+    let mut reader = Reader::from_path(GENERAL_DBF).unwrap();
+    let mut dbf = Cursor::new(Vec::<u8>::new());
+    let mut fpt = Cursor::new(Vec::<u8>::new());
+    reader.pack_memo(&mut dbf, &mut fpt).unwrap();
+
+    // The OLE object keeps the "picture" type (0) of its block, the first one after the header
+    let original = std::fs::read("./tests/data/general.fpt").unwrap();
+    assert_eq!(original[512..516], [0, 0, 0, 0]);
+    assert_eq!(fpt.get_ref()[512..516], [0, 0, 0, 0]);
+
+    dbf.set_position(0);
+    fpt.set_position(0);
+    let mut packed = Reader::new_with_memo(dbf, fpt, MemoFileType::FoxBaseMemo).unwrap();
+    reader.seek(0).unwrap();
+    assert_eq!(packed.read().unwrap(), reader.read().unwrap());
+}
+
+#[test]
+fn test_read_general_field() {
+    let mut reader = Reader::from_path(GENERAL_DBF).unwrap();
+    assert_eq!(format!("{:?}", reader.fields()[2].field_type()), "General");
+    let records = reader.read().unwrap();
+    // The bytes are read as is, even the trailing zeros
+    assert_eq!(
+        records[0].get("PICTURE"),
+        Some(&FieldValue::General(
+            b"\x15\x1c\xff\xfeOLE\x00\x00".to_vec()
+        ))
+    );
+    assert_eq!(
+        records[1].get("PICTURE"),
+        Some(&FieldValue::General(vec![]))
+    );
+}
+
+#[test]
+fn test_write_general_field() {
+    let mut reader = Reader::from_path(GENERAL_DBF).unwrap();
+    let records = reader.read().unwrap();
+    let mut dbf = Cursor::new(Vec::<u8>::new());
+    let mut fpt = Cursor::new(Vec::<u8>::new());
+    {
+        let memo_writer = MemoWriter::new(MemoFileType::FoxBaseMemo, &mut fpt).unwrap();
+        TableWriterBuilder::from_table_info(reader.into_table_info())
+            .build_with_dest_and_memo(&mut dbf, memo_writer)
+            .write_records(&records)
+            .unwrap();
+    }
+    // The OLE object is written with the "picture" type (0), not the text one
+    assert_eq!(fpt.get_ref()[512..516], [0, 0, 0, 0]);
+
+    dbf.set_position(0);
+    fpt.set_position(0);
+    let mut reader = Reader::new_with_memo(dbf, fpt, MemoFileType::FoxBaseMemo).unwrap();
+    assert_eq!(reader.read().unwrap(), records);
+}
+
+#[test]
+fn test_read_picture_field() {
+    let mut reader = Reader::from_path(PICTURE_DBF).unwrap();
+    assert_eq!(format!("{:?}", reader.fields()[2].field_type()), "Picture");
+    let records = reader.read().unwrap();
+    // The raw bytes of the image, even the ones that would end a memo text
+    assert_eq!(
+        records[0].get("IMAGE"),
+        Some(&FieldValue::Picture(
+            b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR\x00\x00\x00\x01".to_vec()
+        ))
+    );
+    assert_eq!(records[1].get("IMAGE"), Some(&FieldValue::Picture(vec![])));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_conversions() {
+    let naive_date = chrono::NaiveDate::from_ymd_opt(2021, 11, 3).unwrap();
+    let date = Date::try_from(naive_date).unwrap();
+    assert_eq!(date, Date::new(3, 11, 2021));
+    assert_eq!(chrono::NaiveDate::try_from(date).unwrap(), naive_date);
+    let error = chrono::NaiveDate::try_from(Date::new(30, 2, 2021)).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidDate));
+
+    let naive_datetime = naive_date.and_hms_opt(8, 15, 42).unwrap();
+    let datetime = DateTime::try_from(naive_datetime).unwrap();
+    assert_eq!(
+        datetime,
+        DateTime::new(Date::new(3, 11, 2021), Time::new(8, 15, 42))
+    );
+    assert_eq!(
+        chrono::NaiveDateTime::try_from(datetime).unwrap(),
+        naive_datetime
+    );
+    let error = chrono::NaiveDateTime::try_from(DateTime::new(
+        Date::new(31, 4, 2021),
+        Time::new(8, 15, 42),
+    ))
+    .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidDate));
+
+    let error = Date::try_from(chrono::NaiveDate::from_ymd_opt(-1, 1, 1).unwrap()).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidDate));
+
+    // The date of the last update of the header is the current one,
+    // the test may run across midnight
+    let today = chrono::Utc::now().date_naive();
+    let cursor = write_empty_table(
+        TableWriterBuilder::new().add_character_field("Name".try_into().unwrap(), 10),
+    );
+    let reader = Reader::new(cursor).unwrap();
+    let last_update = chrono::NaiveDate::try_from(reader.header().last_update).unwrap();
+    assert!(last_update == today || last_update == today.succ_opt().unwrap());
+}
+
+/// Record whose Character field is already encoded
+struct EncodedName(Vec<u8>);
+
+impl WritableRecord for EncodedName {
+    fn write_using<'a, W: Write>(
+        &self,
+        field_writer: &mut FieldWriter<'a, W>,
+    ) -> Result<(), FieldIOError> {
+        field_writer.write_next_field_value(&self.0.as_slice())
+    }
+}
+
+#[test]
+fn test_write_raw_character_bytes() {
+    let names = [
+        EncodedName(LanguageDriver::Cp1252.encode("Café")),
+        // Longer than the field, it is cropped
+        EncodedName(b"\xC9l\xE9onore Dupont".to_vec()),
+    ];
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::new()
+        .add_character_field("Name".try_into().unwrap(), 8)
+        .build_with_dest(&mut cursor)
+        .write_records(&names)
+        .unwrap();
+
+    let bytes = cursor.into_inner();
+    let reader = Reader::new(Cursor::new(bytes.clone())).unwrap();
+    let offset = reader.header().offset_to_first_record as usize;
+    let size_of_record = reader.header().size_of_record as usize;
+    let range = reader.field_byte_range("Name").unwrap();
+    let first = &bytes[offset..][range.clone()];
+    let second = &bytes[offset + size_of_record..][range];
+    assert_eq!(first, b"Caf\xE9    ");
+    assert_eq!(second, b"\xC9l\xE9onore");
+}
+
+#[test]
+fn test_header_flags() {
+    let table_info = Reader::from_path(PENDING_TRANSACTION_DBF)
+        .unwrap()
+        .into_table_info();
+    assert!(table_info.is_transaction_incomplete());
+    assert_eq!(table_info.reserved_bytes(), [1, 2]);
+    assert_eq!(
+        table_info.multi_user_bytes(),
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]
+    );
+    assert_eq!(table_info.fields()[1].name(), "NAME");
+
+    // The bytes are kept when writing a table with the same structure
+    let cursor = write_empty_table(TableWriterBuilder::from_table_info(table_info));
+    assert_eq!(&cursor.get_ref()[12..14], &[1, 2]);
+    assert_eq!(cursor.get_ref()[16], 1);
+
+    let table_info = Reader::from_path(LINE_DBF).unwrap().into_table_info();
+    assert!(!table_info.is_transaction_incomplete());
+}
+
+#[test]
+fn test_field_types() {
+    let reader = Reader::from_path(NULL_BINARY_FIELDS_DBF).unwrap();
+    // The _NullFlags field is not part of the types
+    let expected = [
+        FieldType::Integer,
+        FieldType::Currency,
+        FieldType::Double,
+        FieldType::DateTime,
+    ];
+    assert_eq!(reader.field_types(), expected.iter().copied().collect());
+
+    let reader = Reader::from_path(STATIONS_DBF).unwrap();
+    assert_eq!(
+        reader.field_types(),
+        std::iter::once(FieldType::Character).collect()
+    );
+}
+
+#[test]
+fn test_field_info_accessors() {
+    let reader = Reader::from_path(NULL_BINARY_FIELDS_DBF).unwrap();
+    let field = |name: &str| {
+        reader
+            .fields()
+            .iter()
+            .find(|info| info.name() == name)
+            .unwrap()
+    };
+
+    let ratio = field("RATIO");
+    assert_eq!(ratio.field_type(), FieldType::Double);
+    assert_eq!(ratio.length(), 8);
+    assert_eq!(ratio.num_decimal_places(), 2);
+    assert!(ratio.flags().is_nullable());
+    assert!(!ratio.flags().is_system());
+
+    assert!(!field("ID").flags().is_nullable());
+
+    let null_flags = field("_NullFlags");
+    assert_eq!(null_flags.field_type(), FieldType::NullFlags);
+    assert_eq!(null_flags.flags().bits(), 0x05);
+    assert!(null_flags.flags().is_system());
+    assert!(null_flags.flags().is_binary());
+}
+
+#[test]
+fn test_append_records() {
+    let path = std::env::temp_dir().join("dbase_test_append_records.dbf");
+    std::fs::copy(PEOPLE_DBF, &path).unwrap();
+    let mut records = dbase::read(PEOPLE_DBF).unwrap();
+
+    let person = |name: &str, age: f64| {
+        let mut record = Record::default();
+        record.insert("NAME".to_owned(), FieldValue::from(name.to_owned()));
+        record.insert("AGE".to_owned(), FieldValue::from(age));
+        record
+    };
+    let new_records = vec![person("Jade", 29.0), person("Kim", 41.0)];
+    let writer = TableWriter::append(&path).unwrap();
+    writer.write_records(&new_records).unwrap();
+
+    records.extend(new_records);
+    let mut reader = Reader::from_path(&path).unwrap();
+    assert_eq!(reader.header().num_records, 14);
+    assert_eq!(reader.read().unwrap(), records);
+    let bytes = std::fs::read(&path).unwrap();
+    assert_eq!(bytes.last(), Some(&0x1A));
+
+    // The records must have the fields of the table
+    let mut writer = TableWriter::append(&path).unwrap();
+    let mut record = Record::default();
+    record.insert("NAME".to_owned(), FieldValue::from("Lea".to_owned()));
+    record.insert("BIRTH".to_owned(), FieldValue::from(Date::new(1, 2, 2003)));
+    assert!(writer.write_record(&record).is_err());
+    drop(writer);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_append_checks_file_length() {
+    let path = std::env::temp_dir().join("dbase_test_append_checks_file_length.dbf");
+
+    // The header claims more records than the file has
+    std::fs::copy(INFLATED_RECORD_COUNT_DBF, &path).unwrap();
+    let error = TableWriter::append(&path).err().unwrap();
+    assert!(matches!(error.kind(), ErrorKind::FileLengthMismatch { .. }));
+    let len = std::fs::metadata(INFLATED_RECORD_COUNT_DBF).unwrap().len();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), len);
+
+    // The header claims fewer records than the file has
+    let mut bytes = std::fs::read(PEOPLE_DBF).unwrap();
+    bytes[4] -= 1;
+    std::fs::write(&path, &bytes).unwrap();
+    let error = TableWriter::append(&path).err().unwrap();
+    assert!(matches!(error.kind(), ErrorKind::FileLengthMismatch { .. }));
+    assert_eq!(std::fs::read(&path).unwrap(), bytes);
+
+    // A record that fails leaves nothing behind
+    std::fs::copy(PEOPLE_DBF, &path).unwrap();
+    let mut writer = TableWriter::append(&path).unwrap();
+    let mut record = Record::default();
+    record.insert("NAME".to_owned(), FieldValue::from("Lea".to_owned()));
+    assert!(writer.write_record(&record).is_err());
+    writer.finish().unwrap();
+    let mut reader = Reader::from_path(&path).unwrap();
+    assert_eq!(reader.read().unwrap(), dbase::read(PEOPLE_DBF).unwrap());
+    assert_eq!(
+        std::fs::metadata(&path).unwrap().len(),
+        std::fs::metadata(PEOPLE_DBF).unwrap().len()
+    );
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_julian_day_offset() {
+    let launch = |options: ReadingOptions| {
+        let mut reader = Reader::from_path_with_options(SHIFTED_DATETIME_DBF, options).unwrap();
+        match reader.read().unwrap()[0].get("WHEN") {
+            Some(FieldValue::DateTime(Some(datetime))) => *datetime,
+            value => panic!("Unexpected value {:?}", value),
+        }
+    };
+    // The exporter wrote the day after
+    let datetime = launch(ReadingOptions::default());
+    assert_eq!(datetime.date(), Date::new(4, 11, 2021));
+
+    let datetime = launch(ReadingOptions::default().julian_day_offset(-1));
+    assert_eq!(datetime.date(), Date::new(3, 11, 2021));
+    assert_eq!(datetime.time(), Time::new(8, 15, 42));
+
+    // An offset that moves the date out of range is an error, not a panic
+    let mut reader = Reader::from_path_with_options(
+        SHIFTED_DATETIME_DBF,
+        ReadingOptions::default().julian_day_offset(i32::MIN),
+    )
+    .unwrap();
+    let error = reader.read().unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::InvalidDate));
+
+    // Empty date times stay empty
+    let mut bytes = std::fs::read(SHIFTED_DATETIME_DBF).unwrap();
+    let first_record = usize::from(reader.header().offset_to_first_record);
+    // After the deletion flag and the 10 bytes of the name
+    bytes[first_record + 11..first_record + 19].copy_from_slice(&[0u8; 8]);
+    let options = ReadingOptions::default().julian_day_offset(-1);
+    let records = Reader::new_with_options(Cursor::new(bytes), options)
+        .unwrap()
+        .read()
+        .unwrap();
+    assert_eq!(records[0].get("WHEN"), Some(&FieldValue::DateTime(None)));
+}
+
+#[test]
+fn test_memo_file_type_from_version() {
+    // dBase III, 0x83
+    let mut reader = Reader::from_path(DBASE3_MEMO_DBF).unwrap();
+    assert_eq!(reader.memo_file_type(), Some(MemoFileType::DbaseMemo));
+    let records = reader.read().unwrap();
+    assert_eq!(
+        records[1].get("NOTES"),
+        Some(&FieldValue::Memo("Alice notes".to_owned()))
+    );
+
+    // Visual FoxPro, 0x30
+    let reader = Reader::from_path(SHARED_MEMO_DBF).unwrap();
+    assert_eq!(reader.memo_file_type(), Some(MemoFileType::FoxBaseMemo));
+
+    let memo_type = |byte: u8| Version::from(byte).supported_memo_type();
+    assert_eq!(memo_type(0x83), Some(MemoFileType::DbaseMemo));
+    assert_eq!(memo_type(0x8B), Some(MemoFileType::DbaseMemo4));
+    assert_eq!(memo_type(0x30), Some(MemoFileType::FoxBaseMemo));
+    assert_eq!(memo_type(0xF5), Some(MemoFileType::FoxBaseMemo));
+    assert_eq!(memo_type(0x03), None);
+
+    // No memo file is read for files without memo fields
+    let reader = Reader::from_path(LINE_DBF).unwrap();
+    assert_eq!(reader.memo_file_type(), None);
+}
+
+#[test]
+fn test_borrowing_conversions() {
+    use dbase::FieldConversionError;
+
+    let value = FieldValue::Character(Some("Ferris".to_owned()));
+    let name = <&str>::try_from(&value).unwrap();
+    // The str is the one stored in the value, it was not cloned
+    match &value {
+        FieldValue::Character(Some(string)) => assert_eq!(name.as_ptr(), string.as_ptr()),
+        _ => unreachable!(),
+    }
+    assert_eq!(Option::<&str>::try_from(&value).unwrap(), Some("Ferris"));
+    assert!(matches!(
+        f64::try_from(&value),
+        Err(FieldConversionError::IncompatibleType)
+    ));
+
+    let none = FieldValue::Character(None);
+    assert!(matches!(
+        <&str>::try_from(&none),
+        Err(FieldConversionError::NoneValue)
+    ));
+    assert_eq!(Option::<&str>::try_from(&none).unwrap(), None);
+
+    let value = FieldValue::Numeric(Some(12.5));
+    assert_eq!(f64::try_from(&value).unwrap(), 12.5);
+    assert!(matches!(
+        i32::try_from(&value),
+        Err(FieldConversionError::FieldTypeNotAsExpected { .. })
+    ));
+    assert!(bool::try_from(&FieldValue::Logical(Some(true))).unwrap());
+    assert_eq!(
+        <&[u8]>::try_from(&FieldValue::General(vec![1, 2])).unwrap(),
+        &[1, 2]
+    );
+}
+
+#[test]
+fn test_header_accessors() {
+    let reader = Reader::from_path(LINE_DBF).unwrap();
+    let header = reader.header();
+    assert_eq!(header.version(), 0x03);
+    assert_eq!(header.last_updated(), Date::new(2, 9, 2018));
+    assert_eq!(header.num_records, 1);
+    assert_eq!(header.header_size(), 65);
+    assert_eq!(header.record_size(), 51);
+    assert_eq!(header.table_flags.bits(), 0);
+
+    let reader = Reader::from_path(INDEXED_DBF).unwrap();
+    assert_eq!(reader.header().version(), 0x30);
+    assert_eq!(reader.header().table_flags.bits(), 0x01);
+
+    // The version byte is kept even when it is an alias
+    let mut bytes = std::fs::read(LINE_DBF).unwrap();
+    bytes[0] = 0x63;
+    let reader = Reader::new(Cursor::new(bytes)).unwrap();
+    assert!(matches!(
+        reader.header().file_type,
+        Version::DBase4 {
+            supports_memo: false
+        }
+    ));
+    assert_eq!(reader.header().version(), 0x63);
+}
+
+#[test]
+fn test_written_header_integers() {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field(FieldName::try_from("Name").unwrap(), 10)
+        .add_numeric_field(FieldName::try_from("Age").unwrap(), 3, 0)
+        .build_with_dest(&mut cursor);
+    let record = |name: &str, age: f64| {
+        let mut record = Record::default();
+        record.insert("Name".to_owned(), FieldValue::from(name.to_owned()));
+        record.insert("Age".to_owned(), FieldValue::from(age));
+        record
+    };
+    for i in 0..300 {
+        writer
+            .write_record(&record("Ferris", f64::from(i % 100)))
+            .unwrap();
+    }
+    writer.finish().unwrap();
+
+    // All little endian: record count on 4 bytes,
+    // header size and record size on 2 bytes
+    let bytes = cursor.into_inner();
+    assert_eq!(&bytes[4..8], &300u32.to_le_bytes());
+    assert_eq!(&bytes[8..10], &(32u16 + 2 * 32 + 1).to_le_bytes());
+    assert_eq!(&bytes[10..12], &(1u16 + 10 + 3).to_le_bytes());
+    assert_eq!(&bytes[12..32], &[0u8; 20]);
+    assert_eq!(bytes.len(), 97 + 300 * 14 + 1);
+}
+
+#[test]
+fn test_header_reserved_bytes_do_not_shift_fields() {
+    let bytes = std::fs::read(STATIONS_DBF).unwrap();
+    let mut reader = Reader::new(Cursor::new(bytes.clone())).unwrap();
+    let fields = reader.fields().to_vec();
+    let records = reader.read().unwrap();
+
+    // Fill all the reserved bytes of the header,
+    // the field descriptors still start at byte 32
+    let mut filled = bytes;
+    for i in (12..14).chain(16..28).chain(30..32) {
+        filled[i] = 0xFF;
+    }
+    let mut reader = Reader::new(Cursor::new(filled)).unwrap();
+    assert_eq!(reader.fields(), fields.as_slice());
+    assert_eq!(reader.read().unwrap(), records);
+}
+
+#[test]
+fn test_production_mdx_flag() {
+    let mut reader = Reader::from_path(PRODUCTION_MDX_DBF).unwrap();
+    let records = reader.read().unwrap();
+    let table_info = reader.into_table_info();
+    assert!(table_info.has_production_mdx());
+
+    // The flag is kept when rewriting the table
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    TableWriterBuilder::from_table_info(table_info)
+        .build_with_dest(&mut cursor)
+        .write_records(&records)
+        .unwrap();
+    assert_eq!(cursor.get_ref()[28], 0x01);
+    cursor.set_position(0);
+    let mut reader = Reader::new(cursor).unwrap();
+    assert_eq!(reader.read().unwrap(), records);
+    assert!(reader.into_table_info().has_production_mdx());
+
+    let table_info = Reader::from_path(LINE_DBF).unwrap().into_table_info();
+    assert!(!table_info.has_production_mdx());
+}
+
+#[test]
+fn test_read_general_field_spanning_many_blocks() {
+    // Binary data, with bytes that would end the text of a memo
+    let blob = (0..200u8).map(|i| i.wrapping_mul(7)).collect::<Vec<_>>();
+    assert!(blob.contains(&0x1A) && blob.contains(&0));
+
+    let mut fpt = vec![0u8; 512];
+    fpt[..4].copy_from_slice(&12u32.to_be_bytes());
+    fpt[6..8].copy_from_slice(&64u16.to_be_bytes());
+    // OLE objects are stored with the "picture" type (0)
+    fpt.extend_from_slice(&0u32.to_be_bytes());
+    fpt.extend_from_slice(&(blob.len() as u32).to_be_bytes());
+    fpt.extend_from_slice(&blob);
+    fpt.resize(12 * 64, 0);
+
+    let mut dbf = std::fs::read(GENERAL_DBF).unwrap();
+    let header_size = u16::from_le_bytes([dbf[8], dbf[9]]) as usize;
+    // The PICTURE field of the first record: after the deletion flag and the NAME field
+    dbf[header_size + 11..header_size + 15].copy_from_slice(&8u32.to_le_bytes());
+
+    let mut reader = Reader::new_with_memo(
+        Cursor::new(dbf),
+        Cursor::new(fpt),
+        MemoFileType::FoxBaseMemo,
+    )
+    .unwrap();
+    let records = reader.read().unwrap();
+    assert_eq!(records[0].get("PICTURE"), Some(&FieldValue::General(blob)));
+}
+
+#[test]
+fn test_field_value_conversions() {
+    use dbase::FieldConversionError;
+
+    let name = FieldValue::Character(Some("Ferris".to_owned()));
+    assert_eq!(String::try_from(name.clone()).unwrap(), "Ferris");
+    assert_eq!(
+        Option::<String>::try_from(name).unwrap(),
+        Some("Ferris".to_owned())
+    );
+    assert_eq!(f64::try_from(FieldValue::Numeric(Some(1.5))).unwrap(), 1.5);
+    assert_eq!(f32::try_from(FieldValue::Float(Some(2.5))).unwrap(), 2.5);
+    assert!(bool::try_from(FieldValue::Logical(Some(true))).unwrap());
+    assert_eq!(i32::try_from(FieldValue::Integer(Some(7))).unwrap(), 7);
+    assert_eq!(
+        Date::try_from(FieldValue::Date(Some(Date::new(1, 2, 2003)))).unwrap(),
+        Date::new(1, 2, 2003)
+    );
+    assert_eq!(
+        Vec::<u8>::try_from(FieldValue::General(vec![1, 2])).unwrap(),
+        vec![1, 2]
+    );
+
+    // The null path: an error for plain types, None for Option ones
+    assert!(matches!(
+        String::try_from(FieldValue::Character(None)),
+        Err(FieldConversionError::NoneValue)
+    ));
+    assert!(matches!(
+        f64::try_from(FieldValue::Numeric(None)),
+        Err(FieldConversionError::NoneValue)
+    ));
+    assert_eq!(
+        Option::<bool>::try_from(FieldValue::Logical(None)).unwrap(),
+        None
+    );
+    assert_eq!(
+        Option::<Date>::try_from(FieldValue::Date(None)).unwrap(),
+        None
+    );
+
+    // Mismatching variants
+    match i32::try_from(FieldValue::Logical(Some(true))) {
+        Err(FieldConversionError::FieldTypeNotAsExpected { expected, actual }) => {
+            assert_eq!(format!("{:?} {:?}", expected, actual), "Integer Logical");
+        }
+        result => panic!("Unexpected result {:?}", result),
+    }
+    assert!(Option::<String>::try_from(FieldValue::Integer(Some(1))).is_err());
+}
+
+#[test]
+fn test_read_with_encoding() {
+    fn read_names<T: Read + Seek>(mut reader: Reader<T>) -> Vec<String> {
+        reader
+            .read()
+            .unwrap()
+            .into_iter()
+            .map(|record| match record.get("NAME") {
+                Some(FieldValue::Character(Some(name))) => name.clone(),
+                value => panic!("Unexpected value {:?}", value),
+            })
+            .collect::<Vec<_>>()
+    }
+
+    let options = ReadingOptions::default().encoding(Encoding::CodePage(1252));
+    let reader = Reader::from_path_with_options(CP1252_NO_CODE_PAGE_DBF, options).unwrap();
+    // The last name is stored as UTF-8, it is not sniffed
+    assert_eq!(
+        read_names(reader),
+        vec!["Café Müller", "€uro \u{201C}quoted\u{201D}", "ZoÃ«"]
+    );
+
+    // The code page is read from the header of a file that declares it
+    let mut reencoded = Cursor::new(Vec::<u8>::new());
+    Reader::from_path(CP850_DBF)
+        .unwrap()
+        .reencode(&mut reencoded, LanguageDriver::Cp1252)
+        .unwrap();
+    reencoded.set_position(0);
+    let options = ReadingOptions::default().encoding(Encoding::FromHeader);
+    let reader = Reader::new_with_options(reencoded, options).unwrap();
+    assert_eq!(reader.header().code_page(), Some(1252));
+    assert_eq!(read_names(reader), vec!["François", "Jürgen", "Ñuño"]);
+
+    let reader = Reader::from_path_with_options(CP850_DBF, options).unwrap();
+    assert_eq!(read_names(reader), vec!["François", "Jürgen", "Ñuño"]);
+
+    // The default is still UTF-8
+    let reader = Reader::from_path(CP850_DBF).unwrap();
+    assert_eq!(read_names(reader)[0], "Fran\u{FFFD}ois");
+
+    let reader = Reader::from_path_with_options(CP1250_DBF, options).unwrap();
+    let names = read_names(reader);
+    if cfg!(feature = "encoding") {
+        assert_eq!(names, vec!["Łódź", "Žluťoučký kůň"]);
+    } else {
+        assert_eq!(names[0], "\u{FFFD}\u{FFFD}d\u{FFFD}");
+    }
+}
+
+#[test]
+fn test_record_builder() {
+    let schema = empty_table_fields(
+        TableWriterBuilder::new()
+            .add_character_field("Name".try_into().unwrap(), 10)
+            .add_numeric_field("Age".try_into().unwrap(), 3, 0)
+            .add_logical_field("Active".try_into().unwrap())
+            .add_date_field("Joined".try_into().unwrap()),
+    );
+
+    let record = RecordBuilder::new(&schema)
+        .set("Name", "Yoshi".to_owned())
+        .set("Active", true)
+        .build()
+        .unwrap();
+    assert_eq!(
+        record.get("Name"),
+        Some(&FieldValue::Character(Some("Yoshi".to_owned())))
+    );
+    assert_eq!(record.get("Active"), Some(&FieldValue::Logical(Some(true))));
+    assert_eq!(record.get("Age"), Some(&FieldValue::Numeric(None)));
+    assert_eq!(record.get("Joined"), Some(&FieldValue::Date(None)));
+
+    let error = RecordBuilder::new(&schema)
+        .set("Name", "Yoshimitsu the Great".to_owned())
+        .build()
+        .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::ValueTooLong));
+
+    let schema = empty_table_fields(
+        TableWriterBuilder::new()
+            .add_character_field("Name".try_into().unwrap(), 10)
+            .add_integer_field("Count".try_into().unwrap()),
+    );
+
+    // Integer fields cannot be blank
+    let error = RecordBuilder::new(&schema)
+        .set("Name", "Yoshi".to_owned())
+        .build()
+        .unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::NotEnoughFields));
+    assert_eq!(error.field().as_ref().unwrap().name(), "Count");
+    RecordBuilder::new(&schema)
+        .set("Count", FieldValue::Integer(Some(3)))
+        .build()
+        .unwrap();
+}
+
+#[test]
+fn test_read_truncated_memo_file() {
+    let mut reader = Reader::from_path(TRUNCATED_MEMO_DBF).unwrap();
+    let results = reader.iter_records().collect::<Vec<_>>();
+    assert_eq!(results.len(), 3);
+    assert_eq!(
+        results[0].as_ref().unwrap().get("NOTES"),
+        Some(&FieldValue::Memo("Intact note".to_owned()))
+    );
+
+    // The data of the block is cut
+    let error = results[1].as_ref().unwrap_err();
+    assert_eq!(error.field().as_ref().unwrap().name(), "NOTES");
+    match error.kind() {
+        ErrorKind::TruncatedMemoFile {
+            index,
+            needed_offset,
+            file_len,
+        } => assert_eq!((*index, *needed_offset, *file_len), (9, 684, 587)),
+        kind => panic!("Unexpected error kind {:?}", kind),
+    }
+
+    // The block is past the end of the file
+    match results[2].as_ref().unwrap_err().kind() {
+        ErrorKind::TruncatedMemoFile {
+            index,
+            needed_offset,
+            file_len,
+        } => assert_eq!((*index, *needed_offset, *file_len), (30, 1928, 587)),
+        kind => panic!("Unexpected error kind {:?}", kind),
+    }
+
+    let error = reader.collect_memos().unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::TruncatedMemoFile { index: 9, .. }
+    ));
+}
+
+#[test]
+fn test_read_record_at_index() {
+    let mut reader = Reader::from_path(STATIONS_DBF).unwrap();
+    let records = reader.read().unwrap();
+
+    let mut reader = Reader::from_path(STATIONS_DBF).unwrap();
+    assert_eq!(reader.record(3).unwrap(), records[3]);
+    assert_eq!(reader.record(0).unwrap(), records[0]);
+    // The position of the reader is not changed
+    assert_eq!(reader.iter_records().next().unwrap().unwrap(), records[0]);
+
+    let error = reader.record(records.len()).unwrap_err();
+    assert_eq!(error.record_num(), records.len());
+}
+
+#[test]
+fn test_ndx_index() {
+    let mut reader = Reader::from_path(PEOPLE_DBF).unwrap();
+
+    let index = NdxIndex::open(PEOPLE_NAME_NDX).unwrap();
+    assert_eq!(index.key_type(), NdxKeyType::Character);
+    assert_eq!(index.key_length(), 10);
+    assert_eq!(index.key_expression(), "NAME");
+    assert_eq!(index.root_page(), 5);
+    assert_eq!(index.next_free_page(), 6);
+    assert!(!index.is_unique());
+    // The keys equal to "Paul" are spread over two leaves
+    let paul = FieldValue::Character(Some("Paul".to_owned()));
+    let found = index.find(&paul).unwrap();
+    assert_eq!(found, vec![0, 4, 8]);
+    for record_index in found {
+        assert_eq!(
+            reader.record(record_index).unwrap().get("NAME"),
+            Some(&paul)
+        );
+    }
+    let zoe = index
+        .find(&FieldValue::Character(Some("Zoe".to_owned())))
+        .unwrap();
+    assert_eq!(zoe, vec![2]);
+    let pau = index
+        .find(&FieldValue::Character(Some("Pau".to_owned())))
+        .unwrap();
+    assert!(pau.is_empty());
+    let error = index.find(&FieldValue::Numeric(Some(27.0))).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::IncompatibleType));
+
+    let index = NdxIndex::open(PEOPLE_AGE_NDX).unwrap();
+    assert_eq!(index.key_type(), NdxKeyType::Numeric);
+    assert_eq!(index.key_expression(), "AGE");
+    assert_eq!(
+        index.find(&FieldValue::Numeric(Some(27.0))).unwrap(),
+        vec![1, 5, 9]
+    );
+    assert_eq!(
+        index.find(&FieldValue::Numeric(Some(70.0))).unwrap(),
+        vec![10]
+    );
+    assert_eq!(
+        index.find(&FieldValue::Numeric(Some(8.0))).unwrap(),
+        vec![8]
+    );
+    assert!(index
+        .find(&FieldValue::Numeric(Some(28.0)))
+        .unwrap()
+        .is_empty());
+
+    let error = NdxIndex::open("./tests/data/missing.ndx").unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::ErrorOpeningIndexFile(_)));
+}
+
+#[test]
+fn test_read_bogus_memo_length() {
+    // The length of the second block is 0xFFFFFFF0,
+    // which must not be allocated
+    let mut reader = Reader::from_path(BOGUS_MEMO_LENGTH_DBF).unwrap();
+    let results = reader.iter_records().collect::<Vec<_>>();
+    assert_eq!(
+        results[0].as_ref().unwrap().get("NOTES"),
+        Some(&FieldValue::Memo("Intact note".to_owned()))
+    );
+    match results[1].as_ref().unwrap_err().kind() {
+        ErrorKind::TruncatedMemoFile {
+            index,
+            needed_offset,
+            file_len,
+        } => assert_eq!((*index, *needed_offset, *file_len), (9, 4294967864, 640)),
+        kind => panic!("Unexpected error kind {:?}", kind),
+    }
+
+    let options = ReadingOptions::default().max_memo_length(1024);
+    let mut reader = Reader::from_path_with_options(BOGUS_MEMO_LENGTH_DBF, options).unwrap();
+    let results = reader.iter_records().collect::<Vec<_>>();
+    assert!(results[0].is_ok());
+    match results[1].as_ref().unwrap_err().kind() {
+        ErrorKind::MemoTooLong { index, length, max } => {
+            assert_eq!((*index, *length, *max), (9, 0xFFFFFFF0, 1024))
+        }
+        kind => panic!("Unexpected error kind {:?}", kind),
+    }
+}
+
+#[test]
+fn test_detect_dialect() {
+    let dialect = |path| Reader::from_path(path).unwrap().detect_dialect();
+    assert_eq!(dialect(LINE_DBF), Dialect::DbaseIII);
+    assert_eq!(dialect(BINARY_JULIAN_DATE_DBF), Dialect::DbaseIV);
+    assert_eq!(dialect(GENERAL_DBF), Dialect::VisualFoxPro);
+    // A dBase III file with Float fields
+    assert_eq!(dialect(NONE_FLOAT_DBF), Dialect::DbaseIV);
+    // FoxPro 2 files, with and without Visual FoxPro field types
+    assert_eq!(dialect(TRUNCATED_MEMO_DBF), Dialect::DbaseIII);
+    assert_eq!(dialect(BIG_ENDIAN_BINARY_FIELDS_DBF), Dialect::VisualFoxPro);
+
+    let dbf = std::fs::read(LINE_DBF).unwrap();
+    let reader = Reader::new_with_memo(
+        Cursor::new(dbf),
+        Cursor::new(vec![0u8; 512]),
+        MemoFileType::DbaseMemo4,
+    )
+    .unwrap();
+    assert_eq!(reader.detect_dialect(), Dialect::DbaseIV);
+}
+
+#[test]
+fn test_date_and_time_ordering() {
+    assert!(Date::new(2, 1, 2019) > Date::new(1, 1, 2019));
+    // The year is compared first, then the month, then the day
+    assert!(Date::new(31, 12, 2018) < Date::new(1, 1, 2019));
+    assert!(Date::new(1, 2, 2019) > Date::new(28, 1, 2019));
+    assert!(Time::new(10, 0, 0) > Time::new(9, 59, 59));
+
+    let noon = Time::new(12, 0, 0);
+    let datetimes = vec![
+        DateTime::new(Date::new(2, 1, 2019), Time::new(0, 0, 1)),
+        DateTime::new(Date::new(1, 1, 2019), Time::new(23, 59, 59)),
+        DateTime::new(Date::new(1, 1, 2019), noon),
+    ];
+    let mut sorted = datetimes.clone();
+    sorted.sort();
+    assert_eq!(sorted, vec![datetimes[2], datetimes[1], datetimes[0]]);
+    assert_eq!(datetimes.iter().max(), Some(&datetimes[0]));
+
+    let mut dates = vec![Date::new(3, 5, 2020), Date::new(1, 1, 1990)];
+    dates.sort();
+    assert_eq!(dates, vec![Date::new(1, 1, 1990), Date::new(3, 5, 2020)]);
+}
+
+#[test]
+fn test_parse_date() {
+    // Years before 1900 are valid
+    assert_eq!("18500704".parse::<Date>().unwrap(), Date::new(4, 7, 1850));
+    assert_eq!("20000229".parse::<Date>().unwrap(), Date::new(29, 2, 2000));
+    for invalid in &["19000229", "20191301", "20190000", "20190431"] {
+        assert!(
+            matches!(invalid.parse::<Date>(), Err(ErrorKind::InvalidDate)),
+            "{}",
+            invalid
+        );
+    }
+    assert!(matches!(
+        "2019ab01".parse::<Date>(),
+        Err(ErrorKind::ParseIntError(_))
+    ));
+    // Too short, or with multi-byte characters that
+    // would make slicing the string panic
+    for malformed in &["201", "", "20190é1"] {
+        assert!(
+            matches!(malformed.parse::<Date>(), Err(ErrorKind::InvalidDate)),
+            "{:?}",
+            malformed
+        );
+    }
+}
+
+#[test]
+fn test_read_zero_filled_date() {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let writer = TableWriterBuilder::new()
+        .add_date_field("Born".try_into().unwrap())
+        .build_with_dest(&mut cursor);
+    let mut record = Record::default();
+    record.insert("Born".to_owned(), FieldValue::Date(None));
+    writer.write_records(&[record]).unwrap();
+
+    // Some writers fill empty dates with zeros instead of spaces
+    let mut bytes = cursor.into_inner();
+    let first_record = usize::from(u16::from_le_bytes([bytes[8], bytes[9]]));
+    bytes[first_record + 1..first_record + 9].copy_from_slice(b"00000000");
+    let records = Reader::new(Cursor::new(bytes)).unwrap().read().unwrap();
+    assert_eq!(records[0].get("Born"), Some(&FieldValue::Date(None)));
+}
+
+#[test]
+fn test_read_columnar() {
+    let records = dbase::read(STATIONS_DBF).unwrap();
+    let mut reader = Reader::from_path(STATIONS_DBF).unwrap();
+    let columns = reader.read_columnar().unwrap();
+    assert_eq!(columns.num_records(), records.len());
+    assert_eq!(columns.iter().count(), records[0].as_ref().len());
+
+    // Each cell is the value of the row based reading
+    for (name, column) in columns.iter() {
+        assert_eq!(column.len(), records.len());
+        for (i, record) in records.iter().enumerate() {
+            assert_eq!(record.get(name).cloned(), column.get(i));
+        }
+    }
+    match columns.get("name") {
+        Some(Column::Character(names)) => {
+            assert_eq!(names[0].as_deref(), Some("Van Dorn Street"));
+            assert_eq!(names[5].as_deref(), Some("Metro Center"));
+        }
+        column => panic!("Unexpected column {:?}", column),
+    }
+    assert!(columns.get("not_a_field").is_none());
+
+    // Binary fields have their own column type
+    let mut reader = Reader::from_path(NULL_BINARY_FIELDS_DBF).unwrap();
+    let columns = reader.read_columnar().unwrap();
+    assert_eq!(
+        columns.get("COUNT"),
+        Some(&Column::Integer(vec![Some(10), None, Some(30)]))
+    );
+}
+
+#[test]
+fn test_iter_records_lazily() {
+    // Only the first record and part of the second one are in the file:
+    // the records are read one at a time, the first one is still returned
+    let bytes = std::fs::read(STATIONS_DBF).unwrap();
+    let reader = Reader::new(Cursor::new(bytes.clone())).unwrap();
+    let first_record_end =
+        reader.header().offset_to_first_record as usize + reader.header().size_of_record as usize;
+    let mut reader = Reader::new(Cursor::new(bytes[..first_record_end + 10].to_vec())).unwrap();
+    let mut records = reader.iter_records();
+    assert_eq!(
+        records.next().unwrap().unwrap().get("name"),
+        Some(&FieldValue::Character(Some("Van Dorn Street".to_owned())))
+    );
+    assert!(records.next().unwrap().is_err());
+
+    // The iteration stops at the end of file marker,
+    // even if the header announces more records
+    let mut bytes = bytes[..first_record_end].to_vec();
+    bytes.push(0x1A);
+    let mut reader = Reader::new(Cursor::new(bytes)).unwrap();
+    assert_eq!(reader.header().num_records, 6);
+    assert_eq!(reader.iter_records().count(), 1);
+}
+
+#[test]
+fn test_write_columnar() {
+    let mut columns = dbase::Columns::default();
+    columns.insert(
+        "Name".to_owned(),
+        Column::Character(vec![
+            Some("Ferris".to_owned()),
+            None,
+            Some("Yoshi".to_owned()),
+        ]),
+    );
+    columns.insert(
+        "Age".to_owned(),
+        Column::Numeric(vec![Some(12.0), Some(3.0), None]),
+    );
+    columns.insert(
+        "Alive".to_owned(),
+        Column::Logical(vec![Some(true), None, Some(false)]),
+    );
+
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    {
+        let mut writer = TableWriterBuilder::new()
+            .add_character_field("Name".try_into().unwrap(), 10)
+            .add_numeric_field("Age".try_into().unwrap(), 3, 0)
+            .add_logical_field("Alive".try_into().unwrap())
+            .add_date_field("Birth".try_into().unwrap())
+            .build_with_dest(&mut cursor);
+        writer.write_columnar(&columns).unwrap();
+        writer.finish().unwrap();
+    }
+
+    cursor.set_position(0);
+    let mut reader = Reader::new(cursor).unwrap();
+    let records = reader.read().unwrap();
+    assert_eq!(records.len(), 3);
+    for (i, record) in records.iter().enumerate() {
+        for (name, column) in columns.iter() {
+            assert_eq!(record.get(name).cloned(), column.get(i));
+        }
+        // The fields without a column are written empty
+        assert_eq!(record.get("Birth"), Some(&FieldValue::Date(None)));
+    }
+
+    // The columns must have the same length
+    columns.insert("Age".to_owned(), Column::Numeric(vec![Some(12.0)]));
+    let mut writer = TableWriterBuilder::new()
+        .add_character_field("Name".try_into().unwrap(), 10)
+        .add_numeric_field("Age".try_into().unwrap(), 3, 0)
+        .add_logical_field("Alive".try_into().unwrap())
+        .build_with_dest(Cursor::new(Vec::<u8>::new()));
+    let error = writer.write_columnar(&columns).unwrap_err();
+    match error.kind() {
+        ErrorKind::ColumnLengthMismatch {
+            field_name,
+            expected,
+            actual,
+        } => {
+            assert_eq!(field_name, "Age");
+            assert_eq!((*expected, *actual), (3, 1));
+        }
+        kind => panic!("Unexpected error {:?}", kind),
+    }
+}
+
+#[test]
+fn test_currency_stored_as_scaled_integer() {
+    let mut cursor = Cursor::new(Vec::<u8>::new());
+    let mut writer = TableWriterBuilder::new()
+        .add_currency_field(FieldName::try_from("Price").unwrap())
+        .build_with_dest(&mut cursor);
+    let mut record = Record::default();
+    record.insert("Price".to_owned(), FieldValue::Currency(Some(4567.134)));
+    writer.write_record(&record).unwrap();
+    writer.finish().unwrap();
+
+    // The single record ends with the 8 bytes of the currency,
+    // followed by the end of file marker
+    let mut bytes = cursor.into_inner();
+    let start = bytes.len() - 1 - 8;
+    assert_eq!(&bytes[start..start + 8], &45_671_340i64.to_le_bytes());
+
+    bytes[start..start + 8].copy_from_slice(&123_456_789i64.to_le_bytes());
+    let records = dbase::Reader::new(Cursor::new(bytes))
+        .unwrap()
+        .read()
+        .unwrap();
+    assert_eq!(
+        records[0].get("Price"),
+        Some(&FieldValue::Currency(Some(12345.6789)))
+    );
+
+    for &value in &[f64::NAN, f64::INFINITY, 1e300] {
+        let mut writer = TableWriterBuilder::new()
+            .add_currency_field(FieldName::try_from("Price").unwrap())
+            .build_with_dest(Cursor::new(Vec::<u8>::new()));
+        let mut record = Record::default();
+        record.insert("Price".to_owned(), FieldValue::Currency(Some(value)));
+        let error = writer.write_record(&record).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::CurrencyOutOfRange(_)));
+    }
+}